@@ -10,7 +10,8 @@
 //! Game Hub contract. Games cannot be started or completed without points involvement.
 
 use soroban_sdk::{
-    Address, Bytes, BytesN, Env, IntoVal, contract, contractclient, contracterror, contractimpl, contracttype, vec
+    Address, Bytes, BytesN, Env, IntoVal, Vec, contract, contractclient, contracterror, contractimpl, contracttype, vec,
+    address_payload::AddressPayload,
 };
 
 // Import GameHub contract interface
@@ -67,12 +68,92 @@ pub enum Error {
     BoardCommitAlreadySet = 11,
     BoardCommitNotSet = 12,
     BoardCommitMismatch = 13,
+    InvalidOutcome = 14,
+    PlayerKeyMismatch = 15,
+    BoardNotRevealed = 16,
+    InvalidBoard = 17,
+    InvalidMoveLog = 18,
+    InvalidPlayerCount = 19,
+    InvalidRanking = 20,
+    InvalidTeam = 21,
+    InvalidJournal = 22,
+    RevealDeadlinePassed = 23,
+    RevealDeadlineNotReached = 24,
+    BoardRevealed = 25,
+    RulesetMismatch = 26,
+    RulesNotReplayable = 27,
+    ClaimPending = 28,
+    NoPendingClaim = 29,
+    SessionIdOverflow = 30,
+    HubSessionTaken = 31,
 }
 
 // ============================================================================
 // Data Types
 // ============================================================================
 
+/// How a settled match ended, as reported by the guest journal or found by
+/// `settle_revealed`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    /// No result submitted yet (also the state of games settled by `reveal_winner`).
+    Pending,
+    /// The winner sank every opposing ship.
+    SunkAll,
+    /// The loser resigned.
+    Resigned,
+    /// The loser did not reveal their board before the deadline of the winner's `claim_win`.
+    Forfeited,
+}
+
+/// The rules a match is played under, as the guest journal commits them
+/// (`battleship_rules::PublicOutput`). The default, all zero, is the demo ruleset.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Ruleset {
+    /// `battleship_rules::GameMode` as its variant index: 0 = demo, 1 = classic.
+    pub mode: u32,
+    pub no_touching: bool,
+    pub salvo: bool,
+    pub hit_keeps_turn: bool,
+    pub bombs: u32,
+    pub line_strikes: u32,
+    pub sonar_pings: u32,
+    pub mines: u32,
+    /// `battleship_rules::MinePenalty` as its variant index: 0 = skip turn, 1 = reveal cell.
+    pub mine_penalty: u32,
+}
+
+/// A pending `claim_win`: the claimant's signed move log up to their winning shot.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevealClaim {
+    pub player: Address,
+    pub total_moves: u32,
+    /// Chained hash after the last claimed move, which a `contest_claim` log must extend.
+    pub last_hash: BytesN<32>,
+    /// Last ledger on which the opponent may still reveal or contest.
+    pub deadline: u32,
+}
+
+/// Whether a game has a pending `claim_win`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClaimState {
+    Unclaimed,
+    Pending(RevealClaim),
+}
+
+impl ClaimState {
+    fn pending(&self) -> Option<RevealClaim> {
+        match self {
+            Self::Unclaimed => None,
+            Self::Pending(claim) => Some(claim.clone()),
+        }
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Game {
@@ -80,26 +161,83 @@ pub struct Game {
     pub player2: Address,
     pub player1_points: i128,
     pub player2_points: i128,
+    /// Agreed by both players in `start_game_with_rules`; the demo ruleset for `start_game`.
+    pub ruleset: Ruleset,
     pub player1_guess: Option<u32>,
     pub player2_guess: Option<u32>,
     pub winning_number: Option<u32>,
     pub winner: Option<Address>,
     pub total_moves: Option<u32>,
+    pub outcome: Outcome,
     pub board_hash_p1: Option<BytesN<32>>,
     pub board_hash_p2: Option<BytesN<32>>,
     pub board_commit_p1: Option<BytesN<32>>,
     pub board_commit_p2: Option<BytesN<32>>,
     pub journal_hash: Option<BytesN<32>>,
     pub seal_hash: Option<BytesN<32>>,
+    /// Boards opened with `reveal_board`, as cell bitmaps (bit `y * 4 + x` = ship).
+    pub revealed_board_p1: Option<u32>,
+    pub revealed_board_p2: Option<u32>,
+    pub reveal_claim: ClaimState,
+}
+
+/// One signed shot of a move log replayed by `settle_revealed`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevealedShot {
+    pub x: u32,
+    pub y: u32,
+    /// The shooter's ed25519 signature over the move, as in the zk guest.
+    pub signature: BytesN<64>,
+}
+
+/// A three- or four-player free-for-all, settled as one Game Hub session per pair of
+/// players (see `start_free_for_all`).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FreeForAllGame {
+    /// Players in seat order: `players[0]` is seat 1 of the guest input.
+    pub players: Vec<Address>,
+    pub points: Vec<i128>,
+    pub board_commits: Vec<Option<BytesN<32>>>,
+    /// Seats from first to last place; empty until settled.
+    pub ranking: Vec<u32>,
+    pub total_moves: Option<u32>,
+    pub journal_hash: Option<BytesN<32>>,
+    pub seal_hash: Option<BytesN<32>>,
+}
+
+/// A 2v2 team game, settled as two Game Hub sessions between opposing players (see
+/// `start_team_game`).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TeamGame {
+    /// Players in seat order: seats 1 and 3 form team 1, seats 2 and 4 team 2.
+    pub players: Vec<Address>,
+    pub points: Vec<i128>,
+    /// One shared board commitment per team.
+    pub board_commits: Vec<Option<BytesN<32>>>,
+    /// The winning team, 1 or 2.
+    pub winner: Option<u32>,
+    pub total_moves: Option<u32>,
+    pub journal_hash: Option<BytesN<32>>,
+    pub seal_hash: Option<BytesN<32>>,
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     Game(u32),
+    FreeForAll(u32),
+    Team(u32),
+    /// A Game Hub session id in use by one of this contract's games.
+    HubSession(u32),
     GameHubAddress,
     VerifierAddress,
     VerifierImageId,
+    FreeForAllImageId,
+    TeamImageId,
+    AggregateImageId,
     Admin,
 }
 
@@ -113,6 +251,535 @@ pub enum DataKey {
 /// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
 const GAME_TTL_LEDGERS: u32 = 518_400;
 
+/// The master ed25519 key of an account (G...) address; `None` for contract addresses.
+///
+/// Players sign their moves with this key off-chain. It is not used for authorization, so
+/// accounts whose master key is not a signer still work as long as they hold it.
+fn account_key(address: &Address) -> Option<BytesN<32>> {
+    match address.to_payload()? {
+        AddressPayload::AccountIdPublicKeyEd25519(key) => Some(key),
+        AddressPayload::ContractIdHash(_) => None,
+    }
+}
+
+/// Reads a journal as a RISC0 guest commits it (`env::commit`, risc0 serde): every value
+/// is one or more little-endian `u32` words, with `u8`s and bools widened to a word each,
+/// so a `[u8; 32]` hash takes 32 words. Vectors start with their length and options with
+/// a 0/1 tag; unit enums are their variant index.
+struct JournalReader<'a> {
+    env: &'a Env,
+    journal: &'a Bytes,
+    offset: u32,
+}
+
+impl<'a> JournalReader<'a> {
+    fn new(env: &'a Env, journal: &'a Bytes) -> Self {
+        Self { env, journal, offset: 0 }
+    }
+
+    fn skip(&mut self, words: u32) -> Result<(), Error> {
+        let end = words
+            .checked_mul(4)
+            .and_then(|len| self.offset.checked_add(len))
+            .filter(|&end| end <= self.journal.len())
+            .ok_or(Error::InvalidJournal)?;
+        self.offset = end;
+        Ok(())
+    }
+
+    fn word(&mut self) -> Result<u32, Error> {
+        let start = self.offset;
+        self.skip(1)?;
+        let mut word = [0u8; 4];
+        self.journal.slice(start..self.offset).copy_into_slice(&mut word);
+        Ok(u32::from_le_bytes(word))
+    }
+
+    fn byte(&mut self) -> Result<u8, Error> {
+        u8::try_from(self.word()?).map_err(|_| Error::InvalidJournal)
+    }
+
+    fn hash(&mut self) -> Result<BytesN<32>, Error> {
+        let mut hash = [0u8; 32];
+        for byte in hash.iter_mut() {
+            *byte = self.byte()?;
+        }
+        Ok(BytesN::from_array(self.env, &hash))
+    }
+
+    /// A `Vec` of hashes: its length, then each hash.
+    fn hashes(&mut self) -> Result<Vec<BytesN<32>>, Error> {
+        let mut hashes = Vec::new(self.env);
+        for _ in 0..self.word()? {
+            hashes.push_back(self.hash()?);
+        }
+        Ok(hashes)
+    }
+
+    fn flag(&mut self) -> Result<bool, Error> {
+        match self.word()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::InvalidJournal),
+        }
+    }
+
+    /// The tag of an `Option`: whether a value follows.
+    fn is_some(&mut self) -> Result<bool, Error> {
+        self.flag()
+    }
+
+    /// Fails unless every byte of the journal was read.
+    fn finish(self) -> Result<(), Error> {
+        if self.offset != self.journal.len() {
+            return Err(Error::InvalidJournal);
+        }
+        Ok(())
+    }
+}
+
+/// What `submit_result` settles from the play guest's journal (`battleship_rules::PublicOutput`).
+struct MatchJournal {
+    session_id: u32,
+    winner: u32,
+    /// `battleship_rules::Outcome` as its variant index: 1 = all sunk, 2 = resigned.
+    outcome: u32,
+    board_hashes: [BytesN<32>; 2],
+    total_moves: u32,
+    ruleset: Ruleset,
+    /// The keys every move was verified against; `None` for an unsigned game.
+    player_keys: Option<[BytesN<32>; 2]>,
+}
+
+impl MatchJournal {
+    fn decode(env: &Env, journal: &Bytes) -> Result<Self, Error> {
+        let mut reader = JournalReader::new(env, journal);
+        let session_id = reader.word()?;
+        let winner = reader.word()?;
+        let outcome = reader.word()?;
+        let board_hashes = [reader.hash()?, reader.hash()?];
+        // transcript_hash
+        reader.skip(32)?;
+        let total_moves = reader.word()?;
+        let mut ruleset = Ruleset {
+            mode: reader.word()?,
+            no_touching: reader.flag()?,
+            salvo: reader.flag()?,
+            hit_keeps_turn: reader.flag()?,
+            bombs: reader.word()?,
+            line_strikes: reader.word()?,
+            sonar_pings: reader.word()?,
+            ..Ruleset::default()
+        };
+        // stats_p1, stats_p2 (4 each)
+        reader.skip(2 * 4)?;
+        // sonar: move_index, player, x, y, ship_cells per ping
+        let pings = reader.word()?;
+        reader.skip(pings.checked_mul(5).ok_or(Error::InvalidJournal)?)?;
+        ruleset.mines = reader.word()?;
+        ruleset.mine_penalty = reader.word()?;
+        // mine_hits: move_index, player, x, y, then the optional revealed cell
+        for _ in 0..reader.word()? {
+            reader.skip(4)?;
+            if reader.is_some()? {
+                reader.skip(2)?;
+            }
+        }
+        let player_keys = if reader.is_some()? {
+            Some([reader.hash()?, reader.hash()?])
+        } else {
+            None
+        };
+        reader.finish()?;
+        Ok(Self {
+            session_id,
+            winner,
+            outcome,
+            board_hashes,
+            total_moves,
+            ruleset,
+            player_keys,
+        })
+    }
+}
+
+/// What `submit_free_for_all_result` settles from the free-for-all guest's journal
+/// (`battleship_rules::free_for_all::FreeForAllOutput`).
+struct FreeForAllJournal {
+    session_id: u32,
+    players: u32,
+    /// Seats (1-based) from first to last place.
+    ranking: Vec<u32>,
+    board_hashes: Vec<BytesN<32>>,
+    total_moves: u32,
+    player_keys: Option<Vec<BytesN<32>>>,
+}
+
+impl FreeForAllJournal {
+    fn decode(env: &Env, journal: &Bytes) -> Result<Self, Error> {
+        let mut reader = JournalReader::new(env, journal);
+        let session_id = reader.word()?;
+        let players = reader.word()?;
+        // winner, outcome
+        reader.skip(2)?;
+        let mut ranking = Vec::new(env);
+        for _ in 0..reader.word()? {
+            ranking.push_back(reader.word()?);
+        }
+        let board_hashes = reader.hashes()?;
+        // transcript_hash
+        reader.skip(32)?;
+        let total_moves = reader.word()?;
+        // mode, no_touching
+        reader.skip(2)?;
+        // stats: ships_sunk, hits, misses, accuracy_bps per player
+        let stats = reader.word()?;
+        reader.skip(stats.checked_mul(4).ok_or(Error::InvalidJournal)?)?;
+        let player_keys = if reader.is_some()? {
+            Some(reader.hashes()?)
+        } else {
+            None
+        };
+        reader.finish()?;
+        Ok(Self {
+            session_id,
+            players,
+            ranking,
+            board_hashes,
+            total_moves,
+            player_keys,
+        })
+    }
+}
+
+/// What `submit_team_result` settles from the teams guest's journal
+/// (`battleship_rules::teams::TeamOutput`).
+struct TeamJournal {
+    session_id: u32,
+    winner: u32,
+    board_hashes: Vec<BytesN<32>>,
+    total_moves: u32,
+    player_keys: Option<Vec<BytesN<32>>>,
+}
+
+impl TeamJournal {
+    fn decode(env: &Env, journal: &Bytes) -> Result<Self, Error> {
+        let mut reader = JournalReader::new(env, journal);
+        let session_id = reader.word()?;
+        let winner = reader.word()?;
+        // outcome
+        reader.skip(1)?;
+        let board_hashes = Vec::from_array(env, [reader.hash()?, reader.hash()?]);
+        // transcript_hash
+        reader.skip(32)?;
+        let total_moves = reader.word()?;
+        // mode, no_touching, then ships_sunk, hits, misses, accuracy_bps per team
+        reader.skip(2 + 2 * 4)?;
+        let player_keys = if reader.is_some()? {
+            let mut keys = Vec::new(env);
+            for _ in 0..TEAM_PLAYERS {
+                keys.push_back(reader.hash()?);
+            }
+            Some(keys)
+        } else {
+            None
+        };
+        reader.finish()?;
+        Ok(Self {
+            session_id,
+            winner,
+            board_hashes,
+            total_moves,
+            player_keys,
+        })
+    }
+}
+
+const MIN_FREE_FOR_ALL_PLAYERS: u32 = 3;
+const MAX_FREE_FOR_ALL_PLAYERS: u32 = 4;
+
+/// Every pair of seats `(i, j)` with `i < j`, in the order their Game Hub sessions are
+/// numbered: the k-th pair plays hub session `session_id + k`.
+fn duels(players: u32) -> impl Iterator<Item = (u32, u32)> {
+    (0..players).flat_map(move |i| (i + 1..players).map(move |j| (i, j)))
+}
+
+/// The Game Hub session `offset` ids after `session_id`, as free-for-all duels and team
+/// pairings number theirs; `SessionIdOverflow` past `u32::MAX`.
+fn hub_session(session_id: u32, offset: u32) -> Result<u32, Error> {
+    session_id
+        .checked_add(offset)
+        .ok_or(Error::SessionIdOverflow)
+}
+
+/// Marks a Game Hub session id as used, failing with `HubSessionTaken` if another game of
+/// this contract already uses it: two-player games, free-for-all duels and team pairings
+/// all draw on the same hub ids.
+fn reserve_hub_session(env: &Env, hub_session: u32) -> Result<(), Error> {
+    let key = DataKey::HubSession(hub_session);
+    if env.storage().temporary().has(&key) {
+        return Err(Error::HubSessionTaken);
+    }
+    env.storage().temporary().set(&key, &true);
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+    Ok(())
+}
+
+const TEAM_PLAYERS: u32 = 4;
+
+/// The seats (0-based) of team `team` (1 or 2): its first member sits at `team - 1`, its
+/// second two seats later.
+fn team_seats(team: u32) -> [u32; 2] {
+    [team - 1, team + 1]
+}
+
+/// Side length of the demo board, the only ruleset `settle_revealed` replays.
+const REVEAL_BOARD_SIZE: u32 = 4;
+
+/// Ship cells on a demo board: two size-2 ships.
+const REVEAL_SHIP_CELLS: u32 = 4;
+
+/// Ledgers (~1 day at 5 s) the opponent of a `claim_win` has to reveal or contest.
+const REVEAL_WINDOW_LEDGERS: u32 = 17_280;
+
+/// Cell value of a mine in board commitments (`battleship_rules::MINE`); water is 0 and a
+/// ship cell 1.
+const MINE_CELL: u32 = 2;
+
+/// Merkle leaf of one board cell: `sha256(0x00 || index || cell || salt)`, with a
+/// big-endian `u32` index and the cell value as one byte. Mirrors `battleship_rules::merkle`.
+fn leaf_hash(env: &Env, index: u32, cell: u8, salt: &BytesN<32>) -> BytesN<32> {
+    let mut leaf = Bytes::from_array(env, &[0]);
+    leaf.extend_from_array(&index.to_be_bytes());
+    leaf.push_back(cell);
+    leaf.append(&salt.clone().into());
+    env.crypto().sha256(&leaf).into()
+}
+
+/// Merkle node over two children: `sha256(0x01 || left || right)`.
+fn node_hash(env: &Env, left: BytesN<32>, right: BytesN<32>) -> BytesN<32> {
+    let mut node = Bytes::from_array(env, &[1]);
+    node.append(&left.into());
+    node.append(&right.into());
+    env.crypto().sha256(&node).into()
+}
+
+/// Root of the board Merkle tree implied by one opened cell, the bits of `index` saying
+/// which side the running hash sits on at each level.
+fn opening_root(
+    env: &Env,
+    index: u32,
+    cell: u8,
+    salt: &BytesN<32>,
+    siblings: &Vec<BytesN<32>>,
+) -> BytesN<32> {
+    let mut hash = leaf_hash(env, index, cell, salt);
+    let mut position = index;
+    for sibling in siblings.iter() {
+        hash = if position & 1 == 0 {
+            node_hash(env, hash, sibling)
+        } else {
+            node_hash(env, sibling, hash)
+        };
+        position >>= 1;
+    }
+    hash
+}
+
+/// Root of the Merkle tree over a whole demo board (16 cells, so no padding), each cell
+/// salted with `sha256(board_salt || index)`.
+fn board_root(env: &Env, board: u32, board_salt: &BytesN<32>) -> BytesN<32> {
+    let cells = REVEAL_BOARD_SIZE * REVEAL_BOARD_SIZE;
+    let mut level: Vec<BytesN<32>> = Vec::new(env);
+    for index in 0..cells {
+        let mut salt = Bytes::from_array(env, &board_salt.to_array());
+        salt.extend_from_array(&index.to_be_bytes());
+        let salt: BytesN<32> = env.crypto().sha256(&salt).into();
+        let cell = u8::from(board & (1 << index) != 0);
+        level.push_back(leaf_hash(env, index, cell, &salt));
+    }
+    while level.len() > 1 {
+        let mut parents = Vec::new(env);
+        for pair in 0..level.len() / 2 {
+            parents.push_back(node_hash(
+                env,
+                level.get_unchecked(2 * pair),
+                level.get_unchecked(2 * pair + 1),
+            ));
+        }
+        level = parents;
+    }
+    level.get_unchecked(0)
+}
+
+/// Parses a revealed demo board: 16 row-major cells, each 0 (water) or 1 (ship), holding
+/// two size-2 ships. Returns it as a cell bitmap.
+fn demo_board(cells: &Bytes) -> Option<u32> {
+    let size = REVEAL_BOARD_SIZE;
+    if cells.len() != size * size {
+        return None;
+    }
+    let mut board = 0u32;
+    for (index, cell) in cells.iter().enumerate() {
+        match cell {
+            0 => {}
+            1 => board |= 1 << index,
+            _ => return None,
+        }
+    }
+    if board.count_ones() != REVEAL_SHIP_CELLS {
+        return None;
+    }
+    // Four ship cells that each touch exactly one other ship cell form two dominoes.
+    for index in 0..size * size {
+        if board & (1 << index) == 0 {
+            continue;
+        }
+        let (x, y) = (index % size, index / size);
+        let neighbours = [
+            x > 0 && board & (1 << (index - 1)) != 0,
+            x + 1 < size && board & (1 << (index + 1)) != 0,
+            y > 0 && board & (1 << (index - size)) != 0,
+            y + 1 < size && board & (1 << (index + size)) != 0,
+        ];
+        if neighbours.iter().filter(|&&touching| touching).count() != 1 {
+            return None;
+        }
+    }
+    Some(board)
+}
+
+/// Fails with `RulesNotReplayable` unless the game is played under the demo ruleset, the
+/// only one `settle_revealed` can replay.
+fn require_demo_rules(game: &Game) -> Result<(), Error> {
+    if game.ruleset != Ruleset::default() {
+        return Err(Error::RulesNotReplayable);
+    }
+    Ok(())
+}
+
+/// The cell bitmap bit a demo shot hits, or `None` off the board.
+fn shot_cell(shot: &RevealedShot) -> Option<u32> {
+    (shot.x < REVEAL_BOARD_SIZE && shot.y < REVEAL_BOARD_SIZE)
+        .then(|| 1 << (shot.y * REVEAL_BOARD_SIZE + shot.x))
+}
+
+/// Checks a demo move log without looking at the boards: players alternate single shots,
+/// player 1 first, and never leave the board or repeat a cell (`InvalidMoveLog`). Each shot
+/// must carry its shooter's signature over the same hash-chained message the zk guest
+/// checks, by the ed25519 key of the player's account; a bad signature aborts the call.
+///
+/// Returns the chained hash after each move.
+fn verify_move_log(
+    env: &Env,
+    session_id: u32,
+    game: &Game,
+    moves: &Vec<RevealedShot>,
+) -> Result<Vec<BytesN<32>>, Error> {
+    let keys = [
+        account_key(&game.player1).ok_or(Error::PlayerKeyMismatch)?,
+        account_key(&game.player2).ok_or(Error::PlayerKeyMismatch)?,
+    ];
+
+    let mut shots = [0u32; 2];
+    let mut hashes = Vec::new(env);
+    let mut previous = BytesN::from_array(env, &[0u8; 32]);
+    for (index, shot) in moves.iter().enumerate() {
+        let index = index as u32;
+        let shooter = (index & 1) as usize;
+        let cell = shot_cell(&shot).ok_or(Error::InvalidMoveLog)?;
+        if shots[shooter] & cell != 0 {
+            return Err(Error::InvalidMoveLog);
+        }
+        shots[shooter] |= cell;
+
+        // session || index || previous hash || player, x, y, action (0 = shot)
+        let mut message = Bytes::new(env);
+        message.extend_from_array(&session_id.to_be_bytes());
+        message.extend_from_array(&index.to_be_bytes());
+        message.append(&previous.clone().into());
+        message.extend_from_array(&[shooter as u8 + 1, shot.x as u8, shot.y as u8, 0]);
+        env.crypto()
+            .ed25519_verify(&keys[shooter], &message, &shot.signature);
+        previous = env.crypto().sha256(&message).into();
+        hashes.push_back(previous.clone());
+    }
+    Ok(hashes)
+}
+
+/// Opens a two-player game once both players have authorized it: starts its Game Hub
+/// session, locking their points, and stores it. Fails with `HubSessionTaken` if another
+/// game already plays hub session `session_id`.
+fn open_game(
+    env: &Env,
+    session_id: u32,
+    player1: Address,
+    player2: Address,
+    points: [i128; 2],
+    ruleset: Ruleset,
+) -> Result<(), Error> {
+    reserve_hub_session(env, session_id)?;
+
+    // Get GameHub address
+    let game_hub_addr: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::GameHubAddress)
+        .expect("GameHub address not set");
+
+    // Create GameHub client
+    let game_hub = GameHubClient::new(env, &game_hub_addr);
+
+    // Call Game Hub to start the session and lock points
+    // This requires THIS contract's authorization (env.current_contract_address())
+    game_hub.start_game(
+        &env.current_contract_address(),
+        &session_id,
+        &player1,
+        &player2,
+        &points[0],
+        &points[1],
+    );
+
+    // Create game (winning_number not set yet - will be generated in reveal_winner)
+    let game = Game {
+        player1,
+        player2,
+        player1_points: points[0],
+        player2_points: points[1],
+        ruleset,
+        player1_guess: None,
+        player2_guess: None,
+        winning_number: None,
+        winner: None,
+        total_moves: None,
+        outcome: Outcome::Pending,
+        board_hash_p1: None,
+        board_hash_p2: None,
+        board_commit_p1: None,
+        board_commit_p2: None,
+        journal_hash: None,
+        seal_hash: None,
+        revealed_board_p1: None,
+        revealed_board_p2: None,
+        reveal_claim: ClaimState::Unclaimed,
+    };
+
+    // Store game in temporary storage with 30-day TTL
+    let game_key = DataKey::Game(session_id);
+    env.storage().temporary().set(&game_key, &game);
+
+    // Set TTL to ensure game is retained for at least 30 days
+    env.storage()
+        .temporary()
+        .extend_ttl(&game_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+
+    // Event emitted by the Game Hub contract (GameStarted)
+    Ok(())
+}
+
 // ============================================================================
 // Contract Definition
 // ============================================================================
@@ -135,7 +802,7 @@ impl ZkBattleshipContract {
             .set(&DataKey::GameHubAddress, &game_hub);
     }
 
-    /// Start a new game between two players with points.
+    /// Start a new game between two players with points, under the demo ruleset.
     /// This creates a session in the Game Hub and locks points before starting the game.
     ///
     /// **CRITICAL:** This method requires authorization from THIS contract (not players).
@@ -164,58 +831,45 @@ impl ZkBattleshipContract {
         player1.require_auth_for_args(vec![&env, session_id.into_val(&env), player1_points.into_val(&env)]);
         player2.require_auth_for_args(vec![&env, session_id.into_val(&env), player2_points.into_val(&env)]);
 
-        // Get GameHub address
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
-
-        // Create GameHub client
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
-
-        // Call Game Hub to start the session and lock points
-        // This requires THIS contract's authorization (env.current_contract_address())
-        game_hub.start_game(
-            &env.current_contract_address(),
-            &session_id,
-            &player1,
-            &player2,
-            &player1_points,
-            &player2_points,
-        );
-
-        // Create game (winning_number not set yet - will be generated in reveal_winner)
-        let game = Game {
-            player1: player1.clone(),
-            player2: player2.clone(),
-            player1_points,
-            player2_points,
-            player1_guess: None,
-            player2_guess: None,
-            winning_number: None,
-            winner: None,
-            total_moves: None,
-            board_hash_p1: None,
-            board_hash_p2: None,
-            board_commit_p1: None,
-            board_commit_p2: None,
-            journal_hash: None,
-            seal_hash: None,
-        };
-
-        // Store game in temporary storage with 30-day TTL
-        let game_key = DataKey::Game(session_id);
-        env.storage().temporary().set(&game_key, &game);
-
-        // Set TTL to ensure game is retained for at least 30 days
-        env.storage()
-            .temporary()
-            .extend_ttl(&game_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+        open_game(&env, session_id, player1, player2, [player1_points, player2_points], Ruleset::default())
+    }
 
-        // Event emitted by the Game Hub contract (GameStarted)
+    /// Start a new game like `start_game`, under any ruleset the guest supports.
+    ///
+    /// Both players authorize the ruleset along with their points, and `submit_result` only
+    /// accepts a journal committing exactly this ruleset (`RulesetMismatch` otherwise). Only
+    /// demo games can be settled without a proof: the reveal path fails with
+    /// `RulesNotReplayable` for anything else.
+    ///
+    /// # Arguments
+    /// * `rules` - The agreed ruleset
+    pub fn start_game_with_rules(
+        env: Env,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+        rules: Ruleset,
+    ) -> Result<(), Error> {
+        if player1 == player2 {
+            panic!("Cannot play against yourself: Player 1 and Player 2 must be different addresses");
+        }
 
-        Ok(())
+        player1.require_auth_for_args(vec![
+            &env,
+            session_id.into_val(&env),
+            player1_points.into_val(&env),
+            rules.clone().into_val(&env),
+        ]);
+        player2.require_auth_for_args(vec![
+            &env,
+            session_id.into_val(&env),
+            player2_points.into_val(&env),
+            rules.clone().into_val(&env),
+        ]);
+
+        open_game(&env, session_id, player1, player2, [player1_points, player2_points], rules)
     }
 
     /// Submit a shot for the current game.
@@ -264,175 +918,1088 @@ impl ZkBattleshipContract {
         // Store updated game in temporary storage
         env.storage().temporary().set(&key, &game);
 
-        // No event emitted - game state can be queried via get_game()
+        // No event emitted - game state can be queried via get_game()
+
+        Ok(())
+    }
+
+
+    /// Store a player's board commitment hash on-chain.
+    ///
+    /// Commit values are enforced during `submit_result` (phase 2).
+    pub fn set_board_commit(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        board_commit: BytesN<32>,
+    ) -> Result<(), Error> {
+        player.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+
+        if player == game.player1 {
+            if game.board_commit_p1.is_some() {
+                return Err(Error::BoardCommitAlreadySet);
+            }
+            game.board_commit_p1 = Some(board_commit);
+        } else if player == game.player2 {
+            if game.board_commit_p2.is_some() {
+                return Err(Error::BoardCommitAlreadySet);
+            }
+            game.board_commit_p2 = Some(board_commit);
+        } else {
+            return Err(Error::NotPlayer);
+        }
+
+        env.storage().temporary().set(&key, &game);
+
+        Ok(())
+    }
+
+    /// Check one opened cell of a player's committed board, without any zk proof.
+    ///
+    /// `index` is the row-major cell index, `cell` the claimed contents (0 water, 1 ship,
+    /// 2 mine; `InvalidBoard` for anything else), `salt` the cell's salt and `siblings` the
+    /// Merkle path from the leaf up, as written by the host's `open-cell` command. Returns
+    /// whether the opening matches the player's board commit.
+    pub fn verify_cell_opening(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        index: u32,
+        cell: u32,
+        salt: BytesN<32>,
+        siblings: Vec<BytesN<32>>,
+    ) -> Result<bool, Error> {
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+
+        let commit = if player == game.player1 {
+            game.board_commit_p1
+        } else if player == game.player2 {
+            game.board_commit_p2
+        } else {
+            return Err(Error::NotPlayer);
+        }
+        .ok_or(Error::BoardCommitNotSet)?;
+
+        if cell > MINE_CELL {
+            return Err(Error::InvalidBoard);
+        }
+
+        Ok(opening_root(&env, index, cell as u8, &salt, &siblings) == commit)
+    }
+
+    /// Open a player's whole board after the match, for settlement without a zk proof.
+    ///
+    /// `cells` are the 16 row-major demo cells (1 = ship) and `board_salt` the secret the
+    /// commitment was salted with; together they must rebuild the player's `board_commit`
+    /// (`BoardCommitMismatch` otherwise) and hold two size-2 ships (`InvalidBoard`). Only
+    /// demo games can be revealed (`RulesNotReplayable`), and only until they are settled.
+    ///
+    /// Revealing starts no deadline by itself; the opponent of a pending `claim_win` can
+    /// reveal until its deadline (`RevealDeadlinePassed` after it).
+    pub fn reveal_board(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        cells: Bytes,
+        board_salt: BytesN<32>,
+    ) -> Result<(), Error> {
+        player.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        require_demo_rules(&game)?;
+
+        let commit = if player == game.player1 {
+            game.board_commit_p1.clone()
+        } else if player == game.player2 {
+            game.board_commit_p2.clone()
+        } else {
+            return Err(Error::NotPlayer);
+        }
+        .ok_or(Error::BoardCommitNotSet)?;
+
+        if game
+            .reveal_claim
+            .pending()
+            .is_some_and(|claim| env.ledger().sequence() > claim.deadline)
+        {
+            return Err(Error::RevealDeadlinePassed);
+        }
+
+        let board = demo_board(&cells).ok_or(Error::InvalidBoard)?;
+        if board_root(&env, board, &board_salt) != commit {
+            return Err(Error::BoardCommitMismatch);
+        }
+
+        if player == game.player1 {
+            game.revealed_board_p1 = Some(board);
+        } else {
+            game.revealed_board_p2 = Some(board);
+        }
+        env.storage().temporary().set(&key, &game);
+
+        Ok(())
+    }
+
+    /// Settle a match without a zk proof by replaying its move log against both revealed
+    /// boards.
+    ///
+    /// This is the transparent fallback to `submit_result` for games under the demo ruleset
+    /// (`RulesNotReplayable` otherwise): players alternate single shots, player 1 first,
+    /// until one side has hit every opposing ship cell. Each shot must carry its shooter's
+    /// signature over the same hash-chained message the zk guest checks, by the ed25519 key
+    /// of the player's account. A bad signature aborts the call; a log that repeats a shot,
+    /// leaves the board, continues past the winning shot or ends without a winner fails with
+    /// `InvalidMoveLog`.
+    pub fn settle_revealed(
+        env: Env,
+        session_id: u32,
+        submitter: Address,
+        moves: Vec<RevealedShot>,
+    ) -> Result<Address, Error> {
+        submitter.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+
+        if submitter != game.player1 && submitter != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        require_demo_rules(&game)?;
+
+        let boards = [
+            game.revealed_board_p1.ok_or(Error::BoardNotRevealed)?,
+            game.revealed_board_p2.ok_or(Error::BoardNotRevealed)?,
+        ];
+
+        if moves.is_empty() {
+            return Err(Error::InvalidTotalMoves);
+        }
+        verify_move_log(&env, session_id, &game, &moves)?;
+
+        let mut shots = [0u32; 2];
+        let mut winner: Option<u32> = None;
+        for (index, shot) in moves.iter().enumerate() {
+            if winner.is_some() {
+                return Err(Error::InvalidMoveLog);
+            }
+            let shooter = index & 1;
+            shots[shooter] |= shot_cell(&shot).ok_or(Error::InvalidMoveLog)?;
+            let target = boards[1 - shooter];
+            if shots[shooter] & target == target {
+                winner = Some(shooter as u32 + 1);
+            }
+        }
+        let winner = winner.ok_or(Error::InvalidMoveLog)?;
+
+        let winner_addr = if winner == 1 {
+            game.player1.clone()
+        } else {
+            game.player2.clone()
+        };
+
+        game.winner = Some(winner_addr.clone());
+        game.total_moves = Some(moves.len());
+        game.outcome = Outcome::SunkAll;
+        game.board_hash_p1 = game.board_commit_p1.clone();
+        game.board_hash_p2 = game.board_commit_p2.clone();
+        env.storage().temporary().set(&key, &game);
+
+        let game_hub_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub address not set");
+        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        game_hub.end_game(&session_id, &(winner == 1));
+
+        Ok(winner_addr)
+    }
+
+    /// Claim a win with the signed move log up to the claimant's winning shot, starting a
+    /// window of `REVEAL_WINDOW_LEDGERS` in which the opponent has to reveal their board or
+    /// contest the claim.
+    ///
+    /// The claimant must have revealed their own board first (`BoardNotRevealed`); once both
+    /// boards are open, `settle_revealed` decides instead (`BoardRevealed`). The log is
+    /// checked like in `settle_revealed`, minus the opponent's board, which is still closed:
+    /// it must end with a shot of the claimant, hold at least as many of their shots as there
+    /// are ship cells, and not already sink the claimant's own board (`InvalidMoveLog`
+    /// otherwise). Only one claim can be pending (`ClaimPending`).
+    pub fn claim_win(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        moves: Vec<RevealedShot>,
+    ) -> Result<(), Error> {
+        player.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        require_demo_rules(&game)?;
+
+        let (seat, own, opponent) = if player == game.player1 {
+            (0, game.revealed_board_p1, game.revealed_board_p2)
+        } else if player == game.player2 {
+            (1, game.revealed_board_p2, game.revealed_board_p1)
+        } else {
+            return Err(Error::NotPlayer);
+        };
+        let own = own.ok_or(Error::BoardNotRevealed)?;
+        if opponent.is_some() {
+            return Err(Error::BoardRevealed);
+        }
+        if game.reveal_claim.pending().is_some() {
+            return Err(Error::ClaimPending);
+        }
+
+        let hashes = verify_move_log(&env, session_id, &game, &moves)?;
+        let total_moves = moves.len();
+        if total_moves == 0 || (total_moves - 1) & 1 != seat {
+            return Err(Error::InvalidMoveLog);
+        }
+        let mut shots = [0u32; 2];
+        for (index, shot) in moves.iter().enumerate() {
+            shots[index & 1] |= shot_cell(&shot).ok_or(Error::InvalidMoveLog)?;
+        }
+        let seat = seat as usize;
+        if shots[seat].count_ones() < REVEAL_SHIP_CELLS || shots[1 - seat] & own == own {
+            return Err(Error::InvalidMoveLog);
+        }
+
+        game.reveal_claim = ClaimState::Pending(RevealClaim {
+            player,
+            total_moves,
+            last_hash: hashes.get_unchecked(total_moves - 1),
+            deadline: env.ledger().sequence() + REVEAL_WINDOW_LEDGERS,
+        });
+        env.storage().temporary().set(&key, &game);
+
+        Ok(())
+    }
+
+    /// Withdraw a pending `claim_win` by showing the game went on past the claimed shot.
+    ///
+    /// `moves` must be a signed log that extends the claimed one, the same moves up to the
+    /// claim's last hash, with at least one more shot by the claimant after it
+    /// (`InvalidMoveLog` otherwise). Only possible before the claim's deadline
+    /// (`RevealDeadlinePassed`); fails with `NoPendingClaim` without a claim.
+    pub fn contest_claim(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        moves: Vec<RevealedShot>,
+    ) -> Result<(), Error> {
+        player.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if player != game.player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        let claim = game.reveal_claim.pending().ok_or(Error::NoPendingClaim)?;
+        if env.ledger().sequence() > claim.deadline {
+            return Err(Error::RevealDeadlinePassed);
+        }
+
+        // Players alternate, so the claimant shoots again two moves after their claimed shot.
+        if moves.len() < claim.total_moves + 2 {
+            return Err(Error::InvalidMoveLog);
+        }
+        let hashes = verify_move_log(&env, session_id, &game, &moves)?;
+        if hashes.get_unchecked(claim.total_moves - 1) != claim.last_hash {
+            return Err(Error::InvalidMoveLog);
+        }
+
+        game.reveal_claim = ClaimState::Unclaimed;
+        env.storage().temporary().set(&key, &game);
+
+        Ok(())
+    }
+
+    /// Win a match whose opponent let the deadline of the caller's `claim_win` pass without
+    /// revealing their board or contesting the claim.
+    ///
+    /// Fails with `NoPendingClaim` unless the caller's claim is pending,
+    /// `RevealDeadlineNotReached` until its deadline has passed and `BoardRevealed` if the
+    /// opponent revealed in time (settle with `settle_revealed`). The game is recorded as
+    /// `Forfeited`, with the claimed log's move count.
+    pub fn claim_reveal_forfeit(env: Env, session_id: u32, player: Address) -> Result<Address, Error> {
+        player.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+
+        let opponent = if player == game.player1 {
+            game.revealed_board_p2
+        } else if player == game.player2 {
+            game.revealed_board_p1
+        } else {
+            return Err(Error::NotPlayer);
+        };
+        let claim = game
+            .reveal_claim
+            .pending()
+            .filter(|claim| claim.player == player)
+            .ok_or(Error::NoPendingClaim)?;
+        if opponent.is_some() {
+            return Err(Error::BoardRevealed);
+        }
+        if env.ledger().sequence() <= claim.deadline {
+            return Err(Error::RevealDeadlineNotReached);
+        }
+
+        let player1_won = player == game.player1;
+        game.winner = Some(player.clone());
+        game.total_moves = Some(claim.total_moves);
+        game.outcome = Outcome::Forfeited;
+        env.storage().temporary().set(&key, &game);
+
+        let game_hub_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub address not set");
+        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        game_hub.end_game(&session_id, &player1_won);
+
+        Ok(player)
+    }
+
+    /// Reveal the winner of the game and submit outcome to GameHub.
+    /// Can only be called after both players have submitted shots.
+    /// This generates the winning number, determines the winner, and ends the session.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    ///
+    /// # Returns
+    /// * `Address` - Address of the winning player
+    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Address, Error> {
+        // Get game from temporary storage
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        // Check if game already ended (has a winner)
+        if let Some(winner) = &game.winner {
+            return Ok(winner.clone());
+        }
+
+        // Check both players submitted shots
+        let guess1 = game.player1_guess.ok_or(Error::BothPlayersNotGuessed)?;
+        let guess2 = game.player2_guess.ok_or(Error::BothPlayersNotGuessed)?;
+
+        // Generate random winning number between 1 and 16 using seeded PRNG
+        // This is done AFTER both players have committed their shots
+        //
+        // Seed components (all deterministic and identical between sim/submit):
+        // 1. Session ID - unique per game, same between simulation and submission
+        // 2. Player addresses - both players contribute, same between sim/submit
+        // 3. Shot cells - committed before reveal, same between sim/submit
+        //
+        // Note: We do NOT include ledger sequence or timestamp because those differ
+        // between simulation and submission, which would cause different winners.
+        //
+        // This ensures:
+        // - Same result between simulation and submission (fully deterministic)
+        // - Cannot be easily gamed (both players contribute to randomness)
+
+        // Build seed more efficiently using native arrays where possible
+        // Total: 12 bytes of fixed data (session_id + 2 shot cells)
+        let mut fixed_data = [0u8; 12];
+        fixed_data[0..4].copy_from_slice(&session_id.to_be_bytes());
+        fixed_data[4..8].copy_from_slice(&guess1.to_be_bytes());
+        fixed_data[8..12].copy_from_slice(&guess2.to_be_bytes());
+
+        // Only use Bytes for the final concatenation with player addresses
+        let mut seed_bytes = Bytes::from_array(&env, &fixed_data);
+        seed_bytes.append(&game.player1.to_string().to_bytes());
+        seed_bytes.append(&game.player2.to_string().to_bytes());
+
+        let seed = env.crypto().keccak256(&seed_bytes);
+        env.prng().seed(seed.into());
+        let winning_number = env.prng().gen_range::<u64>(1..=16) as u32;
+        game.winning_number = Some(winning_number);
+
+        // Calculate distances
+        let distance1 = if guess1 > winning_number {
+            guess1 - winning_number
+        } else {
+            winning_number - guess1
+        };
+
+        let distance2 = if guess2 > winning_number {
+            guess2 - winning_number
+        } else {
+            winning_number - guess2
+        };
+
+        // Determine winner (if equal distance, player1 wins)
+        let winner = if distance1 <= distance2 {
+            game.player1.clone()
+        } else {
+            game.player2.clone()
+        };
+
+        // Update game with winner (this marks the game as ended)
+        game.winner = Some(winner.clone());
+        env.storage().temporary().set(&key, &game);
+
+        // Get GameHub address
+        let game_hub_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub address not set");
+
+        // Create GameHub client
+        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+
+        // Call GameHub to end the session
+        // This unlocks points and updates standings
+        // Event emitted by the Game Hub contract (GameEnded)
+        let player1_won = winner == game.player1; // true if player1 won, false if player2 won
+        game_hub.end_game(&session_id, &player1_won);
+
+        Ok(winner)
+    }
+
+    /// Submit a zk-verified match result and settle the game in Game Hub.
+    ///
+    /// This call performs on-chain proof verification via the configured verifier contract.
+    /// The verifier address and image id must be configured by admin using `set_verifier`
+    /// and `set_image_id` before submissions are accepted. Proofs aggregated from per-turn
+    /// receipts verify under the aggregation guest's id instead, set with
+    /// `set_aggregate_image_id`; with both set, the seal is checked against either.
+    ///
+    /// `outcome` is the journal's reason code: 1 when the winner sank every ship, 2 when
+    /// the loser resigned. It must be the outcome the journal commits, which is what the
+    /// game records (`InvalidOutcome` otherwise); a journal that does not decode as the
+    /// guest's output for this session fails with `InvalidJournal`.
+    ///
+    /// `player_key_p1`/`player_key_p2` are the keys the guest checked every move's signature
+    /// against; they must be the ed25519 keys of the `player1`/`player2` accounts.
+    ///
+    /// The journal must commit the ruleset the game was started with (`RulesetMismatch`).
+    ///
+    /// `winner`, `total_moves`, the board hashes and the player keys must also be what the
+    /// journal commits, failing with the same errors as their other checks. A journal without
+    /// player keys (an unsigned game) fails with `PlayerKeyMismatch`.
+    pub fn submit_result(
+        env: Env,
+        session_id: u32,
+        submitter: Address,
+        winner: u32,
+        outcome: u32,
+        total_moves: u32,
+        board_hash_p1: BytesN<32>,
+        board_hash_p2: BytesN<32>,
+        player_key_p1: BytesN<32>,
+        player_key_p2: BytesN<32>,
+        journal: Bytes,
+        seal: Bytes,
+    ) -> Result<Address, Error> {
+        submitter.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+
+        if submitter != game.player1 && submitter != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+
+        if winner != 1 && winner != 2 {
+            return Err(Error::InvalidWinner);
+        }
+
+        let claimed_outcome = match outcome {
+            1 => Outcome::SunkAll,
+            2 => Outcome::Resigned,
+            _ => return Err(Error::InvalidOutcome),
+        };
+
+        if total_moves == 0 {
+            return Err(Error::InvalidTotalMoves);
+        }
+
+        if journal.len() == 0 || seal.len() == 0 {
+            return Err(Error::InvalidProofMaterial);
+        }
+
+        let proven = MatchJournal::decode(&env, &journal)?;
+        if proven.session_id != session_id {
+            return Err(Error::InvalidJournal);
+        }
+        if proven.ruleset != game.ruleset {
+            return Err(Error::RulesetMismatch);
+        }
+        let outcome = match proven.outcome {
+            1 => Outcome::SunkAll,
+            2 => Outcome::Resigned,
+            _ => return Err(Error::InvalidOutcome),
+        };
+        if outcome != claimed_outcome {
+            return Err(Error::InvalidOutcome);
+        }
+
+        let commit_p1 = game
+            .board_commit_p1
+            .clone()
+            .ok_or(Error::BoardCommitNotSet)?;
+        let commit_p2 = game
+            .board_commit_p2
+            .clone()
+            .ok_or(Error::BoardCommitNotSet)?;
+
+        if commit_p1 != board_hash_p1 || commit_p2 != board_hash_p2 {
+            return Err(Error::BoardCommitMismatch);
+        }
+
+        if account_key(&game.player1) != Some(player_key_p1.clone())
+            || account_key(&game.player2) != Some(player_key_p2.clone())
+        {
+            return Err(Error::PlayerKeyMismatch);
+        }
+
+        // Every argument is only a claim about what the guest proved.
+        if proven.winner != winner {
+            return Err(Error::InvalidWinner);
+        }
+        if proven.total_moves != total_moves {
+            return Err(Error::InvalidTotalMoves);
+        }
+        if proven.board_hashes != [board_hash_p1.clone(), board_hash_p2.clone()] {
+            return Err(Error::BoardCommitMismatch);
+        }
+        if proven.player_keys != Some([player_key_p1, player_key_p2]) {
+            return Err(Error::PlayerKeyMismatch);
+        }
+
+        let verifier_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::VerifierAddress)
+            .ok_or(Error::VerifierNotConfigured)?;
+        let image_id: Option<BytesN<32>> =
+            env.storage().instance().get(&DataKey::VerifierImageId);
+        let aggregate_image_id: Option<BytesN<32>> =
+            env.storage().instance().get(&DataKey::AggregateImageId);
+
+        let verifier = VerifierClient::new(&env, &verifier_addr);
+        let journal_digest: BytesN<32> = env.crypto().sha256(&journal).into();
+        // Whole-match and aggregated proofs commit the same journal, so only the seal tells
+        // which guest it came from.
+        match (image_id, aggregate_image_id) {
+            (Some(image_id), Some(aggregate_image_id)) => {
+                let whole_match = verifier.try_verify(&seal, &image_id, &journal_digest);
+                if !matches!(whole_match, Ok(Ok(()))) {
+                    verifier.verify(&seal, &aggregate_image_id, &journal_digest);
+                }
+            }
+            (Some(image_id), None) | (None, Some(image_id)) => {
+                verifier.verify(&seal, &image_id, &journal_digest);
+            }
+            (None, None) => return Err(Error::VerifierNotConfigured),
+        }
+
+        let journal_hash = env.crypto().keccak256(&journal);
+        let seal_hash = env.crypto().keccak256(&seal);
+
+        let winner_addr = if winner == 1 {
+            game.player1.clone()
+        } else {
+            game.player2.clone()
+        };
+
+        game.winner = Some(winner_addr.clone());
+        game.total_moves = Some(total_moves);
+        game.outcome = outcome;
+        game.board_hash_p1 = Some(board_hash_p1);
+        game.board_hash_p2 = Some(board_hash_p2);
+        game.journal_hash = Some(journal_hash.into());
+        game.seal_hash = Some(seal_hash.into());
+        env.storage().temporary().set(&key, &game);
+
+        let game_hub_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub address not set");
+        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+
+        let player1_won = winner == 1;
+        game_hub.end_game(&session_id, &player1_won);
+
+        Ok(winner_addr)
+    }
+
+    /// Start a free-for-all between three or four players.
+    ///
+    /// The Game Hub only knows two-player sessions, so every pair of players gets its own:
+    /// the k-th pair in `(1, 2), (1, 3), ..., (2, 3), ...` order plays hub session
+    /// `session_id + k`, and each player commits their points to every one of their duels.
+    /// Those hub session ids must not be used by another game of this contract
+    /// (`HubSessionTaken`) nor run past `u32::MAX` (`SessionIdOverflow`).
+    ///
+    /// # Arguments
+    /// * `session_id` - Session of the free-for-all, and of its first duel in the Game Hub
+    /// * `players` - Addresses in seat order
+    /// * `points` - Points each player commits per duel, in seat order
+    pub fn start_free_for_all(
+        env: Env,
+        session_id: u32,
+        players: Vec<Address>,
+        points: Vec<i128>,
+    ) -> Result<(), Error> {
+        let count = players.len();
+        if !(MIN_FREE_FOR_ALL_PLAYERS..=MAX_FREE_FOR_ALL_PLAYERS).contains(&count)
+            || points.len() != count
+        {
+            return Err(Error::InvalidPlayerCount);
+        }
+
+        let key = DataKey::FreeForAll(session_id);
+        if env.storage().temporary().has(&key) {
+            return Err(Error::GameAlreadyEnded);
+        }
+
+        for (i, j) in duels(count) {
+            if players.get_unchecked(i) == players.get_unchecked(j) {
+                panic!("Cannot play against yourself: every player must be a different address");
+            }
+        }
+        for (player, player_points) in players.iter().zip(points.iter()) {
+            player.require_auth_for_args(vec![&env, session_id.into_val(&env), player_points.into_val(&env)]);
+        }
+
+        let game_hub_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub address not set");
+        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        for (duel, (i, j)) in duels(count).enumerate() {
+            let hub_session = hub_session(session_id, duel as u32)?;
+            reserve_hub_session(&env, hub_session)?;
+            game_hub.start_game(
+                &env.current_contract_address(),
+                &hub_session,
+                &players.get_unchecked(i),
+                &players.get_unchecked(j),
+                &points.get_unchecked(i),
+                &points.get_unchecked(j),
+            );
+        }
+
+        let mut board_commits = Vec::new(&env);
+        for _ in 0..count {
+            board_commits.push_back(None);
+        }
+        let game = FreeForAllGame {
+            players,
+            points,
+            board_commits,
+            ranking: Vec::new(&env),
+            total_moves: None,
+            journal_hash: None,
+            seal_hash: None,
+        };
+        env.storage().temporary().set(&key, &game);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Store a free-for-all player's board commitment, as `set_board_commit` does for
+    /// two-player games.
+    pub fn set_free_for_all_commit(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        board_commit: BytesN<32>,
+    ) -> Result<(), Error> {
+        player.require_auth();
+
+        let key = DataKey::FreeForAll(session_id);
+        let mut game: FreeForAllGame = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if !game.ranking.is_empty() {
+            return Err(Error::GameAlreadyEnded);
+        }
+
+        let seat = game.players.first_index_of(&player).ok_or(Error::NotPlayer)?;
+        if game.board_commits.get_unchecked(seat).is_some() {
+            return Err(Error::BoardCommitAlreadySet);
+        }
+        game.board_commits.set(seat, Some(board_commit));
+        env.storage().temporary().set(&key, &game);
+
+        Ok(())
+    }
+
+    /// Submit a zk-verified free-for-all ranking and settle every duel in Game Hub.
+    ///
+    /// The proof must be for the free-for-all guest, whose image id the admin sets with
+    /// `set_free_for_all_image_id`. `ranking` lists every seat (1-based) from first to last
+    /// place; each duel is won by whichever of its two players placed higher.
+    /// `board_hashes` and `player_keys` are in seat order and must match the board commits
+    /// and the players' account keys, as in `submit_result`. The ranking, `total_moves`, the
+    /// hashes and the keys must all be what the journal commits, and a journal without
+    /// player keys fails with `PlayerKeyMismatch`.
+    #[allow(
+        clippy::too_many_arguments,
+        reason = "each argument is a separate claim checked against the journal, as in submit_result"
+    )]
+    pub fn submit_free_for_all_result(
+        env: Env,
+        session_id: u32,
+        submitter: Address,
+        ranking: Vec<u32>,
+        total_moves: u32,
+        board_hashes: Vec<BytesN<32>>,
+        player_keys: Vec<BytesN<32>>,
+        journal: Bytes,
+        seal: Bytes,
+    ) -> Result<Address, Error> {
+        submitter.require_auth();
+
+        let key = DataKey::FreeForAll(session_id);
+        let mut game: FreeForAllGame = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if !game.ranking.is_empty() {
+            return Err(Error::GameAlreadyEnded);
+        }
+
+        if !game.players.contains(&submitter) {
+            return Err(Error::NotPlayer);
+        }
+
+        // Every seat exactly once.
+        let count = game.players.len();
+        let mut place = [0u32; MAX_FREE_FOR_ALL_PLAYERS as usize];
+        if ranking.len() != count {
+            return Err(Error::InvalidRanking);
+        }
+        for (rank, seat) in ranking.iter().enumerate() {
+            if seat == 0 || seat > count || place[seat as usize - 1] != 0 {
+                return Err(Error::InvalidRanking);
+            }
+            place[seat as usize - 1] = rank as u32 + 1;
+        }
+
+        if total_moves == 0 {
+            return Err(Error::InvalidTotalMoves);
+        }
+
+        if journal.is_empty() || seal.is_empty() {
+            return Err(Error::InvalidProofMaterial);
+        }
+
+        if board_hashes.len() != count || player_keys.len() != count {
+            return Err(Error::InvalidPlayerCount);
+        }
+        for seat in 0..count {
+            let commit = game
+                .board_commits
+                .get_unchecked(seat)
+                .ok_or(Error::BoardCommitNotSet)?;
+            if commit != board_hashes.get_unchecked(seat) {
+                return Err(Error::BoardCommitMismatch);
+            }
+            if account_key(&game.players.get_unchecked(seat)) != Some(player_keys.get_unchecked(seat)) {
+                return Err(Error::PlayerKeyMismatch);
+            }
+        }
+
+        // The ranking and everything it is checked against must be what the guest proved.
+        let proven = FreeForAllJournal::decode(&env, &journal)?;
+        if proven.session_id != session_id {
+            return Err(Error::InvalidJournal);
+        }
+        if proven.players != count {
+            return Err(Error::InvalidPlayerCount);
+        }
+        if proven.ranking != ranking {
+            return Err(Error::InvalidRanking);
+        }
+        if proven.total_moves != total_moves {
+            return Err(Error::InvalidTotalMoves);
+        }
+        if proven.board_hashes != board_hashes {
+            return Err(Error::BoardCommitMismatch);
+        }
+        if proven.player_keys != Some(player_keys) {
+            return Err(Error::PlayerKeyMismatch);
+        }
+
+        let verifier_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::VerifierAddress)
+            .ok_or(Error::VerifierNotConfigured)?;
+        let image_id: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::FreeForAllImageId)
+            .ok_or(Error::VerifierNotConfigured)?;
+
+        let verifier = VerifierClient::new(&env, &verifier_addr);
+        let journal_digest: BytesN<32> = env.crypto().sha256(&journal).into();
+        verifier.verify(&seal, &image_id, &journal_digest);
+
+        let journal_hash = env.crypto().keccak256(&journal);
+        let seal_hash = env.crypto().keccak256(&seal);
+
+        let winner = game.players.get_unchecked(ranking.get_unchecked(0) - 1);
+        game.ranking = ranking;
+        game.total_moves = Some(total_moves);
+        game.journal_hash = Some(journal_hash.into());
+        game.seal_hash = Some(seal_hash.into());
+        env.storage().temporary().set(&key, &game);
+
+        let game_hub_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub address not set");
+        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        for (duel, (i, j)) in duels(count).enumerate() {
+            let player1_won = place[i as usize] < place[j as usize];
+            game_hub.end_game(&hub_session(session_id, duel as u32)?, &player1_won);
+        }
 
-        Ok(())
+        Ok(winner)
     }
 
+    /// Get free-for-all information.
+    pub fn get_free_for_all(env: Env, session_id: u32) -> Result<FreeForAllGame, Error> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::FreeForAll(session_id))
+            .ok_or(Error::GameNotFound)
+    }
 
-    /// Store a player's board commitment hash on-chain.
+    /// Start a 2v2 team game.
     ///
-    /// Commit values are enforced during `submit_result` (phase 2).
-    pub fn set_board_commit(
+    /// Seats 1 and 3 play for team 1, seats 2 and 4 for team 2. The Game Hub only knows
+    /// two-player sessions, so each player is matched against the opponent in the next
+    /// seat: seats 1 and 2 play hub session `session_id`, seats 3 and 4 hub session
+    /// `session_id + 1`. Both hub session ids must be free (`HubSessionTaken`) and the
+    /// second must not run past `u32::MAX` (`SessionIdOverflow`). Every player commits
+    /// their own points to their session, so a team's winnings are split between its
+    /// members by what each of them staked.
+    ///
+    /// # Arguments
+    /// * `session_id` - Session of the team game, and of its first pairing in the Game Hub
+    /// * `players` - The four addresses in seat order
+    /// * `points` - Points each player commits, in seat order
+    pub fn start_team_game(
         env: Env,
         session_id: u32,
-        player: Address,
-        board_commit: BytesN<32>,
+        players: Vec<Address>,
+        points: Vec<i128>,
     ) -> Result<(), Error> {
-        player.require_auth();
-
-        let key = DataKey::Game(session_id);
-        let mut game: Game = env
-            .storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)?;
+        if players.len() != TEAM_PLAYERS || points.len() != TEAM_PLAYERS {
+            return Err(Error::InvalidPlayerCount);
+        }
 
-        if game.winner.is_some() {
+        let key = DataKey::Team(session_id);
+        if env.storage().temporary().has(&key) {
             return Err(Error::GameAlreadyEnded);
         }
 
-        if player == game.player1 {
-            if game.board_commit_p1.is_some() {
-                return Err(Error::BoardCommitAlreadySet);
-            }
-            game.board_commit_p1 = Some(board_commit);
-        } else if player == game.player2 {
-            if game.board_commit_p2.is_some() {
-                return Err(Error::BoardCommitAlreadySet);
+        for (i, j) in duels(TEAM_PLAYERS) {
+            if players.get_unchecked(i) == players.get_unchecked(j) {
+                panic!("Cannot play against yourself: every player must be a different address");
             }
-            game.board_commit_p2 = Some(board_commit);
-        } else {
-            return Err(Error::NotPlayer);
+        }
+        for (player, player_points) in players.iter().zip(points.iter()) {
+            player.require_auth_for_args(vec![&env, session_id.into_val(&env), player_points.into_val(&env)]);
+        }
+
+        let game_hub_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub address not set");
+        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        for pairing in 0..2 {
+            let hub_session = hub_session(session_id, pairing)?;
+            reserve_hub_session(&env, hub_session)?;
+            game_hub.start_game(
+                &env.current_contract_address(),
+                &hub_session,
+                &players.get_unchecked(2 * pairing),
+                &players.get_unchecked(2 * pairing + 1),
+                &points.get_unchecked(2 * pairing),
+                &points.get_unchecked(2 * pairing + 1),
+            );
         }
 
+        let game = TeamGame {
+            players,
+            points,
+            board_commits: vec![&env, None, None],
+            winner: None,
+            total_moves: None,
+            journal_hash: None,
+            seal_hash: None,
+        };
         env.storage().temporary().set(&key, &game);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
 
         Ok(())
     }
 
-    /// Reveal the winner of the game and submit outcome to GameHub.
-    /// Can only be called after both players have submitted shots.
-    /// This generates the winning number, determines the winner, and ends the session.
+    /// Store a team's shared board commitment, built from both members' ships. Both
+    /// members must authorize it.
     ///
     /// # Arguments
-    /// * `session_id` - The session ID of the game
-    ///
-    /// # Returns
-    /// * `Address` - Address of the winning player
-    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Address, Error> {
-        // Get game from temporary storage
-        let key = DataKey::Game(session_id);
-        let mut game: Game = env
+    /// * `session_id` - The team game
+    /// * `team` - 1 (seats 1 and 3) or 2 (seats 2 and 4)
+    /// * `board_commit` - Merkle root of the team's combined board and salt
+    pub fn set_team_commit(
+        env: Env,
+        session_id: u32,
+        team: u32,
+        board_commit: BytesN<32>,
+    ) -> Result<(), Error> {
+        if !(1..=2).contains(&team) {
+            return Err(Error::InvalidTeam);
+        }
+
+        let key = DataKey::Team(session_id);
+        let mut game: TeamGame = env
             .storage()
             .temporary()
             .get(&key)
             .ok_or(Error::GameNotFound)?;
 
-        // Check if game already ended (has a winner)
-        if let Some(winner) = &game.winner {
-            return Ok(winner.clone());
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
         }
 
-        // Check both players submitted shots
-        let guess1 = game.player1_guess.ok_or(Error::BothPlayersNotGuessed)?;
-        let guess2 = game.player2_guess.ok_or(Error::BothPlayersNotGuessed)?;
-
-        // Generate random winning number between 1 and 16 using seeded PRNG
-        // This is done AFTER both players have committed their shots
-        //
-        // Seed components (all deterministic and identical between sim/submit):
-        // 1. Session ID - unique per game, same between simulation and submission
-        // 2. Player addresses - both players contribute, same between sim/submit
-        // 3. Shot cells - committed before reveal, same between sim/submit
-        //
-        // Note: We do NOT include ledger sequence or timestamp because those differ
-        // between simulation and submission, which would cause different winners.
-        //
-        // This ensures:
-        // - Same result between simulation and submission (fully deterministic)
-        // - Cannot be easily gamed (both players contribute to randomness)
-
-        // Build seed more efficiently using native arrays where possible
-        // Total: 12 bytes of fixed data (session_id + 2 shot cells)
-        let mut fixed_data = [0u8; 12];
-        fixed_data[0..4].copy_from_slice(&session_id.to_be_bytes());
-        fixed_data[4..8].copy_from_slice(&guess1.to_be_bytes());
-        fixed_data[8..12].copy_from_slice(&guess2.to_be_bytes());
-
-        // Only use Bytes for the final concatenation with player addresses
-        let mut seed_bytes = Bytes::from_array(&env, &fixed_data);
-        seed_bytes.append(&game.player1.to_string().to_bytes());
-        seed_bytes.append(&game.player2.to_string().to_bytes());
-
-        let seed = env.crypto().keccak256(&seed_bytes);
-        env.prng().seed(seed.into());
-        let winning_number = env.prng().gen_range::<u64>(1..=16) as u32;
-        game.winning_number = Some(winning_number);
-
-        // Calculate distances
-        let distance1 = if guess1 > winning_number {
-            guess1 - winning_number
-        } else {
-            winning_number - guess1
-        };
-
-        let distance2 = if guess2 > winning_number {
-            guess2 - winning_number
-        } else {
-            winning_number - guess2
-        };
-
-        // Determine winner (if equal distance, player1 wins)
-        let winner = if distance1 <= distance2 {
-            game.player1.clone()
-        } else {
-            game.player2.clone()
-        };
-
-        // Update game with winner (this marks the game as ended)
-        game.winner = Some(winner.clone());
+        for seat in team_seats(team) {
+            game.players.get_unchecked(seat).require_auth();
+        }
+        if game.board_commits.get_unchecked(team - 1).is_some() {
+            return Err(Error::BoardCommitAlreadySet);
+        }
+        game.board_commits.set(team - 1, Some(board_commit));
         env.storage().temporary().set(&key, &game);
 
-        // Get GameHub address
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
-
-        // Create GameHub client
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
-
-        // Call GameHub to end the session
-        // This unlocks points and updates standings
-        // Event emitted by the Game Hub contract (GameEnded)
-        let player1_won = winner == game.player1; // true if player1 won, false if player2 won
-        game_hub.end_game(&session_id, &player1_won);
-
-        Ok(winner)
+        Ok(())
     }
 
-    /// Submit a zk-verified match result and settle the game in Game Hub.
+    /// Submit a zk-verified team result and settle both pairings in Game Hub.
     ///
-    /// This call performs on-chain proof verification via the configured verifier contract.
-    /// The verifier address and image id must be configured by admin using `set_verifier`
-    /// and `set_image_id` before submissions are accepted.
-    pub fn submit_result(
+    /// The proof must be for the teams guest, whose image id the admin sets with
+    /// `set_team_image_id`. `board_hashes` holds the two team commitments and
+    /// `player_keys` the four players' keys in seat order; they are checked against the
+    /// team commits and the players' account keys as in `submit_result`, and like `winner`
+    /// and `total_moves` must be what the journal commits (a journal without player keys
+    /// fails with `PlayerKeyMismatch`). Every member of the winning team wins their pairing.
+    ///
+    /// # Returns
+    /// * The winning team's players
+    #[allow(
+        clippy::too_many_arguments,
+        reason = "each argument is a separate claim checked against the journal, as in submit_result"
+    )]
+    pub fn submit_team_result(
         env: Env,
         session_id: u32,
         submitter: Address,
         winner: u32,
         total_moves: u32,
-        board_hash_p1: BytesN<32>,
-        board_hash_p2: BytesN<32>,
+        board_hashes: Vec<BytesN<32>>,
+        player_keys: Vec<BytesN<32>>,
         journal: Bytes,
         seal: Bytes,
-    ) -> Result<Address, Error> {
+    ) -> Result<Vec<Address>, Error> {
         submitter.require_auth();
 
-        let key = DataKey::Game(session_id);
-        let mut game: Game = env
+        let key = DataKey::Team(session_id);
+        let mut game: TeamGame = env
             .storage()
             .temporary()
             .get(&key)
@@ -442,11 +2009,11 @@ impl ZkBattleshipContract {
             return Err(Error::GameAlreadyEnded);
         }
 
-        if submitter != game.player1 && submitter != game.player2 {
+        if !game.players.contains(&submitter) {
             return Err(Error::NotPlayer);
         }
 
-        if winner != 1 && winner != 2 {
+        if !(1..=2).contains(&winner) {
             return Err(Error::InvalidWinner);
         }
 
@@ -454,22 +2021,44 @@ impl ZkBattleshipContract {
             return Err(Error::InvalidTotalMoves);
         }
 
-        if journal.len() == 0 || seal.len() == 0 {
+        if journal.is_empty() || seal.is_empty() {
             return Err(Error::InvalidProofMaterial);
         }
 
-        let commit_p1 = game
-            .board_commit_p1
-            .clone()
-            .ok_or(Error::BoardCommitNotSet)?;
-        let commit_p2 = game
-            .board_commit_p2
-            .clone()
-            .ok_or(Error::BoardCommitNotSet)?;
+        if board_hashes.len() != 2 || player_keys.len() != TEAM_PLAYERS {
+            return Err(Error::InvalidPlayerCount);
+        }
+        for team in 0..2 {
+            let commit = game
+                .board_commits
+                .get_unchecked(team)
+                .ok_or(Error::BoardCommitNotSet)?;
+            if commit != board_hashes.get_unchecked(team) {
+                return Err(Error::BoardCommitMismatch);
+            }
+        }
+        for seat in 0..TEAM_PLAYERS {
+            if account_key(&game.players.get_unchecked(seat)) != Some(player_keys.get_unchecked(seat)) {
+                return Err(Error::PlayerKeyMismatch);
+            }
+        }
 
-        if commit_p1 != board_hash_p1 || commit_p2 != board_hash_p2 {
+        let proven = TeamJournal::decode(&env, &journal)?;
+        if proven.session_id != session_id {
+            return Err(Error::InvalidJournal);
+        }
+        if proven.winner != winner {
+            return Err(Error::InvalidWinner);
+        }
+        if proven.total_moves != total_moves {
+            return Err(Error::InvalidTotalMoves);
+        }
+        if proven.board_hashes != board_hashes {
             return Err(Error::BoardCommitMismatch);
         }
+        if proven.player_keys != Some(player_keys) {
+            return Err(Error::PlayerKeyMismatch);
+        }
 
         let verifier_addr: Address = env
             .storage()
@@ -479,7 +2068,7 @@ impl ZkBattleshipContract {
         let image_id: BytesN<32> = env
             .storage()
             .instance()
-            .get(&DataKey::VerifierImageId)
+            .get(&DataKey::TeamImageId)
             .ok_or(Error::VerifierNotConfigured)?;
 
         let verifier = VerifierClient::new(&env, &verifier_addr);
@@ -489,31 +2078,36 @@ impl ZkBattleshipContract {
         let journal_hash = env.crypto().keccak256(&journal);
         let seal_hash = env.crypto().keccak256(&seal);
 
-        let winner_addr = if winner == 1 {
-            game.player1.clone()
-        } else {
-            game.player2.clone()
-        };
-
-        game.winner = Some(winner_addr.clone());
+        let winners = Vec::from_array(
+            &env,
+            team_seats(winner).map(|seat| game.players.get_unchecked(seat)),
+        );
+        game.winner = Some(winner);
         game.total_moves = Some(total_moves);
-        game.board_hash_p1 = Some(board_hash_p1);
-        game.board_hash_p2 = Some(board_hash_p2);
         game.journal_hash = Some(journal_hash.into());
         game.seal_hash = Some(seal_hash.into());
         env.storage().temporary().set(&key, &game);
 
+        // Team 1 sits at the first seat of both pairings.
         let game_hub_addr: Address = env
             .storage()
             .instance()
             .get(&DataKey::GameHubAddress)
             .expect("GameHub address not set");
         let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        for pairing in 0..2 {
+            game_hub.end_game(&hub_session(session_id, pairing)?, &(winner == 1));
+        }
 
-        let player1_won = winner == 1;
-        game_hub.end_game(&session_id, &player1_won);
+        Ok(winners)
+    }
 
-        Ok(winner_addr)
+    /// Get team game information.
+    pub fn get_team_game(env: Env, session_id: u32) -> Result<TeamGame, Error> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::Team(session_id))
+            .ok_or(Error::GameNotFound)
     }
 
     /// Get game information.
@@ -630,6 +2224,68 @@ impl ZkBattleshipContract {
             .ok_or(Error::VerifierNotConfigured)
     }
 
+    /// Set the aggregation guest's image id (admin only)
+    ///
+    /// `submit_result` accepts proofs under this id as well as under `set_image_id`'s.
+    pub fn set_aggregate_image_id(env: Env, image_id: BytesN<32>) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::AggregateImageId, &image_id);
+    }
+
+    /// Get the aggregation guest's image id
+    pub fn get_aggregate_image_id(env: Env) -> Result<BytesN<32>, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AggregateImageId)
+            .ok_or(Error::VerifierNotConfigured)
+    }
+
+    /// Set the free-for-all guest's image id (admin only)
+    pub fn set_free_for_all_image_id(env: Env, image_id: BytesN<32>) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::FreeForAllImageId, &image_id);
+    }
+
+    /// Get the free-for-all guest's image id
+    pub fn get_free_for_all_image_id(env: Env) -> Result<BytesN<32>, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::FreeForAllImageId)
+            .ok_or(Error::VerifierNotConfigured)
+    }
+
+    /// Set the teams guest's image id (admin only)
+    pub fn set_team_image_id(env: Env, image_id: BytesN<32>) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::TeamImageId, &image_id);
+    }
+
+    /// Get the teams guest's image id
+    pub fn get_team_image_id(env: Env) -> Result<BytesN<32>, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::TeamImageId)
+            .ok_or(Error::VerifierNotConfigured)
+    }
+
     /// Update the contract WASM hash (upgrade contract)
     ///
     /// # Arguments
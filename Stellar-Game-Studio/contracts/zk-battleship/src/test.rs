@@ -6,9 +6,13 @@
 // Note: These tests use a minimal mock for isolation and speed.
 // For full integration tests with the real Game Hub contract, see the platform repo.
 
-use crate::{Error, ZkBattleshipContract, ZkBattleshipContractClient};
+use crate::{
+    ClaimState, Error, FreeForAllGame, Outcome, RevealedShot, Ruleset, TeamGame, ZkBattleshipContract,
+    ZkBattleshipContractClient,
+};
 use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env};
+use soroban_sdk::address_payload::AddressPayload;
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, Vec};
 
 // ============================================================================
 // Mock GameHub for Unit Testing
@@ -31,8 +35,13 @@ impl MockGameHub {
         // Mock implementation - does nothing
     }
 
-    pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {
-        // Mock implementation - does nothing
+    pub fn end_game(env: Env, session_id: u32, player1_won: bool) {
+        // Recorded so tests can check how each session was settled
+        env.storage().instance().set(&session_id, &player1_won);
+    }
+
+    pub fn result(env: Env, session_id: u32) -> Option<bool> {
+        env.storage().instance().get(&session_id)
     }
 
     pub fn add_game(_env: Env, _game_address: Address) {
@@ -46,7 +55,7 @@ pub struct MockVerifier;
 
 #[contractimpl]
 impl MockVerifier {
-    pub fn verify(_env: Env, seal: soroban_sdk::Bytes, _image_id: BytesN<32>, _journal: BytesN<32>) {
+    pub fn verify(env: Env, seal: soroban_sdk::Bytes, image_id: BytesN<32>, _journal: BytesN<32>) {
         if seal.len() == 0 {
             panic!("invalid proof");
         }
@@ -56,6 +65,16 @@ impl MockVerifier {
         if first == 0 {
             panic!("invalid proof");
         }
+
+        // A seal bound to an image id does not verify under any other.
+        let bound: Option<BytesN<32>> = env.storage().instance().get(&seal);
+        if bound.is_some_and(|bound| bound != image_id) {
+            panic!("invalid proof");
+        }
+    }
+
+    pub fn bind(env: Env, seal: soroban_sdk::Bytes, image_id: BytesN<32>) {
+        env.storage().instance().set(&seal, &image_id);
     }
 }
 
@@ -108,12 +127,84 @@ fn setup_test() -> (
     client.set_verifier(&verifier_addr);
     client.set_image_id(&BytesN::from_array(&env, &[9u8; 32]));
 
-    let player1 = Address::generate(&env);
-    let player2 = Address::generate(&env);
+    let player1 = Address::from_payload(
+        &env,
+        AddressPayload::AccountIdPublicKeyEd25519(player_key(&env, 1)),
+    );
+    let player2 = Address::from_payload(
+        &env,
+        AddressPayload::AccountIdPublicKeyEd25519(player_key(&env, 2)),
+    );
 
     (env, client, game_hub, verifier, player1, player2)
 }
 
+/// The ed25519 key behind the account of `setup_test`'s player 1 or 2.
+fn player_key(env: &Env, player: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[0xa0 + player; 32])
+}
+
+/// Appends one journal word the way a RISC0 guest commits it (little-endian `u32`).
+fn push_word(journal: &mut Bytes, word: u32) {
+    journal.extend_from_array(&word.to_le_bytes());
+}
+
+/// Appends a 32-byte hash as the guest commits it: one word per byte.
+fn push_hash(journal: &mut Bytes, hash: &BytesN<32>) {
+    for byte in hash.to_array() {
+        push_word(journal, byte.into());
+    }
+}
+
+/// The play guest's journal (`PublicOutput`) for a classic match without sonar or mines,
+/// signed by `setup_test`'s players.
+fn match_journal(
+    env: &Env,
+    session_id: u32,
+    winner: u32,
+    outcome: u32,
+    total_moves: u32,
+    board_hashes: (&BytesN<32>, &BytesN<32>),
+) -> Bytes {
+    let player_keys = [player_key(env, 1), player_key(env, 2)];
+    match_journal_signed_by(env, session_id, winner, outcome, total_moves, board_hashes, Some(player_keys))
+}
+
+/// `match_journal` with the move signatures checked against `player_keys` (`None`: unsigned).
+fn match_journal_signed_by(
+    env: &Env,
+    session_id: u32,
+    winner: u32,
+    outcome: u32,
+    total_moves: u32,
+    board_hashes: (&BytesN<32>, &BytesN<32>),
+    player_keys: Option<[BytesN<32>; 2]>,
+) -> Bytes {
+    let mut journal = Bytes::new(env);
+    push_word(&mut journal, session_id);
+    push_word(&mut journal, winner);
+    push_word(&mut journal, outcome);
+    push_hash(&mut journal, board_hashes.0);
+    push_hash(&mut journal, board_hashes.1);
+    // transcript_hash
+    push_hash(&mut journal, &BytesN::from_array(env, &[0u8; 32]));
+    push_word(&mut journal, total_moves);
+    // mode, no_touching, salvo, hit_keeps_turn, weapons (3), stats (2 x 4), no sonar pings,
+    // mines, mine_penalty, no mine hits
+    for _ in 0..(4 + 3 + 8 + 1 + 2 + 1) {
+        push_word(&mut journal, 0);
+    }
+    match player_keys {
+        Some(keys) => {
+            push_word(&mut journal, 1);
+            push_hash(&mut journal, &keys[0]);
+            push_hash(&mut journal, &keys[1]);
+        }
+        None => push_word(&mut journal, 0),
+    }
+    journal
+}
+
 /// Assert that a Result contains a specific number_guess error
 ///
 /// This helper provides type-safe error assertions following Stellar/Soroban best practices.
@@ -525,7 +616,7 @@ fn test_submit_result_success() {
 
     let board_hash_p1 = BytesN::from_array(&env, &[1u8; 32]);
     let board_hash_p2 = BytesN::from_array(&env, &[2u8; 32]);
-    let journal = soroban_sdk::Bytes::from_slice(&env, &[10u8, 11u8, 12u8]);
+    let journal = match_journal(&env, session_id, 1, 1, 7, (&board_hash_p1, &board_hash_p2));
     let seal = soroban_sdk::Bytes::from_slice(&env, &[20u8, 21u8, 22u8]);
 
     client.set_board_commit(&session_id, &player1, &board_hash_p1);
@@ -535,17 +626,21 @@ fn test_submit_result_success() {
         &session_id,
         &player1,
         &1u32,
+        &1u32,
         &7u32,
         &board_hash_p1,
         &board_hash_p2,
+        &player_key(&env, 1),
+        &player_key(&env, 2),
         &journal,
         &seal,
     );
     assert_eq!(winner, player1);
 
     let game = client.get_game(&session_id);
-    assert_eq!(game.winner, Some(player1));
+    assert_eq!(game.winner, Some(player1.clone()));
     assert_eq!(game.total_moves, Some(7));
+    assert_eq!(game.outcome, Outcome::SunkAll);
     assert_eq!(game.board_hash_p1, Some(board_hash_p1));
     assert_eq!(game.board_hash_p2, Some(board_hash_p2));
     assert!(game.journal_hash.is_some());
@@ -562,7 +657,7 @@ fn test_submit_result_rejects_invalid_proof() {
 
     let board_hash_p1 = BytesN::from_array(&env, &[5u8; 32]);
     let board_hash_p2 = BytesN::from_array(&env, &[6u8; 32]);
-    let journal = soroban_sdk::Bytes::from_slice(&env, &[1u8, 2u8, 3u8]);
+    let journal = match_journal(&env, session_id, 1, 1, 7, (&board_hash_p1, &board_hash_p2));
     let invalid_seal = soroban_sdk::Bytes::from_slice(&env, &[0u8, 9u8]);
 
     client.set_board_commit(&session_id, &player1, &board_hash_p1);
@@ -572,9 +667,12 @@ fn test_submit_result_rejects_invalid_proof() {
         &session_id,
         &player1,
         &1u32,
+        &1u32,
         &7u32,
         &board_hash_p1,
         &board_hash_p2,
+        &player_key(&env, 1),
+        &player_key(&env, 2),
         &journal,
         &invalid_seal,
     );
@@ -601,9 +699,12 @@ fn test_submit_result_requires_player_and_valid_inputs() {
         &session_id,
         &non_player,
         &1u32,
+        &1u32,
         &5u32,
         &board_hash_p1,
         &board_hash_p2,
+        &player_key(&env, 1),
+        &player_key(&env, 2),
         &journal,
         &seal,
     );
@@ -613,9 +714,12 @@ fn test_submit_result_requires_player_and_valid_inputs() {
         &session_id,
         &player1,
         &3u32,
+        &1u32,
         &5u32,
         &board_hash_p1,
         &board_hash_p2,
+        &player_key(&env, 1),
+        &player_key(&env, 2),
         &journal,
         &seal,
     );
@@ -625,13 +729,278 @@ fn test_submit_result_requires_player_and_valid_inputs() {
         &session_id,
         &player1,
         &1u32,
+        &1u32,
         &0u32,
         &board_hash_p1,
         &board_hash_p2,
+        &player_key(&env, 1),
+        &player_key(&env, 2),
         &journal,
         &seal,
     );
     assert_number_guess_error(&invalid_moves, Error::InvalidTotalMoves);
+
+    let invalid_outcome = client.try_submit_result(
+        &session_id,
+        &player1,
+        &1u32,
+        &0u32,
+        &5u32,
+        &board_hash_p1,
+        &board_hash_p2,
+        &player_key(&env, 1),
+        &player_key(&env, 2),
+        &journal,
+        &seal,
+    );
+    assert_number_guess_error(&invalid_outcome, Error::InvalidOutcome);
+}
+
+#[test]
+fn test_submit_result_records_resignation() {
+    let (env, client, _hub, _verifier, player1, player2) = setup_test();
+
+    let session_id = 20u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    let board_hash_p1 = BytesN::from_array(&env, &[12u8; 32]);
+    let board_hash_p2 = BytesN::from_array(&env, &[13u8; 32]);
+    let journal = match_journal(&env, session_id, 2, 2, 3, (&board_hash_p1, &board_hash_p2));
+    let seal = soroban_sdk::Bytes::from_slice(&env, &[15u8]);
+
+    client.set_board_commit(&session_id, &player1, &board_hash_p1);
+    client.set_board_commit(&session_id, &player2, &board_hash_p2);
+
+    // Player 1 resigned after three moves.
+    let winner = client.submit_result(
+        &session_id,
+        &player2,
+        &2u32,
+        &2u32,
+        &3u32,
+        &board_hash_p1,
+        &board_hash_p2,
+        &player_key(&env, 1),
+        &player_key(&env, 2),
+        &journal,
+        &seal,
+    );
+    assert_eq!(winner, player2);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.outcome, Outcome::Resigned);
+    assert_eq!(game.total_moves, Some(3));
+}
+
+#[test]
+fn test_submit_result_takes_outcome_from_journal() {
+    let (env, client, _hub, _verifier, player1, player2) = setup_test();
+
+    let session_id = 23u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    let board_hash_p1 = BytesN::from_array(&env, &[24u8; 32]);
+    let board_hash_p2 = BytesN::from_array(&env, &[25u8; 32]);
+    let seal = soroban_sdk::Bytes::from_slice(&env, &[26u8]);
+
+    client.set_board_commit(&session_id, &player1, &board_hash_p1);
+    client.set_board_commit(&session_id, &player2, &board_hash_p2);
+
+    // The proof is of a resignation; claiming a full sinking instead must not settle.
+    let resigned = match_journal(&env, session_id, 1, 2, 7, (&board_hash_p1, &board_hash_p2));
+    let claimed_sunk = client.try_submit_result(
+        &session_id,
+        &player1,
+        &1u32,
+        &1u32,
+        &7u32,
+        &board_hash_p1,
+        &board_hash_p2,
+        &player_key(&env, 1),
+        &player_key(&env, 2),
+        &resigned,
+        &seal,
+    );
+    assert_number_guess_error(&claimed_sunk, Error::InvalidOutcome);
+
+    // A journal proven for another session, or one cut short, is not this game's result.
+    let other_session = match_journal(&env, 99, 1, 1, 7, (&board_hash_p1, &board_hash_p2));
+    let truncated = resigned.slice(0..resigned.len() - 4);
+    for journal in [other_session, truncated] {
+        let result = client.try_submit_result(
+            &session_id,
+            &player1,
+            &1u32,
+            &2u32,
+            &7u32,
+            &board_hash_p1,
+            &board_hash_p2,
+            &player_key(&env, 1),
+            &player_key(&env, 2),
+            &journal,
+            &seal,
+        );
+        assert_number_guess_error(&result, Error::InvalidJournal);
+    }
+
+    let winner = client.submit_result(
+        &session_id,
+        &player1,
+        &1u32,
+        &2u32,
+        &7u32,
+        &board_hash_p1,
+        &board_hash_p2,
+        &player_key(&env, 1),
+        &player_key(&env, 2),
+        &resigned,
+        &seal,
+    );
+    assert_eq!(winner, player1);
+    assert_eq!(client.get_game(&session_id).outcome, Outcome::Resigned);
+}
+
+#[test]
+fn test_submit_result_binds_arguments_to_journal() {
+    let (env, client, _hub, _verifier, player1, player2) = setup_test();
+
+    let session_id = 24u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    let board_hash_p1 = BytesN::from_array(&env, &[27u8; 32]);
+    let board_hash_p2 = BytesN::from_array(&env, &[28u8; 32]);
+    let seal = soroban_sdk::Bytes::from_slice(&env, &[29u8]);
+
+    client.set_board_commit(&session_id, &player1, &board_hash_p1);
+    client.set_board_commit(&session_id, &player2, &board_hash_p2);
+
+    // Player 2 won this proof; the submitter claims the win and a shorter game instead.
+    let journal = match_journal(&env, session_id, 2, 1, 9, (&board_hash_p1, &board_hash_p2));
+    let submit = |winner: u32, total_moves: u32, journal: &Bytes| {
+        client.try_submit_result(
+            &session_id,
+            &player1,
+            &winner,
+            &1u32,
+            &total_moves,
+            &board_hash_p1,
+            &board_hash_p2,
+            &player_key(&env, 1),
+            &player_key(&env, 2),
+            journal,
+            &seal,
+        )
+    };
+    assert_number_guess_error(&submit(1, 9, &journal), Error::InvalidWinner);
+    assert_number_guess_error(&submit(2, 5, &journal), Error::InvalidTotalMoves);
+
+    // A proof over other boards, another pair of signers or unsigned moves.
+    let other_boards = match_journal(&env, session_id, 2, 1, 9, (&board_hash_p2, &board_hash_p1));
+    assert_number_guess_error(&submit(2, 9, &other_boards), Error::BoardCommitMismatch);
+
+    let other_keys = Some([player_key(&env, 1), player_key(&env, 3)]);
+    let hashes = (&board_hash_p1, &board_hash_p2);
+    let other_signers = match_journal_signed_by(&env, session_id, 2, 1, 9, hashes, other_keys);
+    assert_number_guess_error(&submit(2, 9, &other_signers), Error::PlayerKeyMismatch);
+
+    let unsigned = match_journal_signed_by(&env, session_id, 2, 1, 9, hashes, None);
+    assert_number_guess_error(&submit(2, 9, &unsigned), Error::PlayerKeyMismatch);
+
+    assert_eq!(submit(2, 9, &journal), Ok(Ok(player2.clone())));
+    let game = client.get_game(&session_id);
+    assert_eq!(game.winner, Some(player2));
+    assert_eq!(game.total_moves, Some(9));
+}
+
+#[test]
+fn test_submit_result_requires_the_agreed_ruleset() {
+    let (env, client, _hub, _verifier, player1, player2) = setup_test();
+
+    let salvo = Ruleset { salvo: true, ..Ruleset::default() };
+    let board_hash_p1 = BytesN::from_array(&env, &[35u8; 32]);
+    let board_hash_p2 = BytesN::from_array(&env, &[36u8; 32]);
+    let seal = soroban_sdk::Bytes::from_slice(&env, &[37u8]);
+
+    let start = |session_id: u32, rules: &Ruleset| {
+        client.start_game_with_rules(&session_id, &player1, &player2, &100_0000000, &100_0000000, rules);
+        client.set_board_commit(&session_id, &player1, &board_hash_p1);
+        client.set_board_commit(&session_id, &player2, &board_hash_p2);
+    };
+    let submit = |session_id: u32, journal: &Bytes| {
+        client.try_submit_result(
+            &session_id,
+            &player1,
+            &1u32,
+            &1u32,
+            &7u32,
+            &board_hash_p1,
+            &board_hash_p2,
+            &player_key(&env, 1),
+            &player_key(&env, 2),
+            journal,
+            &seal,
+        )
+    };
+    let journal = |session_id: u32| match_journal(&env, session_id, 1, 1, 7, (&board_hash_p1, &board_hash_p2));
+    // The salvo flag follows the header, the three hashes, total_moves, mode and no_touching.
+    let salvo_journal = |session_id: u32| {
+        let mut journal = journal(session_id);
+        journal.set((3 + 3 * 32 + 3) * 4, 1);
+        journal
+    };
+
+    // A demo game settled with a salvo proof, and the other way around.
+    start(28, &Ruleset::default());
+    assert_number_guess_error(&submit(28, &salvo_journal(28)), Error::RulesetMismatch);
+    start(29, &salvo);
+    assert_eq!(client.get_game(&29).ruleset, salvo);
+    assert_number_guess_error(&submit(29, &journal(29)), Error::RulesetMismatch);
+
+    assert_eq!(submit(29, &salvo_journal(29)), Ok(Ok(player1.clone())));
+    assert_eq!(submit(28, &journal(28)), Ok(Ok(player1)));
+}
+
+#[test]
+fn test_submit_result_accepts_whole_match_and_aggregated_proofs() {
+    let (env, client, _hub, verifier, player1, player2) = setup_test();
+
+    let image_id = BytesN::from_array(&env, &[9u8; 32]);
+    let aggregate_image_id = BytesN::from_array(&env, &[8u8; 32]);
+    client.set_aggregate_image_id(&aggregate_image_id);
+    assert_eq!(client.get_aggregate_image_id(), aggregate_image_id);
+
+    let board_hash_p1 = BytesN::from_array(&env, &[30u8; 32]);
+    let board_hash_p2 = BytesN::from_array(&env, &[31u8; 32]);
+    let whole_match_seal = soroban_sdk::Bytes::from_slice(&env, &[32u8]);
+    let aggregated_seal = soroban_sdk::Bytes::from_slice(&env, &[33u8]);
+    let foreign_seal = soroban_sdk::Bytes::from_slice(&env, &[34u8]);
+    verifier.bind(&whole_match_seal, &image_id);
+    verifier.bind(&aggregated_seal, &aggregate_image_id);
+    verifier.bind(&foreign_seal, &BytesN::from_array(&env, &[7u8; 32]));
+
+    let submit = |session_id: u32, seal: &Bytes| {
+        client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+        client.set_board_commit(&session_id, &player1, &board_hash_p1);
+        client.set_board_commit(&session_id, &player2, &board_hash_p2);
+        let journal = match_journal(&env, session_id, 1, 1, 7, (&board_hash_p1, &board_hash_p2));
+        client.try_submit_result(
+            &session_id,
+            &player1,
+            &1u32,
+            &1u32,
+            &7u32,
+            &board_hash_p1,
+            &board_hash_p2,
+            &player_key(&env, 1),
+            &player_key(&env, 2),
+            &journal,
+            seal,
+        )
+    };
+
+    assert_eq!(submit(25, &whole_match_seal), Ok(Ok(player1.clone())));
+    assert_eq!(submit(26, &aggregated_seal), Ok(Ok(player1.clone())));
+    assert!(matches!(submit(27, &foreign_seal), Err(Err(_))), "a seal for another guest must not verify");
 }
 
 #[test]
@@ -668,16 +1037,19 @@ fn test_submit_result_requires_commits_and_matching_hashes() {
 
     let board_hash_p1 = BytesN::from_array(&env, &[7u8; 32]);
     let board_hash_p2 = BytesN::from_array(&env, &[8u8; 32]);
-    let journal = soroban_sdk::Bytes::from_slice(&env, &[10u8]);
+    let journal = match_journal(&env, session_id, 1, 1, 7, (&board_hash_p1, &board_hash_p2));
     let seal = soroban_sdk::Bytes::from_slice(&env, &[11u8]);
 
     let missing_commit = client.try_submit_result(
         &session_id,
         &player1,
         &1u32,
+        &1u32,
         &7u32,
         &board_hash_p1,
         &board_hash_p2,
+        &player_key(&env, 1),
+        &player_key(&env, 2),
         &journal,
         &seal,
     );
@@ -691,15 +1063,73 @@ fn test_submit_result_requires_commits_and_matching_hashes() {
         &session_id,
         &player1,
         &1u32,
+        &1u32,
         &7u32,
         &mismatched_hash,
         &board_hash_p2,
+        &player_key(&env, 1),
+        &player_key(&env, 2),
         &journal,
         &seal,
     );
     assert_number_guess_error(&mismatch, Error::BoardCommitMismatch);
 }
 
+#[test]
+fn test_submit_result_requires_player_account_keys() {
+    let (env, client, _hub, _verifier, player1, player2) = setup_test();
+
+    let session_id = 21u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    let board_hash_p1 = BytesN::from_array(&env, &[16u8; 32]);
+    let board_hash_p2 = BytesN::from_array(&env, &[17u8; 32]);
+    let journal = match_journal(&env, session_id, 1, 1, 7, (&board_hash_p1, &board_hash_p2));
+    let seal = soroban_sdk::Bytes::from_slice(&env, &[19u8]);
+
+    client.set_board_commit(&session_id, &player1, &board_hash_p1);
+    client.set_board_commit(&session_id, &player2, &board_hash_p2);
+
+    // Moves signed by some other key than player 2's account.
+    let swapped = client.try_submit_result(
+        &session_id,
+        &player1,
+        &1u32,
+        &1u32,
+        &7u32,
+        &board_hash_p1,
+        &board_hash_p2,
+        &player_key(&env, 1),
+        &player_key(&env, 1),
+        &journal,
+        &seal,
+    );
+    assert_number_guess_error(&swapped, Error::PlayerKeyMismatch);
+
+    // Contract addresses have no ed25519 key to sign moves with.
+    let session_id = 22u32;
+    let journal = match_journal(&env, session_id, 1, 1, 7, (&board_hash_p1, &board_hash_p2));
+    let contract_player = Address::generate(&env);
+    client.start_game(&session_id, &player1, &contract_player, &100_0000000, &100_0000000);
+    client.set_board_commit(&session_id, &player1, &board_hash_p1);
+    client.set_board_commit(&session_id, &contract_player, &board_hash_p2);
+
+    let unsigned = client.try_submit_result(
+        &session_id,
+        &player1,
+        &1u32,
+        &1u32,
+        &7u32,
+        &board_hash_p1,
+        &board_hash_p2,
+        &player_key(&env, 1),
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &journal,
+        &seal,
+    );
+    assert_number_guess_error(&unsigned, Error::PlayerKeyMismatch);
+}
+
 
 #[test]
 fn test_set_board_commit_stores_hashes() {
@@ -735,6 +1165,846 @@ fn test_set_board_commit_rejects_duplicates() {
     assert_number_guess_error(&duplicate, Error::BoardCommitAlreadySet);
 }
 
+/// Player 2's board in `game-input.example.json` (zero board salt), as committed by
+/// `battleship_rules::merkle`, with the opening of cell (1, 2) from `open-cell`. Both
+/// boards of `game-input.signed.example.json` are the same board.
+const SAMPLE_BOARD_COMMIT: &str =
+    "c03ca9b12cbd57d5e2c4ff84be91c41e26297fa165e4f9fe44f009dbecd15cee";
+const SAMPLE_CELL_SALT: &str =
+    "535f9cc7c3b0ed4a235436b7b20cb3dbead3ef3c38e9f03ec449e6d2e113e1c3";
+const SAMPLE_CELL_SIBLINGS: [&str; 4] = [
+    "b8d59345548f0f34ab8697dae76c60c3754b32e4d34044aae3b9fe73af168531",
+    "33bda86d1da088b7b4d8010e9db29466320744cd1e91639b39894a5191fca546",
+    "986bb739f450f6c77c600d4e9e27cefa3bfeabdd7b1f66dce28a0453b140b5ef",
+    "49599c1c7bcc9f5f3e31e902b2a5b2811e4a01fbda208f71f67d44d9e507f0fd",
+];
+
+/// Player 1's board in `game-input.mines.example.json`, whose cell (3, 1) is a mine, with
+/// that cell's opening from `open-cell`.
+const MINES_BOARD_COMMIT: &str =
+    "0312d0a041fdadb33c94b0b210d794891abd3907e3733adfa8a8dd24cdd51bb6";
+const MINES_CELL_SALT: &str =
+    "e42c0a057b28451002782f0f1f2fdc85198ed4637f67f2c27b27d1550daf99f6";
+const MINES_CELL_SIBLINGS: [&str; 4] = [
+    "d511565d541d890b425b05b3ed0b51bf21eb759183e4e4ae9272e82813077b3d",
+    "0821e552e9704e58ecf4189ea888a1b79c8a5752ffbc3f74099bb349beba11a0",
+    "2fb779b0d613f1235a99030c7d4cf24dbf1a14172130b5e861a7db473c330cdd",
+    "65db1b18be9c90faeb2ec6ac0af3da3b59e882d036254882e72d55cbc771e96a",
+];
+
+fn bytes_from_hex<const N: usize>(env: &Env, value: &str) -> BytesN<N> {
+    let mut bytes = [0u8; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[2 * i..2 * i + 2], 16).unwrap();
+    }
+    BytesN::from_array(env, &bytes)
+}
+
+#[test]
+fn test_verify_cell_opening_checks_the_board_commit() {
+    let (env, client, _hub, _verifier, player1, player2) = setup_test();
+
+    let session_id = 18u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    let salt = bytes_from_hex(&env, SAMPLE_CELL_SALT);
+    let mut siblings = Vec::new(&env);
+    for sibling in SAMPLE_CELL_SIBLINGS {
+        siblings.push_back(bytes_from_hex(&env, sibling));
+    }
+
+    let unset = client.try_verify_cell_opening(&session_id, &player2, &9, &1, &salt, &siblings);
+    assert_number_guess_error(&unset, Error::BoardCommitNotSet);
+
+    client.set_board_commit(&session_id, &player2, &bytes_from_hex(&env, SAMPLE_BOARD_COMMIT));
+
+    assert!(client.verify_cell_opening(&session_id, &player2, &9, &1, &salt, &siblings));
+    // Claiming water or a mine, or another cell, with the same path fails.
+    assert!(!client.verify_cell_opening(&session_id, &player2, &9, &0, &salt, &siblings));
+    assert!(!client.verify_cell_opening(&session_id, &player2, &9, &2, &salt, &siblings));
+    assert!(!client.verify_cell_opening(&session_id, &player2, &8, &1, &salt, &siblings));
+
+    let unknown = client.try_verify_cell_opening(&session_id, &player2, &9, &3, &salt, &siblings);
+    assert_number_guess_error(&unknown, Error::InvalidBoard);
+
+    let outsider = Address::generate(&env);
+    let not_player =
+        client.try_verify_cell_opening(&session_id, &outsider, &9, &1, &salt, &siblings);
+    assert_number_guess_error(&not_player, Error::NotPlayer);
+
+    // The mine at (3, 1) of player 1's board in `game-input.mines.example.json`.
+    client.set_board_commit(&session_id, &player1, &bytes_from_hex(&env, MINES_BOARD_COMMIT));
+    let salt = bytes_from_hex(&env, MINES_CELL_SALT);
+    let mut siblings = Vec::new(&env);
+    for sibling in MINES_CELL_SIBLINGS {
+        siblings.push_back(bytes_from_hex(&env, sibling));
+    }
+    assert!(client.verify_cell_opening(&session_id, &player1, &7, &2, &salt, &siblings));
+    assert!(!client.verify_cell_opening(&session_id, &player1, &7, &0, &salt, &siblings));
+    assert!(!client.verify_cell_opening(&session_id, &player1, &7, &1, &salt, &siblings));
+}
+
+// ============================================================================
+// Reveal Settlement Tests
+// ============================================================================
+
+/// `game-input.signed.example.json`: both boards, the players' keys and every move's
+/// signature, produced by `battleship_rules::transcript::sign_moves`.
+const SIGNED_SESSION_ID: u32 = 149478307;
+const SIGNED_BOARD: [u8; 16] = [1, 1, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0];
+const SIGNED_PLAYER_KEYS: [&str; 2] = [
+    "8a88e3dd7409f195fd52db2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c",
+    "8139770ea87d175f56a35466c34c7ecccb8d8a91b4ee37a25df60f5b8fc9b394",
+];
+const SIGNED_MOVES: [(u32, u32, &str); 7] = [
+    (0, 0, "7944a6baefc5b057c52eaf65dc929c344ca51a2b224e9b1d7e26f6e4bce9f5038069875eef1b0b1753d675610f85d5f8ac81bfe033db8abbb34799f0bbbc4001"),
+    (3, 3, "7f0a15acc92f777ebed0172b24b34e147981487b14f9fbe646c9c57e5f4335fbb046067528696e91749ef5c6488df73a75dde5688b1bf10530abfb391a58e102"),
+    (1, 0, "675e96909bb8f01c060b08df384e746c66ffe5352dbb00b41600de55ef7d82b72174ee1c1fb1dd659ae8b61d2d397fef4ad311272efe21bb81d58f1120036903"),
+    (3, 2, "1a3ac12f421710ec2b489e30d427006c49d7e00d9496d2bc72e649ac7faff42b4486fa8af0fdf8abd9df9d5cb6847e71fb4911ab18c07faf34641712feade704"),
+    (0, 2, "0935fbd8c76ead549ddb368fcd720b5fd5838353d6fac38ffc2a3edba4af6f67faad752171e9531f13b21887f1ada31e8f4d4ee19833ed9dc67fabe2ba463c0f"),
+    (2, 2, "c06d63d46891c28dfb1279c32bf123ea6472f08974ad5f66d3cbdd98bd72872b01138ac2fbfbd72507ba7c7d0413a223fa070c2dcff844b5bb0ab8caa7ed8a03"),
+    (1, 2, "304e5348e479408fdafdf5f1775aaab7e8a5712a4afa2d75c9518f6c27add373ae8d297b2e2d10e125634703c79a4a07f7a582958bd43ed96bc8e6083be16709"),
+];
+
+/// Starts the signed sample's session between the accounts of its two keys, with both
+/// board commits set.
+fn setup_signed_game(
+    env: &Env,
+    client: &ZkBattleshipContractClient<'static>,
+) -> (Address, Address) {
+    let [player1, player2] = SIGNED_PLAYER_KEYS.map(|key| {
+        Address::from_payload(env, AddressPayload::AccountIdPublicKeyEd25519(bytes_from_hex(env, key)))
+    });
+    client.start_game(&SIGNED_SESSION_ID, &player1, &player2, &100_0000000, &100_0000000);
+
+    let commit = bytes_from_hex(env, SAMPLE_BOARD_COMMIT);
+    client.set_board_commit(&SIGNED_SESSION_ID, &player1, &commit);
+    client.set_board_commit(&SIGNED_SESSION_ID, &player2, &commit);
+    (player1, player2)
+}
+
+/// A game in the signed sample's session where both sides keep missing, signed with the
+/// same keys: P1 shoots first and last.
+const MISSED_MOVES: [(u32, u32, &str); 9] = [
+    (3, 0, "28d8b53909b901ddc2b6250d7e8dd243cfdfb05f84d9ec3b89e8e26ff53037a9d17c61553cfafba64c44e60c3ada8e0676cb96a6f4b9848816cbc9b137e12e02"),
+    (3, 0, "4b39db57b1d56fa5244c59cb0cd2dd089b53d62a01a5a11bec1c342d42c70ec8652b9a9ad8c745d826fd9be67b6b8e5c5d3c0f35eef43ad9912c9816fa36bb0c"),
+    (3, 1, "b73632a211ed284c69239e596fa6ee2cf208bd848d02d2119af83f01ebe6f696891b233980d79d3dd255f899ea8a3536d43f07c6cd15f9a4a15c99610c69910e"),
+    (3, 1, "b29af93d7e8810e969f48ba7d860454097ebc00a1425975c28dce65b53e6769e72d37ce26d91ee2b1b110a3607ad473f49b8f693d36868cc5f39be8fcd23e701"),
+    (2, 1, "b05e27f9cd989fcf79ca8bdd66300ae5ecd65faff3694cecc8af6c9baab1f97e56c543a61d76c6ea6a7908f983d25286c01a3de4b0a3dbf7c56bfb3971632806"),
+    (2, 1, "5675dea6e20e6e9d399a2895afd9a947c58b401cb0de30f7692b804fa99604b4cf4d345d984d26471cae0e6161ed96a9d613994bd5ecec5c08f90a1dce596d0d"),
+    (3, 3, "8f2c35cccab699f2735833965116885e80cf4bfa31329cce0658ae863775993a985a574c8ebabe29475e790456f0bbd0e86101d121b40a7fb3c2ced0768d420c"),
+    (3, 3, "c57820d654b6b9078ce00bd65d6b6314f2838bd5cb9213c28af0d2c3a32f4c9a57349e734800548a9ca2e9be4e1cc78e012f22bfea9d35f0d16beb65a03d3201"),
+    (2, 3, "6441da3db749f403107e4c549d381c8565e31422f7e19bd614f3b9953c881d52308c1f1e6721600b61aad7133d8823fd4385d78d7e33efa1e503eb468016f400"),
+];
+
+fn shot_log(env: &Env, shots: &[(u32, u32, &str)]) -> Vec<RevealedShot> {
+    let mut moves = Vec::new(env);
+    for (x, y, signature) in shots {
+        moves.push_back(RevealedShot { x: *x, y: *y, signature: bytes_from_hex(env, signature) });
+    }
+    moves
+}
+
+fn signed_moves(env: &Env, count: usize) -> Vec<RevealedShot> {
+    shot_log(env, &SIGNED_MOVES[..count])
+}
+
+fn missed_moves(env: &Env, count: usize) -> Vec<RevealedShot> {
+    shot_log(env, &MISSED_MOVES[..count])
+}
+
+#[test]
+fn test_settle_revealed_replays_signed_moves() {
+    let (env, client, _hub, _verifier, _player1, _player2) = setup_test();
+    let (player1, player2) = setup_signed_game(&env, &client);
+
+    let cells = Bytes::from_array(&env, &SIGNED_BOARD);
+    let salt = BytesN::from_array(&env, &[0u8; 32]);
+    client.reveal_board(&SIGNED_SESSION_ID, &player1, &cells, &salt);
+    client.reveal_board(&SIGNED_SESSION_ID, &player2, &cells, &salt);
+
+    let game = client.get_game(&SIGNED_SESSION_ID);
+    assert_eq!(game.revealed_board_p1, Some(0b11_0000_0011));
+    assert_eq!(game.revealed_board_p2, Some(0b11_0000_0011));
+
+    let winner = client.settle_revealed(&SIGNED_SESSION_ID, &player2, &signed_moves(&env, 7));
+    assert_eq!(winner, player1);
+
+    let game = client.get_game(&SIGNED_SESSION_ID);
+    assert_eq!(game.winner, Some(player1.clone()));
+    assert_eq!(game.total_moves, Some(7));
+    assert_eq!(game.outcome, Outcome::SunkAll);
+    assert_eq!(game.board_hash_p2, game.board_commit_p2);
+    assert!(game.journal_hash.is_none());
+
+    let again = client.try_settle_revealed(&SIGNED_SESSION_ID, &player1, &signed_moves(&env, 7));
+    assert_number_guess_error(&again, Error::GameAlreadyEnded);
+    let late = client.try_reveal_board(&SIGNED_SESSION_ID, &player1, &cells, &salt);
+    assert_number_guess_error(&late, Error::GameAlreadyEnded);
+}
+
+#[test]
+fn test_reveal_board_must_match_commit_and_layout() {
+    let (env, client, _hub, _verifier, _player1, _player2) = setup_test();
+    let (player1, player2) = setup_signed_game(&env, &client);
+
+    let cells = Bytes::from_array(&env, &SIGNED_BOARD);
+    let wrong_salt = client.try_reveal_board(
+        &SIGNED_SESSION_ID,
+        &player1,
+        &cells,
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    assert_number_guess_error(&wrong_salt, Error::BoardCommitMismatch);
+
+    // Three ship cells in a row plus one: four cells, but not two size-2 ships.
+    let line = Bytes::from_array(&env, &[1, 1, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0]);
+    let salt = BytesN::from_array(&env, &[0u8; 32]);
+    let invalid = client.try_reveal_board(&SIGNED_SESSION_ID, &player1, &line, &salt);
+    assert_number_guess_error(&invalid, Error::InvalidBoard);
+
+    client.reveal_board(&SIGNED_SESSION_ID, &player1, &cells, &salt);
+    let unrevealed = client.try_settle_revealed(&SIGNED_SESSION_ID, &player1, &signed_moves(&env, 7));
+    assert_number_guess_error(&unrevealed, Error::BoardNotRevealed);
+    client.reveal_board(&SIGNED_SESSION_ID, &player2, &cells, &salt);
+}
+
+#[test]
+fn test_settle_revealed_rejects_bad_move_logs() {
+    let (env, client, _hub, _verifier, _player1, _player2) = setup_test();
+    let (player1, player2) = setup_signed_game(&env, &client);
+
+    let cells = Bytes::from_array(&env, &SIGNED_BOARD);
+    let salt = BytesN::from_array(&env, &[0u8; 32]);
+    client.reveal_board(&SIGNED_SESSION_ID, &player1, &cells, &salt);
+    client.reveal_board(&SIGNED_SESSION_ID, &player2, &cells, &salt);
+
+    let unfinished = client.try_settle_revealed(&SIGNED_SESSION_ID, &player1, &signed_moves(&env, 6));
+    assert_number_guess_error(&unfinished, Error::InvalidMoveLog);
+
+    let mut overlong = signed_moves(&env, 7);
+    overlong.push_back(signed_moves(&env, 2).get_unchecked(1));
+    let past_winner = client.try_settle_revealed(&SIGNED_SESSION_ID, &player1, &overlong);
+    assert_number_guess_error(&past_winner, Error::InvalidMoveLog);
+
+    // A shot moved to another cell no longer matches its signature.
+    let mut forged = signed_moves(&env, 7);
+    let mut shot = forged.get_unchecked(1);
+    shot.x = 2;
+    forged.set(1, shot);
+    assert!(client.try_settle_revealed(&SIGNED_SESSION_ID, &player2, &forged).is_err());
+
+    let empty = client.try_settle_revealed(&SIGNED_SESSION_ID, &player1, &Vec::new(&env));
+    assert_number_guess_error(&empty, Error::InvalidTotalMoves);
+}
+
+#[test]
+fn test_unrevealed_board_forfeits_after_a_claim() {
+    let (env, client, hub, _verifier, _player1, _player2) = setup_test();
+    let (player1, player2) = setup_signed_game(&env, &client);
+
+    let cells = Bytes::from_array(&env, &SIGNED_BOARD);
+    let salt = BytesN::from_array(&env, &[0u8; 32]);
+    let unrevealed = client.try_claim_win(&SIGNED_SESSION_ID, &player1, &signed_moves(&env, 7));
+    assert_number_guess_error(&unrevealed, Error::BoardNotRevealed);
+
+    // Revealing alone starts no deadline.
+    client.reveal_board(&SIGNED_SESSION_ID, &player1, &cells, &salt);
+    let unclaimed = client.try_claim_reveal_forfeit(&SIGNED_SESSION_ID, &player1);
+    assert_number_guess_error(&unclaimed, Error::NoPendingClaim);
+
+    // Player 2 lost and never opens their board.
+    client.claim_win(&SIGNED_SESSION_ID, &player1, &signed_moves(&env, 7));
+    let ClaimState::Pending(claim) = client.get_game(&SIGNED_SESSION_ID).reveal_claim else {
+        panic!("expected a pending claim");
+    };
+    assert_eq!(claim.player, player1);
+    assert_eq!(claim.total_moves, 7);
+    assert_eq!(claim.deadline, env.ledger().sequence() + 17_280);
+    let twice = client.try_claim_win(&SIGNED_SESSION_ID, &player1, &signed_moves(&env, 7));
+    assert_number_guess_error(&twice, Error::ClaimPending);
+
+    env.ledger().set_sequence_number(claim.deadline);
+    let early = client.try_claim_reveal_forfeit(&SIGNED_SESSION_ID, &player1);
+    assert_number_guess_error(&early, Error::RevealDeadlineNotReached);
+    let not_claimant = client.try_claim_reveal_forfeit(&SIGNED_SESSION_ID, &player2);
+    assert_number_guess_error(&not_claimant, Error::NoPendingClaim);
+
+    env.ledger().set_sequence_number(claim.deadline + 1);
+    let late = client.try_reveal_board(&SIGNED_SESSION_ID, &player2, &cells, &salt);
+    assert_number_guess_error(&late, Error::RevealDeadlinePassed);
+
+    assert_eq!(client.claim_reveal_forfeit(&SIGNED_SESSION_ID, &player1), player1);
+    let game = client.get_game(&SIGNED_SESSION_ID);
+    assert_eq!(game.winner, Some(player1.clone()));
+    assert_eq!(game.outcome, Outcome::Forfeited);
+    assert_eq!(game.total_moves, Some(7));
+    assert_eq!(hub.result(&SIGNED_SESSION_ID), Some(true));
+
+    let again = client.try_claim_reveal_forfeit(&SIGNED_SESSION_ID, &player1);
+    assert_number_guess_error(&again, Error::GameAlreadyEnded);
+}
+
+#[test]
+fn test_claim_win_needs_a_log_ending_in_the_claimants_win() {
+    let (env, client, _hub, _verifier, _player1, _player2) = setup_test();
+    let (player1, player2) = setup_signed_game(&env, &client);
+
+    let cells = Bytes::from_array(&env, &SIGNED_BOARD);
+    let salt = BytesN::from_array(&env, &[0u8; 32]);
+    client.reveal_board(&SIGNED_SESSION_ID, &player2, &cells, &salt);
+
+    // The log's last shot is player 1's, and player 2 has fired only three before it.
+    let not_last = client.try_claim_win(&SIGNED_SESSION_ID, &player2, &signed_moves(&env, 7));
+    assert_number_guess_error(&not_last, Error::InvalidMoveLog);
+    let too_few = client.try_claim_win(&SIGNED_SESSION_ID, &player2, &signed_moves(&env, 6));
+    assert_number_guess_error(&too_few, Error::InvalidMoveLog);
+
+    client.reveal_board(&SIGNED_SESSION_ID, &player1, &cells, &salt);
+    let open = client.try_claim_win(&SIGNED_SESSION_ID, &player1, &signed_moves(&env, 7));
+    assert_number_guess_error(&open, Error::BoardRevealed);
+}
+
+#[test]
+fn test_contested_claim_is_withdrawn() {
+    let (env, client, _hub, _verifier, _player1, _player2) = setup_test();
+    let (player1, player2) = setup_signed_game(&env, &client);
+
+    let cells = Bytes::from_array(&env, &SIGNED_BOARD);
+    let salt = BytesN::from_array(&env, &[0u8; 32]);
+    client.reveal_board(&SIGNED_SESSION_ID, &player1, &cells, &salt);
+    let unclaimed = client.try_contest_claim(&SIGNED_SESSION_ID, &player2, &missed_moves(&env, 9));
+    assert_number_guess_error(&unclaimed, Error::NoPendingClaim);
+
+    // Player 1 claims after their fourth miss, but went on to shoot again.
+    client.claim_win(&SIGNED_SESSION_ID, &player1, &missed_moves(&env, 7));
+    let no_later_shot = client.try_contest_claim(&SIGNED_SESSION_ID, &player2, &missed_moves(&env, 8));
+    assert_number_guess_error(&no_later_shot, Error::InvalidMoveLog);
+    let other_game = client.try_contest_claim(&SIGNED_SESSION_ID, &player2, &shot_log(&env, &[
+        SIGNED_MOVES[0], MISSED_MOVES[1], MISSED_MOVES[2], MISSED_MOVES[3], MISSED_MOVES[4],
+        MISSED_MOVES[5], MISSED_MOVES[6], MISSED_MOVES[7], MISSED_MOVES[8],
+    ]));
+    assert!(other_game.is_err(), "a log that does not extend the claim must not contest it");
+
+    client.contest_claim(&SIGNED_SESSION_ID, &player2, &missed_moves(&env, 9));
+    assert_eq!(client.get_game(&SIGNED_SESSION_ID).reveal_claim, ClaimState::Unclaimed);
+    env.ledger().set_sequence_number(env.ledger().sequence() + 17_281);
+    let forfeit = client.try_claim_reveal_forfeit(&SIGNED_SESSION_ID, &player1);
+    assert_number_guess_error(&forfeit, Error::NoPendingClaim);
+}
+
+#[test]
+fn test_revealed_boards_cannot_be_forfeited() {
+    let (env, client, _hub, _verifier, _player1, _player2) = setup_test();
+    let (player1, player2) = setup_signed_game(&env, &client);
+
+    let cells = Bytes::from_array(&env, &SIGNED_BOARD);
+    let salt = BytesN::from_array(&env, &[0u8; 32]);
+    client.reveal_board(&SIGNED_SESSION_ID, &player1, &cells, &salt);
+    client.claim_win(&SIGNED_SESSION_ID, &player1, &signed_moves(&env, 7));
+    client.reveal_board(&SIGNED_SESSION_ID, &player2, &cells, &salt);
+
+    // Both boards are open in time, so the move log decides even after the deadline.
+    env.ledger().set_sequence_number(env.ledger().sequence() + 17_281);
+    let claim = client.try_claim_reveal_forfeit(&SIGNED_SESSION_ID, &player1);
+    assert_number_guess_error(&claim, Error::BoardRevealed);
+    assert_eq!(client.settle_revealed(&SIGNED_SESSION_ID, &player2, &signed_moves(&env, 7)), player1);
+}
+
+#[test]
+fn test_only_demo_games_settle_by_reveal() {
+    let (env, client, _hub, _verifier, _player1, _player2) = setup_test();
+    let [player1, player2] = SIGNED_PLAYER_KEYS.map(|key| {
+        Address::from_payload(&env, AddressPayload::AccountIdPublicKeyEd25519(bytes_from_hex(&env, key)))
+    });
+    let classic = Ruleset { mode: 1, ..Ruleset::default() };
+    client.start_game_with_rules(&SIGNED_SESSION_ID, &player1, &player2, &100_0000000, &100_0000000, &classic);
+    let commit = bytes_from_hex(&env, SAMPLE_BOARD_COMMIT);
+    client.set_board_commit(&SIGNED_SESSION_ID, &player1, &commit);
+
+    let cells = Bytes::from_array(&env, &SIGNED_BOARD);
+    let salt = BytesN::from_array(&env, &[0u8; 32]);
+    let reveal = client.try_reveal_board(&SIGNED_SESSION_ID, &player1, &cells, &salt);
+    assert_number_guess_error(&reveal, Error::RulesNotReplayable);
+    let claim = client.try_claim_win(&SIGNED_SESSION_ID, &player1, &signed_moves(&env, 7));
+    assert_number_guess_error(&claim, Error::RulesNotReplayable);
+    let settle = client.try_settle_revealed(&SIGNED_SESSION_ID, &player1, &signed_moves(&env, 7));
+    assert_number_guess_error(&settle, Error::RulesNotReplayable);
+}
+
+// ============================================================================
+// Free-for-all Tests
+// ============================================================================
+
+const FFA_SESSION_ID: u32 = 500;
+
+/// Starts a three-player free-for-all between `setup_test`'s players and a third account,
+/// with every board committed.
+fn setup_free_for_all(
+    env: &Env,
+    client: &ZkBattleshipContractClient<'static>,
+    player1: &Address,
+    player2: &Address,
+) -> Vec<Address> {
+    let player3 = Address::from_payload(
+        env,
+        AddressPayload::AccountIdPublicKeyEd25519(player_key(env, 3)),
+    );
+    let players = Vec::from_array(env, [player1.clone(), player2.clone(), player3]);
+    let points = Vec::from_array(env, [100i128, 200, 300]);
+    client.start_free_for_all(&FFA_SESSION_ID, &players, &points);
+    client.set_free_for_all_image_id(&BytesN::from_array(env, &[8u8; 32]));
+    for (seat, player) in players.iter().enumerate() {
+        client.set_free_for_all_commit(&FFA_SESSION_ID, &player, &BytesN::from_array(env, &[seat as u8 + 1; 32]));
+    }
+    players
+}
+
+fn free_for_all_hashes(env: &Env) -> (Vec<BytesN<32>>, Vec<BytesN<32>>) {
+    let board_hashes = Vec::from_array(env, [1u8, 2, 3].map(|seat| BytesN::from_array(env, &[seat; 32])));
+    let player_keys = Vec::from_array(env, [1u8, 2, 3].map(|seat| player_key(env, seat)));
+    (board_hashes, player_keys)
+}
+
+/// The free-for-all guest's journal (`FreeForAllOutput`) for `ranking`, in classic mode.
+fn free_for_all_journal(
+    env: &Env,
+    ranking: [u32; 3],
+    total_moves: u32,
+    board_hashes: &Vec<BytesN<32>>,
+    player_keys: Option<&Vec<BytesN<32>>>,
+) -> Bytes {
+    let mut journal = Bytes::new(env);
+    push_word(&mut journal, FFA_SESSION_ID);
+    push_word(&mut journal, ranking.len() as u32);
+    push_word(&mut journal, ranking[0]);
+    push_word(&mut journal, 1);
+    push_word(&mut journal, ranking.len() as u32);
+    for seat in ranking {
+        push_word(&mut journal, seat);
+    }
+    push_word(&mut journal, board_hashes.len());
+    for hash in board_hashes.iter() {
+        push_hash(&mut journal, &hash);
+    }
+    // transcript_hash
+    push_hash(&mut journal, &BytesN::from_array(env, &[0u8; 32]));
+    push_word(&mut journal, total_moves);
+    // mode, no_touching, then zeroed stats for every player
+    push_word(&mut journal, 0);
+    push_word(&mut journal, 0);
+    push_word(&mut journal, ranking.len() as u32);
+    for _ in 0..ranking.len() * 4 {
+        push_word(&mut journal, 0);
+    }
+    match player_keys {
+        Some(keys) => {
+            push_word(&mut journal, 1);
+            push_word(&mut journal, keys.len());
+            for key in keys.iter() {
+                push_hash(&mut journal, &key);
+            }
+        }
+        None => push_word(&mut journal, 0),
+    }
+    journal
+}
+
+#[test]
+fn test_free_for_all_settles_every_duel() {
+    let (env, client, hub, _verifier, player1, player2) = setup_test();
+    let players = setup_free_for_all(&env, &client, &player1, &player2);
+    let (board_hashes, player_keys) = free_for_all_hashes(&env);
+
+    let journal = free_for_all_journal(&env, [2, 3, 1], 12, &board_hashes, Some(&player_keys));
+    let seal = Bytes::from_array(&env, &[1, 9, 9]);
+    let ranking = Vec::from_array(&env, [2u32, 3, 1]);
+    let winner = client.submit_free_for_all_result(
+        &FFA_SESSION_ID,
+        &player1,
+        &ranking,
+        &12,
+        &board_hashes,
+        &player_keys,
+        &journal,
+        &seal,
+    );
+    assert_eq!(winner, player2);
+
+    // Duels (P1, P2), (P1, P3), (P2, P3) in hub sessions 500, 501, 502.
+    assert_eq!(hub.result(&FFA_SESSION_ID), Some(false));
+    assert_eq!(hub.result(&(FFA_SESSION_ID + 1)), Some(false));
+    assert_eq!(hub.result(&(FFA_SESSION_ID + 2)), Some(true));
+    assert_eq!(hub.result(&(FFA_SESSION_ID + 3)), None);
+
+    let game: FreeForAllGame = client.get_free_for_all(&FFA_SESSION_ID);
+    assert_eq!(game.players, players);
+    assert_eq!(game.ranking, ranking);
+    assert_eq!(game.total_moves, Some(12));
+    assert!(game.seal_hash.is_some());
+
+    let again = client.try_submit_free_for_all_result(
+        &FFA_SESSION_ID,
+        &player1,
+        &ranking,
+        &12,
+        &board_hashes,
+        &player_keys,
+        &journal,
+        &seal,
+    );
+    assert_number_guess_error(&again, Error::GameAlreadyEnded);
+}
+
+#[test]
+fn test_free_for_all_rejects_bad_players_and_rankings() {
+    let (env, client, _hub, _verifier, player1, player2) = setup_test();
+
+    let two = Vec::from_array(&env, [player1.clone(), player2.clone()]);
+    let result = client.try_start_free_for_all(&1, &two, &Vec::from_array(&env, [1i128, 1]));
+    assert_number_guess_error(&result, Error::InvalidPlayerCount);
+
+    let players = setup_free_for_all(&env, &client, &player1, &player2);
+    let duplicate = client.try_set_free_for_all_commit(
+        &FFA_SESSION_ID,
+        &players.get_unchecked(2),
+        &BytesN::from_array(&env, &[7u8; 32]),
+    );
+    assert_number_guess_error(&duplicate, Error::BoardCommitAlreadySet);
+
+    let (board_hashes, player_keys) = free_for_all_hashes(&env);
+    let journal = free_for_all_journal(&env, [3, 1, 2], 12, &board_hashes, Some(&player_keys));
+    let seal = Bytes::from_array(&env, &[1, 9, 9]);
+    let submit = |ranking: [u32; 3], board_hashes: &Vec<BytesN<32>>, player_keys: &Vec<BytesN<32>>| {
+        client.try_submit_free_for_all_result(
+            &FFA_SESSION_ID,
+            &player1,
+            &Vec::from_array(&env, ranking),
+            &12,
+            board_hashes,
+            player_keys,
+            &journal,
+            &seal,
+        )
+    };
+
+    assert_number_guess_error(&submit([1, 1, 2], &board_hashes, &player_keys), Error::InvalidRanking);
+    assert_number_guess_error(&submit([1, 2, 4], &board_hashes, &player_keys), Error::InvalidRanking);
+
+    let mut wrong_board = board_hashes.clone();
+    wrong_board.set(1, BytesN::from_array(&env, &[9u8; 32]));
+    assert_number_guess_error(&submit([1, 2, 3], &wrong_board, &player_keys), Error::BoardCommitMismatch);
+
+    let mut wrong_key = player_keys.clone();
+    wrong_key.set(2, player_key(&env, 1));
+    assert_number_guess_error(&submit([1, 2, 3], &board_hashes, &wrong_key), Error::PlayerKeyMismatch);
+
+    let outsider = Address::generate(&env);
+    let result = client.try_submit_free_for_all_result(
+        &FFA_SESSION_ID,
+        &outsider,
+        &Vec::from_array(&env, [1u32, 2, 3]),
+        &12,
+        &board_hashes,
+        &player_keys,
+        &journal,
+        &seal,
+    );
+    assert_number_guess_error(&result, Error::NotPlayer);
+
+    assert!(submit([3, 1, 2], &board_hashes, &player_keys).is_ok());
+}
+
+#[test]
+fn test_free_for_all_hub_sessions_must_be_free() {
+    let (env, client, _hub, _verifier, player1, player2) = setup_test();
+
+    let player3 = Address::from_payload(
+        &env,
+        AddressPayload::AccountIdPublicKeyEd25519(player_key(&env, 3)),
+    );
+    let players = Vec::from_array(&env, [player1.clone(), player2.clone(), player3]);
+    let points = Vec::from_array(&env, [100i128, 200, 300]);
+
+    // The third duel would be hub session u32::MAX + 1.
+    let overflow = client.try_start_free_for_all(&(u32::MAX - 1), &players, &points);
+    assert_number_guess_error(&overflow, Error::SessionIdOverflow);
+
+    // A two-player game already plays the second duel's hub session.
+    client.start_game(&(FFA_SESSION_ID + 1), &player1, &player2, &100_0000000, &100_0000000);
+    let taken = client.try_start_free_for_all(&FFA_SESSION_ID, &players, &points);
+    assert_number_guess_error(&taken, Error::HubSessionTaken);
+
+    // And the other way around: duels 3, 4 and 5 are reserved by the free-for-all.
+    client.start_free_for_all(&(FFA_SESSION_ID + 3), &players, &points);
+    let reused = client.try_start_game(&(FFA_SESSION_ID + 5), &player1, &player2, &1, &1);
+    assert_number_guess_error(&reused, Error::HubSessionTaken);
+}
+
+#[test]
+fn test_free_for_all_result_must_match_journal() {
+    let (env, client, _hub, _verifier, player1, player2) = setup_test();
+    let players = setup_free_for_all(&env, &client, &player1, &player2);
+    let (board_hashes, player_keys) = free_for_all_hashes(&env);
+
+    // A valid seal for a game seat 3 won; the submitter claims first place instead.
+    let seal = Bytes::from_array(&env, &[1, 9, 9]);
+    let proven = free_for_all_journal(&env, [3, 2, 1], 12, &board_hashes, Some(&player_keys));
+    let submit = |ranking: [u32; 3], total_moves: u32, journal: &Bytes| {
+        client.try_submit_free_for_all_result(
+            &FFA_SESSION_ID,
+            &player1,
+            &Vec::from_array(&env, ranking),
+            &total_moves,
+            &board_hashes,
+            &player_keys,
+            journal,
+            &seal,
+        )
+    };
+    assert_number_guess_error(&submit([1, 2, 3], 12, &proven), Error::InvalidRanking);
+    assert_number_guess_error(&submit([3, 2, 1], 10, &proven), Error::InvalidTotalMoves);
+
+    let mut other_boards = board_hashes.clone();
+    other_boards.set(0, BytesN::from_array(&env, &[9u8; 32]));
+    let journal = free_for_all_journal(&env, [3, 2, 1], 12, &other_boards, Some(&player_keys));
+    assert_number_guess_error(&submit([3, 2, 1], 12, &journal), Error::BoardCommitMismatch);
+
+    let mut other_keys = player_keys.clone();
+    other_keys.set(0, player_key(&env, 4));
+    let journal = free_for_all_journal(&env, [3, 2, 1], 12, &board_hashes, Some(&other_keys));
+    assert_number_guess_error(&submit([3, 2, 1], 12, &journal), Error::PlayerKeyMismatch);
+
+    let unsigned = free_for_all_journal(&env, [3, 2, 1], 12, &board_hashes, None);
+    assert_number_guess_error(&submit([3, 2, 1], 12, &unsigned), Error::PlayerKeyMismatch);
+
+    let truncated = proven.slice(0..proven.len() - 4);
+    assert_number_guess_error(&submit([3, 2, 1], 12, &truncated), Error::InvalidJournal);
+
+    assert_eq!(submit([3, 2, 1], 12, &proven), Ok(Ok(players.get_unchecked(2))));
+}
+
+// ============================================================================
+// Team Tests
+// ============================================================================
+
+const TEAM_SESSION_ID: u32 = 600;
+
+/// Starts a 2v2 game between `setup_test`'s players and two more accounts, seated 1 to 4
+/// by key number, with both team boards committed.
+fn setup_team_game(
+    env: &Env,
+    client: &ZkBattleshipContractClient<'static>,
+    player1: &Address,
+    player2: &Address,
+) -> Vec<Address> {
+    let account = |seat| Address::from_payload(env, AddressPayload::AccountIdPublicKeyEd25519(player_key(env, seat)));
+    let players = Vec::from_array(env, [player1.clone(), player2.clone(), account(3), account(4)]);
+    let points = Vec::from_array(env, [100i128, 200, 300, 400]);
+    client.start_team_game(&TEAM_SESSION_ID, &players, &points);
+    client.set_team_image_id(&BytesN::from_array(env, &[7u8; 32]));
+    for team in 1..=2u32 {
+        client.set_team_commit(&TEAM_SESSION_ID, &team, &BytesN::from_array(env, &[team as u8; 32]));
+    }
+    players
+}
+
+fn team_hashes(env: &Env) -> (Vec<BytesN<32>>, Vec<BytesN<32>>) {
+    let board_hashes = Vec::from_array(env, [1u8, 2].map(|team| BytesN::from_array(env, &[team; 32])));
+    let player_keys = Vec::from_array(env, [1u8, 2, 3, 4].map(|seat| player_key(env, seat)));
+    (board_hashes, player_keys)
+}
+
+/// The teams guest's journal (`TeamOutput`) for a classic-mode win by team `winner`.
+fn team_journal(
+    env: &Env,
+    winner: u32,
+    total_moves: u32,
+    board_hashes: &Vec<BytesN<32>>,
+    player_keys: Option<&Vec<BytesN<32>>>,
+) -> Bytes {
+    let mut journal = Bytes::new(env);
+    push_word(&mut journal, TEAM_SESSION_ID);
+    push_word(&mut journal, winner);
+    push_word(&mut journal, 1);
+    for hash in board_hashes.iter() {
+        push_hash(&mut journal, &hash);
+    }
+    // transcript_hash
+    push_hash(&mut journal, &BytesN::from_array(env, &[0u8; 32]));
+    push_word(&mut journal, total_moves);
+    // mode, no_touching, zeroed stats for both teams
+    for _ in 0..2 + 2 * 4 {
+        push_word(&mut journal, 0);
+    }
+    match player_keys {
+        Some(keys) => {
+            push_word(&mut journal, 1);
+            for key in keys.iter() {
+                push_hash(&mut journal, &key);
+            }
+        }
+        None => push_word(&mut journal, 0),
+    }
+    journal
+}
+
+#[test]
+fn test_team_game_settles_both_pairings() {
+    let (env, client, hub, _verifier, player1, player2) = setup_test();
+    let players = setup_team_game(&env, &client, &player1, &player2);
+    let (board_hashes, player_keys) = team_hashes(&env);
+
+    let journal = team_journal(&env, 2, 7, &board_hashes, Some(&player_keys));
+    let seal = Bytes::from_array(&env, &[1, 9, 9]);
+    let winners = client.submit_team_result(
+        &TEAM_SESSION_ID,
+        &player1,
+        &2,
+        &7,
+        &board_hashes,
+        &player_keys,
+        &journal,
+        &seal,
+    );
+    assert_eq!(winners, Vec::from_array(&env, [player2, players.get_unchecked(3)]));
+
+    // Pairings (P1, P2) and (P3, P4) in hub sessions 600 and 601, both lost by team 1.
+    assert_eq!(hub.result(&TEAM_SESSION_ID), Some(false));
+    assert_eq!(hub.result(&(TEAM_SESSION_ID + 1)), Some(false));
+    assert_eq!(hub.result(&(TEAM_SESSION_ID + 2)), None);
+
+    let game: TeamGame = client.get_team_game(&TEAM_SESSION_ID);
+    assert_eq!(game.players, players);
+    assert_eq!(game.winner, Some(2));
+    assert_eq!(game.total_moves, Some(7));
+    assert!(game.seal_hash.is_some());
+
+    let again = client.try_submit_team_result(
+        &TEAM_SESSION_ID,
+        &player1,
+        &2,
+        &7,
+        &board_hashes,
+        &player_keys,
+        &journal,
+        &seal,
+    );
+    assert_number_guess_error(&again, Error::GameAlreadyEnded);
+}
+
+#[test]
+fn test_team_game_rejects_bad_teams_and_results() {
+    let (env, client, _hub, _verifier, player1, player2) = setup_test();
+
+    let three = Vec::from_array(&env, [player1.clone(), player2.clone(), Address::generate(&env)]);
+    let result = client.try_start_team_game(&1, &three, &Vec::from_array(&env, [1i128, 1, 1]));
+    assert_number_guess_error(&result, Error::InvalidPlayerCount);
+
+    setup_team_game(&env, &client, &player1, &player2);
+    let commit = BytesN::from_array(&env, &[5u8; 32]);
+    assert_number_guess_error(&client.try_set_team_commit(&TEAM_SESSION_ID, &3, &commit), Error::InvalidTeam);
+    assert_number_guess_error(
+        &client.try_set_team_commit(&TEAM_SESSION_ID, &1, &commit),
+        Error::BoardCommitAlreadySet,
+    );
+
+    let (board_hashes, player_keys) = team_hashes(&env);
+    let journal = team_journal(&env, 1, 7, &board_hashes, Some(&player_keys));
+    let seal = Bytes::from_array(&env, &[1, 9, 9]);
+    let submit = |winner: u32, board_hashes: &Vec<BytesN<32>>, player_keys: &Vec<BytesN<32>>| {
+        client.try_submit_team_result(
+            &TEAM_SESSION_ID,
+            &player2,
+            &winner,
+            &7,
+            board_hashes,
+            player_keys,
+            &journal,
+            &seal,
+        )
+    };
+
+    assert_number_guess_error(&submit(0, &board_hashes, &player_keys), Error::InvalidWinner);
+    assert_number_guess_error(&submit(3, &board_hashes, &player_keys), Error::InvalidWinner);
+
+    let swapped = Vec::from_array(&env, [board_hashes.get_unchecked(1), board_hashes.get_unchecked(0)]);
+    assert_number_guess_error(&submit(1, &swapped, &player_keys), Error::BoardCommitMismatch);
+
+    let mut short = player_keys.clone();
+    short.pop_back();
+    assert_number_guess_error(&submit(1, &board_hashes, &short), Error::InvalidPlayerCount);
+
+    let mut wrong_key = player_keys.clone();
+    wrong_key.set(3, player_key(&env, 2));
+    assert_number_guess_error(&submit(1, &board_hashes, &wrong_key), Error::PlayerKeyMismatch);
+
+    let winners = submit(1, &board_hashes, &player_keys).unwrap().unwrap();
+    assert_eq!(winners.get_unchecked(0), player1);
+}
+
+#[test]
+fn test_team_result_must_match_journal() {
+    let (env, client, _hub, _verifier, player1, player2) = setup_test();
+    let players = setup_team_game(&env, &client, &player1, &player2);
+    let (board_hashes, player_keys) = team_hashes(&env);
+
+    // A valid seal for a team 2 win; team 1 claims it instead.
+    let seal = Bytes::from_array(&env, &[1, 9, 9]);
+    let proven = team_journal(&env, 2, 7, &board_hashes, Some(&player_keys));
+    let submit = |winner: u32, total_moves: u32, journal: &Bytes| {
+        client.try_submit_team_result(
+            &TEAM_SESSION_ID,
+            &player1,
+            &winner,
+            &total_moves,
+            &board_hashes,
+            &player_keys,
+            journal,
+            &seal,
+        )
+    };
+    assert_number_guess_error(&submit(1, 7, &proven), Error::InvalidWinner);
+    assert_number_guess_error(&submit(2, 8, &proven), Error::InvalidTotalMoves);
+
+    let swapped = Vec::from_array(&env, [board_hashes.get_unchecked(1), board_hashes.get_unchecked(0)]);
+    let journal = team_journal(&env, 2, 7, &swapped, Some(&player_keys));
+    assert_number_guess_error(&submit(2, 7, &journal), Error::BoardCommitMismatch);
+
+    let mut other_keys = player_keys.clone();
+    other_keys.set(1, player_key(&env, 5));
+    let journal = team_journal(&env, 2, 7, &board_hashes, Some(&other_keys));
+    assert_number_guess_error(&submit(2, 7, &journal), Error::PlayerKeyMismatch);
+
+    let unsigned = team_journal(&env, 2, 7, &board_hashes, None);
+    assert_number_guess_error(&submit(2, 7, &unsigned), Error::PlayerKeyMismatch);
+
+    let winners = submit(2, 7, &proven).unwrap().unwrap();
+    assert_eq!(winners, Vec::from_array(&env, [player2, players.get_unchecked(3)]));
+}
+
+#[test]
+fn test_team_hub_sessions_must_be_free() {
+    let (env, client, _hub, _verifier, player1, player2) = setup_test();
+
+    let account = |seat| Address::from_payload(&env, AddressPayload::AccountIdPublicKeyEd25519(player_key(&env, seat)));
+    let players = Vec::from_array(&env, [player1.clone(), player2.clone(), account(3), account(4)]);
+    let points = Vec::from_array(&env, [100i128, 200, 300, 400]);
+
+    // The second pairing would be hub session u32::MAX + 1.
+    let overflow = client.try_start_team_game(&u32::MAX, &players, &points);
+    assert_number_guess_error(&overflow, Error::SessionIdOverflow);
+
+    // A two-player game already plays the second pairing's hub session.
+    client.start_game(&(TEAM_SESSION_ID + 1), &player1, &player2, &100_0000000, &100_0000000);
+    let taken = client.try_start_team_game(&TEAM_SESSION_ID, &players, &points);
+    assert_number_guess_error(&taken, Error::HubSessionTaken);
+
+    // And the other way around: the second pairing of a team game is reserved too.
+    client.start_team_game(&(TEAM_SESSION_ID + 2), &players, &points);
+    let reused = client.try_start_game(&(TEAM_SESSION_ID + 3), &player1, &player2, &1, &1);
+    assert_number_guess_error(&reused, Error::HubSessionTaken);
+}
 
 // ============================================================================
 // Admin Function Tests
@@ -1,4 +1,6 @@
 import { useState, useEffect, useRef } from 'react';
+import { StrKey, hash } from '@stellar/stellar-sdk';
+import { Buffer } from 'buffer';
 import { ZkBattleshipService, type ZkProofPayload } from './zkBattleshipService';
 import { requestCache, createCacheKey } from '@/utils/requestCache';
 import { useWallet } from '@/hooks/useWallet';
@@ -21,6 +23,13 @@ const createRandomSessionId = (): number => {
   return (Math.floor(Math.random() * 0xffffffff) >>> 0) || 1;
 };
 
+// Secret per-board salt; each cell's Merkle leaf salt is derived from it by the prover.
+const createBoardSalt = (): number[] => {
+  const buffer = new Uint8Array(32);
+  crypto.getRandomValues(buffer);
+  return Array.from(buffer);
+};
+
 const toHex = (value: unknown): string | null => {
   if (value === null || value === undefined) return null;
 
@@ -60,6 +69,23 @@ type LocalMove = {
   cell: number;
   x: number;
   y: number;
+  /** Shooter's ed25519 signature over the move message. */
+  signature: number[];
+  /** sha256 of the move message, chained into the next move's message. */
+  hash: number[];
+};
+
+// Same bytes as the prover's `move_message` (rules/src/transcript.rs) for a plain shot:
+// session id and move index big-endian, the previous move's hash, then player, x, y and
+// action code 0.
+const buildMoveMessage = (sessionId: number, index: number, previous: number[], move: Pick<LocalMove, 'player' | 'x' | 'y'>): Uint8Array => {
+  const message = new Uint8Array(44);
+  const view = new DataView(message.buffer);
+  view.setUint32(0, sessionId);
+  view.setUint32(4, index);
+  message.set(previous, 8);
+  message.set([move.player, move.x, move.y, 0], 40);
+  return message;
 };
 
 interface ZkBattleshipGameProps {
@@ -117,6 +143,8 @@ export function ZkBattleshipGame({
   const [boardP2Cells, setBoardP2Cells] = useState<number[]>([]);
   const [boardP1Locked, setBoardP1Locked] = useState(false);
   const [boardP2Locked, setBoardP2Locked] = useState(false);
+  const [boardSaltP1, setBoardSaltP1] = useState<number[]>([]);
+  const [boardSaltP2, setBoardSaltP2] = useState<number[]>([]);
   const [submittedProof, setSubmittedProof] = useState<ZkProofPayload | null>(null);
   const [settlementTxHash, setSettlementTxHash] = useState<string | null>(null);
   const [verifierContract, setVerifierContract] = useState<string | null>(null);
@@ -142,6 +170,8 @@ export function ZkBattleshipGame({
     setBoardP2Cells([]);
     setBoardP1Locked(false);
     setBoardP2Locked(false);
+    setBoardSaltP1([]);
+    setBoardSaltP2([]);
     setShotCell(null);
     setSubmittedProof(null);
     setSettlementTxHash(null);
@@ -208,6 +238,8 @@ export function ZkBattleshipGame({
     setBoardP2Cells([]);
     setBoardP1Locked(false);
     setBoardP2Locked(false);
+    setBoardSaltP1([]);
+    setBoardSaltP2([]);
     setSubmittedProof(null);
     setSettlementTxHash(null);
     setVerifierContract(null);
@@ -241,10 +273,12 @@ export function ZkBattleshipGame({
     const publicOutput = parsed.public_output ?? parsed.publicOutput ?? parsed;
 
     const winnerValue = publicOutput.winner;
+    const outcomeValue = publicOutput.outcome ?? 'all_sunk';
     const totalMovesValue = publicOutput.total_moves ?? publicOutput.totalMoves;
     const proofSessionId = publicOutput.session_id ?? publicOutput.sessionId;
     const boardHashP1 = publicOutput.board_hash_p1 ?? publicOutput.boardHashP1Hex;
     const boardHashP2 = publicOutput.board_hash_p2 ?? publicOutput.boardHashP2Hex;
+    const playerKeys = publicOutput.player_keys ?? publicOutput.playerKeysHex;
     const journalHex = parsed.journal_hex ?? parsed.journalHex;
     const sealHex = parsed.seal_hex ?? parsed.sealHex;
 
@@ -256,6 +290,12 @@ export function ZkBattleshipGame({
       throw new Error('Proof winner must be 1 or 2');
     }
 
+    const outcomeCodes: Record<string, 1 | 2> = { all_sunk: 1, resigned: 2 };
+    const outcome = outcomeCodes[outcomeValue];
+    if (outcome === undefined) {
+      throw new Error(`Proof outcome must be all_sunk or resigned, got ${outcomeValue}`);
+    }
+
     const totalMoves = Number(totalMovesValue);
     if (!Number.isFinite(totalMoves) || totalMoves <= 0) {
       throw new Error('Proof total_moves must be a positive number');
@@ -265,11 +305,17 @@ export function ZkBattleshipGame({
       throw new Error('Missing required proof fields (board hashes, journal, or seal)');
     }
 
+    if (!Array.isArray(playerKeys) || playerKeys.length !== 2 || !playerKeys.every((key) => typeof key === 'string')) {
+      throw new Error('Proof is for an unsigned game: submit_result needs moves signed with both player keys');
+    }
+
     return {
       winner: winnerValue,
+      outcome,
       totalMoves,
       boardHashP1Hex: boardHashP1,
       boardHashP2Hex: boardHashP2,
+      playerKeysHex: [playerKeys[0], playerKeys[1]],
       journalHex,
       sealHex,
     };
@@ -1052,11 +1098,13 @@ export function ZkBattleshipGame({
     setError(null);
 
     if (setupStep === 1) {
+      setBoardSaltP1(createBoardSalt());
       setBoardP1Locked(true);
       setSuccess('Player 1 board locked. Switch to Player 2 to place ships.');
       return;
     }
 
+    setBoardSaltP2(createBoardSalt());
     setBoardP2Locked(true);
     setSuccess('Player 2 board locked. Setup complete, proceed with shots.');
   };
@@ -1094,9 +1142,32 @@ export function ZkBattleshipGame({
       return;
     }
 
+    // The contract only settles signed games, checked against the players' account keys,
+    // so every shot is signed by the shooter's wallet as it is taken.
+    if (walletType !== 'dev' || devWalletService.getPublicKey() !== userAddress) {
+      setError('Shots must be signed with the player account key; connect a dev wallet to play.');
+      return;
+    }
+
     const x = (shotCell - 1) % 4;
     const y = Math.floor((shotCell - 1) / 4);
-    const move: LocalMove = { player: player as 1 | 2, cell: shotCell, x, y };
+    const previous = moves.length > 0 ? moves[moves.length - 1].hash : new Array<number>(32).fill(0);
+    const message = buildMoveMessage(sessionId, moves.length, previous, { player: player as 1 | 2, x, y });
+    let signature: number[];
+    try {
+      signature = Array.from(devWalletService.signMessage(message));
+    } catch (err) {
+      setError(err instanceof Error ? err.message : 'Failed to sign shot');
+      return;
+    }
+    const move: LocalMove = {
+      player: player as 1 | 2,
+      cell: shotCell,
+      x,
+      y,
+      signature,
+      hash: Array.from(hash(Buffer.from(message))),
+    };
 
     const isHit = player === 1 ? boardP2Cells.includes(shotCell) : boardP1Cells.includes(shotCell);
 
@@ -1150,7 +1221,12 @@ export function ZkBattleshipGame({
     session_id: sessionId,
     board_p1: buildBoardArray(boardP1Cells),
     board_p2: buildBoardArray(boardP2Cells),
-    moves: moves.map((move) => ({ player: move.player, x: move.x, y: move.y })),
+    board_salt_p1: boardSaltP1,
+    board_salt_p2: boardSaltP2,
+    player_keys: gameState
+      ? [gameState.player1, gameState.player2].map((address) => Array.from(StrKey.decodeEd25519PublicKey(address)))
+      : undefined,
+    moves: moves.map((move) => ({ player: move.player, x: move.x, y: move.y, signature: move.signature })),
   });
 
   const handleGenerateGameInputJson = () => {
@@ -1162,6 +1238,10 @@ export function ZkBattleshipGame({
       setError('No moves recorded yet.');
       return;
     }
+    if (!gameState) {
+      setError('Load the on-chain game before generating game-input.json: its player keys are needed.');
+      return;
+    }
 
     const payload = buildGameInputPayload();
     const json = JSON.stringify(payload, null, 2);
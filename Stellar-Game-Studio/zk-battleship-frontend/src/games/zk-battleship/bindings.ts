@@ -45,18 +45,75 @@ export interface Game {
   board_hash_p1: Option<Buffer>;
   board_hash_p2: Option<Buffer>;
   journal_hash: Option<Buffer>;
+  outcome: Outcome;
   player1: string;
   player1_guess: Option<u32>;
   player1_points: i128;
   player2: string;
   player2_guess: Option<u32>;
   player2_points: i128;
+  reveal_claim: ClaimState;
+  revealed_board_p1: Option<u32>;
+  revealed_board_p2: Option<u32>;
+  ruleset: Ruleset;
   seal_hash: Option<Buffer>;
   total_moves: Option<u32>;
   winner: Option<string>;
   winning_number: Option<u32>;
 }
 
+
+export interface Ruleset {
+  bombs: u32;
+  hit_keeps_turn: boolean;
+  line_strikes: u32;
+  mine_penalty: u32;
+  mines: u32;
+  mode: u32;
+  no_touching: boolean;
+  salvo: boolean;
+  sonar_pings: u32;
+}
+
+
+export interface RevealClaim {
+  deadline: u32;
+  last_hash: Buffer;
+  player: string;
+  total_moves: u32;
+}
+
+export type ClaimState = {tag: "Unclaimed", values: void} | {tag: "Pending", values: readonly [RevealClaim]};
+
+
+export interface RevealedShot {
+  signature: Buffer;
+  x: u32;
+  y: u32;
+}
+
+
+export interface FreeForAllGame {
+  board_commits: Array<Option<Buffer>>;
+  journal_hash: Option<Buffer>;
+  players: Array<string>;
+  points: Array<i128>;
+  ranking: Array<u32>;
+  seal_hash: Option<Buffer>;
+  total_moves: Option<u32>;
+}
+
+
+export interface TeamGame {
+  board_commits: Array<Option<Buffer>>;
+  journal_hash: Option<Buffer>;
+  players: Array<string>;
+  points: Array<i128>;
+  seal_hash: Option<Buffer>;
+  total_moves: Option<u32>;
+  winner: Option<u32>;
+}
+
 export const Errors = {
   1: {message:"GameNotFound"},
   2: {message:"NotPlayer"},
@@ -70,10 +127,30 @@ export const Errors = {
   10: {message:"ProofVerificationFailed"},
   11: {message:"BoardCommitAlreadySet"},
   12: {message:"BoardCommitNotSet"},
-  13: {message:"BoardCommitMismatch"}
+  13: {message:"BoardCommitMismatch"},
+  14: {message:"InvalidOutcome"},
+  15: {message:"PlayerKeyMismatch"},
+  16: {message:"BoardNotRevealed"},
+  17: {message:"InvalidBoard"},
+  18: {message:"InvalidMoveLog"},
+  19: {message:"InvalidPlayerCount"},
+  20: {message:"InvalidRanking"},
+  21: {message:"InvalidTeam"},
+  22: {message:"InvalidJournal"},
+  23: {message:"RevealDeadlinePassed"},
+  24: {message:"RevealDeadlineNotReached"},
+  25: {message:"BoardRevealed"},
+  26: {message:"RulesetMismatch"},
+  27: {message:"RulesNotReplayable"},
+  28: {message:"ClaimPending"},
+  29: {message:"NoPendingClaim"},
+  30: {message:"SessionIdOverflow"},
+  31: {message:"HubSessionTaken"}
 }
 
-export type DataKey = {tag: "Game", values: readonly [u32]} | {tag: "GameHubAddress", values: void} | {tag: "VerifierAddress", values: void} | {tag: "VerifierImageId", values: void} | {tag: "Admin", values: void};
+export type Outcome = {tag: "Pending", values: void} | {tag: "SunkAll", values: void} | {tag: "Resigned", values: void} | {tag: "Forfeited", values: void};
+
+export type DataKey = {tag: "Game", values: readonly [u32]} | {tag: "FreeForAll", values: readonly [u32]} | {tag: "Team", values: readonly [u32]} | {tag: "HubSession", values: readonly [u32]} | {tag: "GameHubAddress", values: void} | {tag: "VerifierAddress", values: void} | {tag: "VerifierImageId", values: void} | {tag: "FreeForAllImageId", values: void} | {tag: "TeamImageId", values: void} | {tag: "AggregateImageId", values: void} | {tag: "Admin", values: void};
 
 export interface Client {
   /**
@@ -147,7 +224,7 @@ export interface Client {
 
   /**
    * Construct and simulate a start_game transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
-   * Start a new game between two players with points.
+   * Start a new game between two players with points, under the demo ruleset.
    * This creates a session in the Game Hub and locks points before starting the game.
    * 
    * **CRITICAL:** This method requires authorization from THIS contract (not players).
@@ -162,6 +239,20 @@ export interface Client {
    */
   start_game: ({session_id, player1, player2, player1_points, player2_points}: {session_id: u32, player1: string, player2: string, player1_points: i128, player2_points: i128}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
 
+  /**
+   * Construct and simulate a start_game_with_rules transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Start a new game like `start_game`, under any ruleset the guest supports.
+   * 
+   * Both players authorize the ruleset along with their points, and `submit_result` only
+   * accepts a journal committing exactly this ruleset (`RulesetMismatch` otherwise). Only
+   * demo games can be settled without a proof: the reveal path fails with
+   * `RulesNotReplayable` for anything else.
+   * 
+   * # Arguments
+   * * `rules` - The agreed ruleset
+   */
+  start_game_with_rules: ({session_id, player1, player2, player1_points, player2_points, rules}: {session_id: u32, player1: string, player2: string, player1_points: i128, player2_points: i128, rules: Ruleset}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
+
   /**
    * Construct and simulate a get_image_id transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
    * Get verifier image id
@@ -186,6 +277,84 @@ export interface Client {
    */
   set_verifier: ({verifier}: {verifier: string}, options?: MethodOptions) => Promise<AssembledTransaction<null>>
 
+  /**
+   * Construct and simulate a verify_cell_opening transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Check one opened cell of a player's committed board, without any zk proof.
+   * 
+   * `index` is the row-major cell index, `cell` the claimed contents (0 water, 1 ship,
+   * 2 mine; `InvalidBoard` for anything else), `salt` the cell's salt and `siblings` the
+   * Merkle path from the leaf up, as written by the host's `open-cell` command. Returns
+   * whether the opening matches the player's board commit.
+   */
+  verify_cell_opening: ({session_id, player, index, cell, salt, siblings}: {session_id: u32, player: string, index: u32, cell: u32, salt: Buffer, siblings: Array<Buffer>}, options?: MethodOptions) => Promise<AssembledTransaction<Result<boolean>>>
+
+  /**
+   * Construct and simulate a reveal_board transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Open a player's whole board after the match, for settlement without a zk proof.
+   * 
+   * `cells` are the 16 row-major demo cells (1 = ship) and `board_salt` the secret the
+   * commitment was salted with; together they must rebuild the player's `board_commit`
+   * (`BoardCommitMismatch` otherwise) and hold two size-2 ships (`InvalidBoard`). Only
+   * demo games can be revealed (`RulesNotReplayable`), and only until they are settled.
+   * 
+   * Revealing starts no deadline by itself; the opponent of a pending `claim_win` can
+   * reveal until its deadline (`RevealDeadlinePassed` after it).
+   */
+  reveal_board: ({session_id, player, cells, board_salt}: {session_id: u32, player: string, cells: Buffer, board_salt: Buffer}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
+
+  /**
+   * Construct and simulate a settle_revealed transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Settle a match without a zk proof by replaying its move log against both revealed
+   * boards.
+   * 
+   * This is the transparent fallback to `submit_result` for games under the demo ruleset
+   * (`RulesNotReplayable` otherwise): players alternate single shots, player 1 first,
+   * until one side has hit every opposing ship cell. Each shot must carry its shooter's
+   * signature over the same hash-chained message the zk guest checks, by the ed25519 key
+   * of the player's account. A bad signature aborts the call; a log that repeats a shot,
+   * leaves the board, continues past the winning shot or ends without a winner fails with
+   * `InvalidMoveLog`.
+   */
+  settle_revealed: ({session_id, submitter, moves}: {session_id: u32, submitter: string, moves: Array<RevealedShot>}, options?: MethodOptions) => Promise<AssembledTransaction<Result<string>>>
+
+  /**
+   * Construct and simulate a claim_win transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Claim a win with the signed move log up to the claimant's winning shot, starting a
+   * window of `REVEAL_WINDOW_LEDGERS` in which the opponent has to reveal their board or
+   * contest the claim.
+   * 
+   * The claimant must have revealed their own board first (`BoardNotRevealed`); once both
+   * boards are open, `settle_revealed` decides instead (`BoardRevealed`). The log is
+   * checked like in `settle_revealed`, minus the opponent's board, which is still closed:
+   * it must end with a shot of the claimant, hold at least as many of their shots as there
+   * are ship cells, and not already sink the claimant's own board (`InvalidMoveLog`
+   * otherwise). Only one claim can be pending (`ClaimPending`).
+   */
+  claim_win: ({session_id, player, moves}: {session_id: u32, player: string, moves: Array<RevealedShot>}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
+
+  /**
+   * Construct and simulate a contest_claim transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Withdraw a pending `claim_win` by showing the game went on past the claimed shot.
+   * 
+   * `moves` must be a signed log that extends the claimed one, the same moves up to the
+   * claim's last hash, with at least one more shot by the claimant after it
+   * (`InvalidMoveLog` otherwise). Only possible before the claim's deadline
+   * (`RevealDeadlinePassed`); fails with `NoPendingClaim` without a claim.
+   */
+  contest_claim: ({session_id, player, moves}: {session_id: u32, player: string, moves: Array<RevealedShot>}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
+
+  /**
+   * Construct and simulate a claim_reveal_forfeit transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Win a match whose opponent let the deadline of the caller's `claim_win` pass without
+   * revealing their board or contesting the claim.
+   * 
+   * Fails with `NoPendingClaim` unless the caller's claim is pending,
+   * `RevealDeadlineNotReached` until its deadline has passed and `BoardRevealed` if the
+   * opponent revealed in time (settle with `settle_revealed`). The game is recorded as
+   * `Forfeited`, with the claimed log's move count.
+   */
+  claim_reveal_forfeit: ({session_id, player}: {session_id: u32, player: string}, options?: MethodOptions) => Promise<AssembledTransaction<Result<string>>>
+
   /**
    * Construct and simulate a reveal_winner transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
    * Reveal the winner of the game and submit outcome to GameHub.
@@ -206,9 +375,25 @@ export interface Client {
    * 
    * This call performs on-chain proof verification via the configured verifier contract.
    * The verifier address and image id must be configured by admin using `set_verifier`
-   * and `set_image_id` before submissions are accepted.
+   * and `set_image_id` before submissions are accepted. Proofs aggregated from per-turn
+   * receipts verify under the aggregation guest's id instead, set with
+   * `set_aggregate_image_id`; with both set, the seal is checked against either.
+   * 
+   * `outcome` is the journal's reason code: 1 when the winner sank every ship, 2 when
+   * the loser resigned. It must be the outcome the journal commits, which is what the
+   * game records (`InvalidOutcome` otherwise); a journal that does not decode as the
+   * guest's output for this session fails with `InvalidJournal`.
+   * 
+   * `player_key_p1`/`player_key_p2` are the keys the guest checked every move's signature
+   * against; they must be the ed25519 keys of the `player1`/`player2` accounts.
+   * 
+   * The journal must commit the ruleset the game was started with (`RulesetMismatch`).
+   * 
+   * `winner`, `total_moves`, the board hashes and the player keys must also be what the
+   * journal commits, failing with the same errors as their other checks. A journal without
+   * player keys (an unsigned game) fails with `PlayerKeyMismatch`.
    */
-  submit_result: ({session_id, submitter, winner, total_moves, board_hash_p1, board_hash_p2, journal, seal}: {session_id: u32, submitter: string, winner: u32, total_moves: u32, board_hash_p1: Buffer, board_hash_p2: Buffer, journal: Buffer, seal: Buffer}, options?: MethodOptions) => Promise<AssembledTransaction<Result<string>>>
+  submit_result: ({session_id, submitter, winner, outcome, total_moves, board_hash_p1, board_hash_p2, player_key_p1, player_key_p2, journal, seal}: {session_id: u32, submitter: string, winner: u32, outcome: u32, total_moves: u32, board_hash_p1: Buffer, board_hash_p2: Buffer, player_key_p1: Buffer, player_key_p2: Buffer, journal: Buffer, seal: Buffer}, options?: MethodOptions) => Promise<AssembledTransaction<Result<string>>>
 
   /**
    * Construct and simulate a set_board_commit transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
@@ -218,6 +403,141 @@ export interface Client {
    */
   set_board_commit: ({session_id, player, board_commit}: {session_id: u32, player: string, board_commit: Buffer}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
 
+  /**
+   * Construct and simulate a start_free_for_all transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Start a free-for-all between three or four players.
+   * 
+   * The Game Hub only knows two-player sessions, so every pair of players gets its own:
+   * the k-th pair in `(1, 2), (1, 3), ..., (2, 3), ...` order plays hub session
+   * `session_id + k`, and each player commits their points to every one of their duels.
+   * Those hub session ids must not be used by another game of this contract
+   * (`HubSessionTaken`) nor run past `u32::MAX` (`SessionIdOverflow`).
+   * 
+   * # Arguments
+   * * `session_id` - Session of the free-for-all, and of its first duel in the Game Hub
+   * * `players` - Addresses in seat order
+   * * `points` - Points each player commits per duel, in seat order
+   */
+  start_free_for_all: ({session_id, players, points}: {session_id: u32, players: Array<string>, points: Array<i128>}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
+
+  /**
+   * Construct and simulate a set_free_for_all_commit transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Store a free-for-all player's board commitment, as `set_board_commit` does for
+   * two-player games.
+   */
+  set_free_for_all_commit: ({session_id, player, board_commit}: {session_id: u32, player: string, board_commit: Buffer}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
+
+  /**
+   * Construct and simulate a submit_free_for_all_result transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Submit a zk-verified free-for-all ranking and settle every duel in Game Hub.
+   * 
+   * The proof must be for the free-for-all guest, whose image id the admin sets with
+   * `set_free_for_all_image_id`. `ranking` lists every seat (1-based) from first to last
+   * place; each duel is won by whichever of its two players placed higher.
+   * `board_hashes` and `player_keys` are in seat order and must match the board commits
+   * and the players' account keys, as in `submit_result`. The ranking, `total_moves`, the
+   * hashes and the keys must all be what the journal commits, and a journal without
+   * player keys fails with `PlayerKeyMismatch`.
+   */
+  submit_free_for_all_result: ({session_id, submitter, ranking, total_moves, board_hashes, player_keys, journal, seal}: {session_id: u32, submitter: string, ranking: Array<u32>, total_moves: u32, board_hashes: Array<Buffer>, player_keys: Array<Buffer>, journal: Buffer, seal: Buffer}, options?: MethodOptions) => Promise<AssembledTransaction<Result<string>>>
+
+  /**
+   * Construct and simulate a get_free_for_all transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Get free-for-all information.
+   */
+  get_free_for_all: ({session_id}: {session_id: u32}, options?: MethodOptions) => Promise<AssembledTransaction<Result<FreeForAllGame>>>
+
+  /**
+   * Construct and simulate a set_aggregate_image_id transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Set the aggregation guest's image id (admin only)
+   * 
+   * `submit_result` accepts proofs under this id as well as under `set_image_id`'s.
+   */
+  set_aggregate_image_id: ({image_id}: {image_id: Buffer}, options?: MethodOptions) => Promise<AssembledTransaction<null>>
+
+  /**
+   * Construct and simulate a get_aggregate_image_id transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Get the aggregation guest's image id
+   */
+  get_aggregate_image_id: (options?: MethodOptions) => Promise<AssembledTransaction<Result<Buffer>>>
+
+  /**
+   * Construct and simulate a set_free_for_all_image_id transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Set the free-for-all guest's image id (admin only)
+   */
+  set_free_for_all_image_id: ({image_id}: {image_id: Buffer}, options?: MethodOptions) => Promise<AssembledTransaction<null>>
+
+  /**
+   * Construct and simulate a get_free_for_all_image_id transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Get the free-for-all guest's image id
+   */
+  get_free_for_all_image_id: (options?: MethodOptions) => Promise<AssembledTransaction<Result<Buffer>>>
+
+  /**
+   * Construct and simulate a start_team_game transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Start a 2v2 team game.
+   * 
+   * Seats 1 and 3 play for team 1, seats 2 and 4 for team 2. The Game Hub only knows
+   * two-player sessions, so each player is matched against the opponent in the next
+   * seat: seats 1 and 2 play hub session `session_id`, seats 3 and 4 hub session
+   * `session_id + 1`. Both hub session ids must be free (`HubSessionTaken`) and the
+   * second must not run past `u32::MAX` (`SessionIdOverflow`). Every player commits
+   * their own points to their session, so a team's winnings are split between its
+   * members by what each of them staked.
+   * 
+   * # Arguments
+   * * `session_id` - Session of the team game, and of its first pairing in the Game Hub
+   * * `players` - The four addresses in seat order
+   * * `points` - Points each player commits, in seat order
+   */
+  start_team_game: ({session_id, players, points}: {session_id: u32, players: Array<string>, points: Array<i128>}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
+
+  /**
+   * Construct and simulate a set_team_commit transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Store a team's shared board commitment, built from both members' ships. Both
+   * members must authorize it.
+   * 
+   * # Arguments
+   * * `session_id` - The team game
+   * * `team` - 1 (seats 1 and 3) or 2 (seats 2 and 4)
+   * * `board_commit` - Merkle root of the team's combined board and salt
+   */
+  set_team_commit: ({session_id, team, board_commit}: {session_id: u32, team: u32, board_commit: Buffer}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
+
+  /**
+   * Construct and simulate a submit_team_result transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Submit a zk-verified team result and settle both pairings in Game Hub.
+   * 
+   * The proof must be for the teams guest, whose image id the admin sets with
+   * `set_team_image_id`. `board_hashes` holds the two team commitments and
+   * `player_keys` the four players' keys in seat order; they are checked against the
+   * team commits and the players' account keys as in `submit_result`, and like `winner`
+   * and `total_moves` must be what the journal commits (a journal without player keys
+   * fails with `PlayerKeyMismatch`). Every member of the winning team wins their pairing.
+   * 
+   * # Returns
+   * * The winning team's players
+   */
+  submit_team_result: ({session_id, submitter, winner, total_moves, board_hashes, player_keys, journal, seal}: {session_id: u32, submitter: string, winner: u32, total_moves: u32, board_hashes: Array<Buffer>, player_keys: Array<Buffer>, journal: Buffer, seal: Buffer}, options?: MethodOptions) => Promise<AssembledTransaction<Result<Array<string>>>>
+
+  /**
+   * Construct and simulate a get_team_game transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Get team game information.
+   */
+  get_team_game: ({session_id}: {session_id: u32}, options?: MethodOptions) => Promise<AssembledTransaction<Result<TeamGame>>>
+
+  /**
+   * Construct and simulate a set_team_image_id transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Set the teams guest's image id (admin only)
+   */
+  set_team_image_id: ({image_id}: {image_id: Buffer}, options?: MethodOptions) => Promise<AssembledTransaction<null>>
+
+  /**
+   * Construct and simulate a get_team_image_id transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Get the teams guest's image id
+   */
+  get_team_image_id: (options?: MethodOptions) => Promise<AssembledTransaction<Result<Buffer>>>
+
 }
 export class Client extends ContractClient {
   static async deploy<T = Client>(
@@ -238,9 +558,16 @@ export class Client extends ContractClient {
   }
   constructor(public readonly options: ContractClientOptions) {
     super(
-      new ContractSpec([ "AAAAAQAAAAAAAAAAAAAABEdhbWUAAAAPAAAAAAAAAA9ib2FyZF9jb21taXRfcDEAAAAD6AAAA+4AAAAgAAAAAAAAAA9ib2FyZF9jb21taXRfcDIAAAAD6AAAA+4AAAAgAAAAAAAAAA1ib2FyZF9oYXNoX3AxAAAAAAAD6AAAA+4AAAAgAAAAAAAAAA1ib2FyZF9oYXNoX3AyAAAAAAAD6AAAA+4AAAAgAAAAAAAAAAxqb3VybmFsX2hhc2gAAAPoAAAD7gAAACAAAAAAAAAAB3BsYXllcjEAAAAAEwAAAAAAAAANcGxheWVyMV9ndWVzcwAAAAAAA+gAAAAEAAAAAAAAAA5wbGF5ZXIxX3BvaW50cwAAAAAACwAAAAAAAAAHcGxheWVyMgAAAAATAAAAAAAAAA1wbGF5ZXIyX2d1ZXNzAAAAAAAD6AAAAAQAAAAAAAAADnBsYXllcjJfcG9pbnRzAAAAAAALAAAAAAAAAAlzZWFsX2hhc2gAAAAAAAPoAAAD7gAAACAAAAAAAAAAC3RvdGFsX21vdmVzAAAAA+gAAAAEAAAAAAAAAAZ3aW5uZXIAAAAAA+gAAAATAAAAAAAAAA53aW5uaW5nX251bWJlcgAAAAAD6AAAAAQ=",
-        "AAAABAAAAAAAAAAAAAAABUVycm9yAAAAAAAADQAAAAAAAAAMR2FtZU5vdEZvdW5kAAAAAQAAAAAAAAAJTm90UGxheWVyAAAAAAAAAgAAAAAAAAAOQWxyZWFkeUd1ZXNzZWQAAAAAAAMAAAAAAAAAFUJvdGhQbGF5ZXJzTm90R3Vlc3NlZAAAAAAAAAQAAAAAAAAAEEdhbWVBbHJlYWR5RW5kZWQAAAAFAAAAAAAAAA1JbnZhbGlkV2lubmVyAAAAAAAABgAAAAAAAAARSW52YWxpZFRvdGFsTW92ZXMAAAAAAAAHAAAAAAAAABRJbnZhbGlkUHJvb2ZNYXRlcmlhbAAAAAgAAAAAAAAAFVZlcmlmaWVyTm90Q29uZmlndXJlZAAAAAAAAAkAAAAAAAAAF1Byb29mVmVyaWZpY2F0aW9uRmFpbGVkAAAAAAoAAAAAAAAAFUJvYXJkQ29tbWl0QWxyZWFkeVNldAAAAAAAAAsAAAAAAAAAEUJvYXJkQ29tbWl0Tm90U2V0AAAAAAAADAAAAAAAAAATQm9hcmRDb21taXRNaXNtYXRjaAAAAAAN",
-        "AAAAAgAAAAAAAAAAAAAAB0RhdGFLZXkAAAAABQAAAAEAAAAAAAAABEdhbWUAAAABAAAABAAAAAAAAAAAAAAADkdhbWVIdWJBZGRyZXNzAAAAAAAAAAAAAAAAAA9WZXJpZmllckFkZHJlc3MAAAAAAAAAAAAAAAAPVmVyaWZpZXJJbWFnZUlkAAAAAAAAAAAAAAAABUFkbWluAAAA",
+      new ContractSpec([ "AAAAAQAAAAAAAAAAAAAABEdhbWUAAAAUAAAAAAAAAA9ib2FyZF9jb21taXRfcDEAAAAD6AAAA+4AAAAgAAAAAAAAAA9ib2FyZF9jb21taXRfcDIAAAAD6AAAA+4AAAAgAAAAAAAAAA1ib2FyZF9oYXNoX3AxAAAAAAAD6AAAA+4AAAAgAAAAAAAAAA1ib2FyZF9oYXNoX3AyAAAAAAAD6AAAA+4AAAAgAAAAAAAAAAxqb3VybmFsX2hhc2gAAAPoAAAD7gAAACAAAAAAAAAAB291dGNvbWUAAAAH0AAAAAdPdXRjb21lAAAAAAAAAAAHcGxheWVyMQAAAAATAAAAAAAAAA1wbGF5ZXIxX2d1ZXNzAAAAAAAD6AAAAAQAAAAAAAAADnBsYXllcjFfcG9pbnRzAAAAAAALAAAAAAAAAAdwbGF5ZXIyAAAAABMAAAAAAAAADXBsYXllcjJfZ3Vlc3MAAAAAAAPoAAAABAAAAAAAAAAOcGxheWVyMl9wb2ludHMAAAAAAAsAAAAAAAAADHJldmVhbF9jbGFpbQAAB9AAAAAKQ2xhaW1TdGF0ZQAAAAAATEJvYXJkcyBvcGVuZWQgd2l0aCBgcmV2ZWFsX2JvYXJkYCwgYXMgY2VsbCBiaXRtYXBzIChiaXQgYHkgKiA0ICsgeGAgPSBzaGlwKS4AAAARcmV2ZWFsZWRfYm9hcmRfcDEAAAAAAAPoAAAABAAAAAAAAAARcmV2ZWFsZWRfYm9hcmRfcDIAAAAAAAPoAAAABAAAAFVBZ3JlZWQgYnkgYm90aCBwbGF5ZXJzIGluIGBzdGFydF9nYW1lX3dpdGhfcnVsZXNgOyB0aGUgZGVtbyBydWxlc2V0IGZvciBgc3RhcnRfZ2FtZWAuAAAAAAAAB3J1bGVzZXQAAAAH0AAAAAdSdWxlc2V0AAAAAAAAAAAJc2VhbF9oYXNoAAAAAAAD6AAAA+4AAAAgAAAAAAAAAAt0b3RhbF9tb3ZlcwAAAAPoAAAABAAAAAAAAAAGd2lubmVyAAAAAAPoAAAAEwAAAAAAAAAOd2lubmluZ19udW1iZXIAAAAAA+gAAAAE",
+        "AAAAAQAAAJRUaGUgcnVsZXMgYSBtYXRjaCBpcyBwbGF5ZWQgdW5kZXIsIGFzIHRoZSBndWVzdCBqb3VybmFsIGNvbW1pdHMgdGhlbQooYGJhdHRsZXNoaXBfcnVsZXM6OlB1YmxpY091dHB1dGApLiBUaGUgZGVmYXVsdCwgYWxsIHplcm8sIGlzIHRoZSBkZW1vIHJ1bGVzZXQuAAAAAAAAAAdSdWxlc2V0AAAAAAkAAAAAAAAABWJvbWJzAAAAAAAABAAAAAAAAAAOaGl0X2tlZXBzX3R1cm4AAAAAAAEAAAAAAAAADGxpbmVfc3RyaWtlcwAAAAQAAABVYGJhdHRsZXNoaXBfcnVsZXM6Ok1pbmVQZW5hbHR5YCBhcyBpdHMgdmFyaWFudCBpbmRleDogMCA9IHNraXAgdHVybiwgMSA9IHJldmVhbCBjZWxsLgAAAAAAAAxtaW5lX3BlbmFsdHkAAAAEAAAAAAAAAAVtaW5lcwAAAAAAAAQAAABJYGJhdHRsZXNoaXBfcnVsZXM6OkdhbWVNb2RlYCBhcyBpdHMgdmFyaWFudCBpbmRleDogMCA9IGRlbW8sIDEgPSBjbGFzc2ljLgAAAAAAAARtb2RlAAAABAAAAAAAAAALbm9fdG91Y2hpbmcAAAAAAQAAAAAAAAAFc2Fsdm8AAAAAAAABAAAAAAAAAAtzb25hcl9waW5ncwAAAAAE",
+        "AAAAAQAAAE9BIHBlbmRpbmcgYGNsYWltX3dpbmA6IHRoZSBjbGFpbWFudCdzIHNpZ25lZCBtb3ZlIGxvZyB1cCB0byB0aGVpciB3aW5uaW5nIHNob3QuAAAAAAAAAAALUmV2ZWFsQ2xhaW0AAAAABAAAAD5MYXN0IGxlZGdlciBvbiB3aGljaCB0aGUgb3Bwb25lbnQgbWF5IHN0aWxsIHJldmVhbCBvciBjb250ZXN0LgAAAAAACGRlYWRsaW5lAAAABAAAAFJDaGFpbmVkIGhhc2ggYWZ0ZXIgdGhlIGxhc3QgY2xhaW1lZCBtb3ZlLCB3aGljaCBhIGBjb250ZXN0X2NsYWltYCBsb2cgbXVzdCBleHRlbmQuAAAAAAAJbGFzdF9oYXNoAAAAAAAD7gAAACAAAAAAAAAABnBsYXllcgAAAAAAEwAAAAAAAAALdG90YWxfbW92ZXMAAAAABA==",
+        "AAAAAgAAAClXaGV0aGVyIGEgZ2FtZSBoYXMgYSBwZW5kaW5nIGBjbGFpbV93aW5gLgAAAAAAAAAAAAAKQ2xhaW1TdGF0ZQAAAAAAAgAAAAAAAAAAAAAACVVuY2xhaW1lZAAAAAAAAAEAAAAAAAAAB1BlbmRpbmcAAAAAAQAAB9AAAAALUmV2ZWFsQ2xhaW0A",
+        "AAAAAQAAADxPbmUgc2lnbmVkIHNob3Qgb2YgYSBtb3ZlIGxvZyByZXBsYXllZCBieSBgc2V0dGxlX3JldmVhbGVkYC4AAAAAAAAADFJldmVhbGVkU2hvdAAAAAMAAABCVGhlIHNob290ZXIncyBlZDI1NTE5IHNpZ25hdHVyZSBvdmVyIHRoZSBtb3ZlLCBhcyBpbiB0aGUgemsgZ3Vlc3QuAAAAAAAJc2lnbmF0dXJlAAAAAAAD7gAAAEAAAAAAAAAAAXgAAAAAAAAEAAAAAAAAAAF5AAAAAAAABA==",
+        "AAAAAQAAAHVBIHRocmVlLSBvciBmb3VyLXBsYXllciBmcmVlLWZvci1hbGwsIHNldHRsZWQgYXMgb25lIEdhbWUgSHViIHNlc3Npb24gcGVyIHBhaXIgb2YKcGxheWVycyAoc2VlIGBzdGFydF9mcmVlX2Zvcl9hbGxgKS4AAAAAAAAAAAAADkZyZWVGb3JBbGxHYW1lAAAAAAAHAAAAAAAAAA1ib2FyZF9jb21taXRzAAAAAAAD6gAAA+gAAAPuAAAAIAAAAAAAAAAMam91cm5hbF9oYXNoAAAD6AAAA+4AAAAgAAAAQVBsYXllcnMgaW4gc2VhdCBvcmRlcjogYHBsYXllcnNbMF1gIGlzIHNlYXQgMSBvZiB0aGUgZ3Vlc3QgaW5wdXQuAAAAAAAAB3BsYXllcnMAAAAD6gAAABMAAAAAAAAABnBvaW50cwAAAAAD6gAAAAsAAAA0U2VhdHMgZnJvbSBmaXJzdCB0byBsYXN0IHBsYWNlOyBlbXB0eSB1bnRpbCBzZXR0bGVkLgAAAAdyYW5raW5nAAAAA+oAAAAEAAAAAAAAAAlzZWFsX2hhc2gAAAAAAAPoAAAD7gAAACAAAAAAAAAAC3RvdGFsX21vdmVzAAAAA+gAAAAE",
+        "AAAAAQAAAGNBIDJ2MiB0ZWFtIGdhbWUsIHNldHRsZWQgYXMgdHdvIEdhbWUgSHViIHNlc3Npb25zIGJldHdlZW4gb3Bwb3NpbmcgcGxheWVycyAoc2VlCmBzdGFydF90ZWFtX2dhbWVgKS4AAAAAAAAAAAhUZWFtR2FtZQAAAAcAAAAlT25lIHNoYXJlZCBib2FyZCBjb21taXRtZW50IHBlciB0ZWFtLgAAAAAAAA1ib2FyZF9jb21taXRzAAAAAAAD6gAAA+gAAAPuAAAAIAAAAAAAAAAMam91cm5hbF9oYXNoAAAD6AAAA+4AAAAgAAAAR1BsYXllcnMgaW4gc2VhdCBvcmRlcjogc2VhdHMgMSBhbmQgMyBmb3JtIHRlYW0gMSwgc2VhdHMgMiBhbmQgNCB0ZWFtIDIuAAAAAAdwbGF5ZXJzAAAAA+oAAAATAAAAAAAAAAZwb2ludHMAAAAAA+oAAAALAAAAAAAAAAlzZWFsX2hhc2gAAAAAAAPoAAAD7gAAACAAAAAAAAAAC3RvdGFsX21vdmVzAAAAA+gAAAAEAAAAGVRoZSB3aW5uaW5nIHRlYW0sIDEgb3IgMi4AAAAAAAAGd2lubmVyAAAAAAPoAAAABA==",
+        "AAAAAgAAAFpIb3cgYSBzZXR0bGVkIG1hdGNoIGVuZGVkLCBhcyByZXBvcnRlZCBieSB0aGUgZ3Vlc3Qgam91cm5hbCBvciBmb3VuZCBieQpgc2V0dGxlX3JldmVhbGVkYC4AAAAAAAAAAAAHT3V0Y29tZQAAAAAEAAAAAAAAAE1ObyByZXN1bHQgc3VibWl0dGVkIHlldCAoYWxzbyB0aGUgc3RhdGUgb2YgZ2FtZXMgc2V0dGxlZCBieSBgcmV2ZWFsX3dpbm5lcmApLgAAAAAAAAdQZW5kaW5nAAAAAAAAAAAkVGhlIHdpbm5lciBzYW5rIGV2ZXJ5IG9wcG9zaW5nIHNoaXAuAAAAB1N1bmtBbGwAAAAAAAAAABNUaGUgbG9zZXIgcmVzaWduZWQuAAAAAAhSZXNpZ25lZAAAAAAAAABVVGhlIGxvc2VyIGRpZCBub3QgcmV2ZWFsIHRoZWlyIGJvYXJkIGJlZm9yZSB0aGUgZGVhZGxpbmUgb2YgdGhlIHdpbm5lcidzIGBjbGFpbV93aW5gLgAAAAAAAAlGb3JmZWl0ZWQAAAA=",
+        "AAAABAAAAAAAAAAAAAAABUVycm9yAAAAAAAAHwAAAAAAAAAMR2FtZU5vdEZvdW5kAAAAAQAAAAAAAAAJTm90UGxheWVyAAAAAAAAAgAAAAAAAAAOQWxyZWFkeUd1ZXNzZWQAAAAAAAMAAAAAAAAAFUJvdGhQbGF5ZXJzTm90R3Vlc3NlZAAAAAAAAAQAAAAAAAAAEEdhbWVBbHJlYWR5RW5kZWQAAAAFAAAAAAAAAA1JbnZhbGlkV2lubmVyAAAAAAAABgAAAAAAAAARSW52YWxpZFRvdGFsTW92ZXMAAAAAAAAHAAAAAAAAABRJbnZhbGlkUHJvb2ZNYXRlcmlhbAAAAAgAAAAAAAAAFVZlcmlmaWVyTm90Q29uZmlndXJlZAAAAAAAAAkAAAAAAAAAF1Byb29mVmVyaWZpY2F0aW9uRmFpbGVkAAAAAAoAAAAAAAAAFUJvYXJkQ29tbWl0QWxyZWFkeVNldAAAAAAAAAsAAAAAAAAAEUJvYXJkQ29tbWl0Tm90U2V0AAAAAAAADAAAAAAAAAATQm9hcmRDb21taXRNaXNtYXRjaAAAAAANAAAAAAAAAA5JbnZhbGlkT3V0Y29tZQAAAAAADgAAAAAAAAARUGxheWVyS2V5TWlzbWF0Y2gAAAAAAAAPAAAAAAAAABBCb2FyZE5vdFJldmVhbGVkAAAAEAAAAAAAAAAMSW52YWxpZEJvYXJkAAAAEQAAAAAAAAAOSW52YWxpZE1vdmVMb2cAAAAAABIAAAAAAAAAEkludmFsaWRQbGF5ZXJDb3VudAAAAAAAEwAAAAAAAAAOSW52YWxpZFJhbmtpbmcAAAAAABQAAAAAAAAAC0ludmFsaWRUZWFtAAAAABUAAAAAAAAADkludmFsaWRKb3VybmFsAAAAAAAWAAAAAAAAABRSZXZlYWxEZWFkbGluZVBhc3NlZAAAABcAAAAAAAAAGFJldmVhbERlYWRsaW5lTm90UmVhY2hlZAAAABgAAAAAAAAADUJvYXJkUmV2ZWFsZWQAAAAAAAAZAAAAAAAAAA9SdWxlc2V0TWlzbWF0Y2gAAAAAGgAAAAAAAAASUnVsZXNOb3RSZXBsYXlhYmxlAAAAAAAbAAAAAAAAAAxDbGFpbVBlbmRpbmcAAAAcAAAAAAAAAA5Ob1BlbmRpbmdDbGFpbQAAAAAAHQAAAAAAAAARU2Vzc2lvbklkT3ZlcmZsb3cAAAAAAAAeAAAAAAAAAA9IdWJTZXNzaW9uVGFrZW4AAAAAHw==",
+        "AAAAAgAAAAAAAAAAAAAAB0RhdGFLZXkAAAAACwAAAAEAAAAAAAAABEdhbWUAAAABAAAABAAAAAEAAAAAAAAACkZyZWVGb3JBbGwAAAAAAAEAAAAEAAAAAQAAAAAAAAAEVGVhbQAAAAEAAAAEAAAAAQAAAD1BIEdhbWUgSHViIHNlc3Npb24gaWQgaW4gdXNlIGJ5IG9uZSBvZiB0aGlzIGNvbnRyYWN0J3MgZ2FtZXMuAAAAAAAACkh1YlNlc3Npb24AAAAAAAEAAAAEAAAAAAAAAAAAAAAOR2FtZUh1YkFkZHJlc3MAAAAAAAAAAAAAAAAAD1ZlcmlmaWVyQWRkcmVzcwAAAAAAAAAAAAAAAA9WZXJpZmllckltYWdlSWQAAAAAAAAAAAAAAAARRnJlZUZvckFsbEltYWdlSWQAAAAAAAAAAAAAAAAAAAtUZWFtSW1hZ2VJZAAAAAAAAAAAAAAAABBBZ2dyZWdhdGVJbWFnZUlkAAAAAAAAAAAAAAAFQWRtaW4AAAA=",
         "AAAAAAAAAF5HZXQgdGhlIGN1cnJlbnQgR2FtZUh1YiBjb250cmFjdCBhZGRyZXNzCgojIFJldHVybnMKKiBgQWRkcmVzc2AgLSBUaGUgR2FtZUh1YiBjb250cmFjdCBhZGRyZXNzAAAAAAAHZ2V0X2h1YgAAAAAAAAAAAQAAABM=",
         "AAAAAAAAAF5TZXQgYSBuZXcgR2FtZUh1YiBjb250cmFjdCBhZGRyZXNzCgojIEFyZ3VtZW50cwoqIGBuZXdfaHViYCAtIFRoZSBuZXcgR2FtZUh1YiBjb250cmFjdCBhZGRyZXNzAAAAAAAHc2V0X2h1YgAAAAABAAAAAAAAAAduZXdfaHViAAAAABMAAAAA",
         "AAAAAAAAAHFVcGRhdGUgdGhlIGNvbnRyYWN0IFdBU00gaGFzaCAodXBncmFkZSBjb250cmFjdCkKCiMgQXJndW1lbnRzCiogYG5ld193YXNtX2hhc2hgIC0gVGhlIGhhc2ggb2YgdGhlIG5ldyBXQVNNIGJpbmFyeQAAAAAAAAd1cGdyYWRlAAAAAAEAAAAAAAAADW5ld193YXNtX2hhc2gAAAAAAAPuAAAAIAAAAAA=",
@@ -248,15 +575,36 @@ export class Client extends ContractClient {
         "AAAAAAAAAEhHZXQgdGhlIGN1cnJlbnQgYWRtaW4gYWRkcmVzcwoKIyBSZXR1cm5zCiogYEFkZHJlc3NgIC0gVGhlIGFkbWluIGFkZHJlc3MAAAAJZ2V0X2FkbWluAAAAAAAAAAAAAAEAAAAT",
         "AAAAAAAAAEpTZXQgYSBuZXcgYWRtaW4gYWRkcmVzcwoKIyBBcmd1bWVudHMKKiBgbmV3X2FkbWluYCAtIFRoZSBuZXcgYWRtaW4gYWRkcmVzcwAAAAAACXNldF9hZG1pbgAAAAAAAAEAAAAAAAAACW5ld19hZG1pbgAAAAAAABMAAAAA",
         "AAAAAAAAAOVTdWJtaXQgYSBzaG90IGZvciB0aGUgY3VycmVudCBnYW1lLgpQbGF5ZXJzIGNob29zZSBhIGNlbGwgYmV0d2VlbiAxIGFuZCAxNi4KCiMgQXJndW1lbnRzCiogYHNlc3Npb25faWRgIC0gVGhlIHNlc3Npb24gSUQgb2YgdGhlIGdhbWUKKiBgcGxheWVyYCAtIEFkZHJlc3Mgb2YgdGhlIHBsYXllciBzdWJtaXR0aW5nIHRoZSBzaG90CiogYGd1ZXNzYCAtIFRoZSBzZWxlY3RlZCBzaG90IGNlbGwgKDEtMTYpAAAAAAAACm1ha2VfZ3Vlc3MAAAAAAAMAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAABnBsYXllcgAAAAAAEwAAAAAAAAAFZ3Vlc3MAAAAAAAAEAAAAAQAAA+kAAAACAAAAAw==",
-        "AAAAAAAAAipTdGFydCBhIG5ldyBnYW1lIGJldHdlZW4gdHdvIHBsYXllcnMgd2l0aCBwb2ludHMuClRoaXMgY3JlYXRlcyBhIHNlc3Npb24gaW4gdGhlIEdhbWUgSHViIGFuZCBsb2NrcyBwb2ludHMgYmVmb3JlIHN0YXJ0aW5nIHRoZSBnYW1lLgoKKipDUklUSUNBTDoqKiBUaGlzIG1ldGhvZCByZXF1aXJlcyBhdXRob3JpemF0aW9uIGZyb20gVEhJUyBjb250cmFjdCAobm90IHBsYXllcnMpLgpUaGUgR2FtZSBIdWIgd2lsbCBjYWxsIGBnYW1lX2lkLnJlcXVpcmVfYXV0aCgpYCB3aGljaCBjaGVja3MgdGhpcyBjb250cmFjdCdzIGFkZHJlc3MuCgojIEFyZ3VtZW50cwoqIGBzZXNzaW9uX2lkYCAtIFVuaXF1ZSBzZXNzaW9uIGlkZW50aWZpZXIgKHUzMikKKiBgcGxheWVyMWAgLSBBZGRyZXNzIG9mIGZpcnN0IHBsYXllcgoqIGBwbGF5ZXIyYCAtIEFkZHJlc3Mgb2Ygc2Vjb25kIHBsYXllcgoqIGBwbGF5ZXIxX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMQoqIGBwbGF5ZXIyX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMgAAAAAACnN0YXJ0X2dhbWUAAAAAAAUAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAAB3BsYXllcjEAAAAAEwAAAAAAAAAHcGxheWVyMgAAAAATAAAAAAAAAA5wbGF5ZXIxX3BvaW50cwAAAAAACwAAAAAAAAAOcGxheWVyMl9wb2ludHMAAAAAAAsAAAABAAAD6QAAAAIAAAAD",
+        "AAAAAAAAAkJTdGFydCBhIG5ldyBnYW1lIGJldHdlZW4gdHdvIHBsYXllcnMgd2l0aCBwb2ludHMsIHVuZGVyIHRoZSBkZW1vIHJ1bGVzZXQuClRoaXMgY3JlYXRlcyBhIHNlc3Npb24gaW4gdGhlIEdhbWUgSHViIGFuZCBsb2NrcyBwb2ludHMgYmVmb3JlIHN0YXJ0aW5nIHRoZSBnYW1lLgoKKipDUklUSUNBTDoqKiBUaGlzIG1ldGhvZCByZXF1aXJlcyBhdXRob3JpemF0aW9uIGZyb20gVEhJUyBjb250cmFjdCAobm90IHBsYXllcnMpLgpUaGUgR2FtZSBIdWIgd2lsbCBjYWxsIGBnYW1lX2lkLnJlcXVpcmVfYXV0aCgpYCB3aGljaCBjaGVja3MgdGhpcyBjb250cmFjdCdzIGFkZHJlc3MuCgojIEFyZ3VtZW50cwoqIGBzZXNzaW9uX2lkYCAtIFVuaXF1ZSBzZXNzaW9uIGlkZW50aWZpZXIgKHUzMikKKiBgcGxheWVyMWAgLSBBZGRyZXNzIG9mIGZpcnN0IHBsYXllcgoqIGBwbGF5ZXIyYCAtIEFkZHJlc3Mgb2Ygc2Vjb25kIHBsYXllcgoqIGBwbGF5ZXIxX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMQoqIGBwbGF5ZXIyX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMgAAAAAACnN0YXJ0X2dhbWUAAAAAAAUAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAAB3BsYXllcjEAAAAAEwAAAAAAAAAHcGxheWVyMgAAAAATAAAAAAAAAA5wbGF5ZXIxX3BvaW50cwAAAAAACwAAAAAAAAAOcGxheWVyMl9wb2ludHMAAAAAAAsAAAABAAAD6QAAAAIAAAAD",
+        "AAAAAAAAAY9TdGFydCBhIG5ldyBnYW1lIGxpa2UgYHN0YXJ0X2dhbWVgLCB1bmRlciBhbnkgcnVsZXNldCB0aGUgZ3Vlc3Qgc3VwcG9ydHMuCgpCb3RoIHBsYXllcnMgYXV0aG9yaXplIHRoZSBydWxlc2V0IGFsb25nIHdpdGggdGhlaXIgcG9pbnRzLCBhbmQgYHN1Ym1pdF9yZXN1bHRgIG9ubHkKYWNjZXB0cyBhIGpvdXJuYWwgY29tbWl0dGluZyBleGFjdGx5IHRoaXMgcnVsZXNldCAoYFJ1bGVzZXRNaXNtYXRjaGAgb3RoZXJ3aXNlKS4gT25seQpkZW1vIGdhbWVzIGNhbiBiZSBzZXR0bGVkIHdpdGhvdXQgYSBwcm9vZjogdGhlIHJldmVhbCBwYXRoIGZhaWxzIHdpdGgKYFJ1bGVzTm90UmVwbGF5YWJsZWAgZm9yIGFueXRoaW5nIGVsc2UuCgojIEFyZ3VtZW50cwoqIGBydWxlc2AgLSBUaGUgYWdyZWVkIHJ1bGVzZXQAAAAAFXN0YXJ0X2dhbWVfd2l0aF9ydWxlcwAAAAAAAAYAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAAB3BsYXllcjEAAAAAEwAAAAAAAAAHcGxheWVyMgAAAAATAAAAAAAAAA5wbGF5ZXIxX3BvaW50cwAAAAAACwAAAAAAAAAOcGxheWVyMl9wb2ludHMAAAAAAAsAAAAAAAAABXJ1bGVzAAAAAAAH0AAAAAdSdWxlc2V0AAAAAAEAAAPpAAAAAgAAAAM=",
         "AAAAAAAAABVHZXQgdmVyaWZpZXIgaW1hZ2UgaWQAAAAAAAAMZ2V0X2ltYWdlX2lkAAAAAAAAAAEAAAPpAAAD7gAAACAAAAAD",
         "AAAAAAAAAB1HZXQgdmVyaWZpZXIgY29udHJhY3QgYWRkcmVzcwAAAAAAAAxnZXRfdmVyaWZpZXIAAAAAAAAAAQAAA+kAAAATAAAAAw==",
         "AAAAAAAAACJTZXQgdmVyaWZpZXIgaW1hZ2UgaWQgKGFkbWluIG9ubHkpAAAAAAAMc2V0X2ltYWdlX2lkAAAAAQAAAAAAAAAIaW1hZ2VfaWQAAAPuAAAAIAAAAAA=",
         "AAAAAAAAACpTZXQgdmVyaWZpZXIgY29udHJhY3QgYWRkcmVzcyAoYWRtaW4gb25seSkAAAAAAAxzZXRfdmVyaWZpZXIAAAABAAAAAAAAAAh2ZXJpZmllcgAAABMAAAAA",
         "AAAAAAAAAKNJbml0aWFsaXplIHRoZSBjb250cmFjdCB3aXRoIEdhbWVIdWIgYWRkcmVzcyBhbmQgYWRtaW4KCiMgQXJndW1lbnRzCiogYGFkbWluYCAtIEFkbWluIGFkZHJlc3MgKGNhbiB1cGdyYWRlIGNvbnRyYWN0KQoqIGBnYW1lX2h1YmAgLSBBZGRyZXNzIG9mIHRoZSBHYW1lSHViIGNvbnRyYWN0AAAAAA1fX2NvbnN0cnVjdG9yAAAAAAAAAgAAAAAAAAAFYWRtaW4AAAAAAAATAAAAAAAAAAhnYW1lX2h1YgAAABMAAAAA",
+        "AAAAAAAAAX5DaGVjayBvbmUgb3BlbmVkIGNlbGwgb2YgYSBwbGF5ZXIncyBjb21taXR0ZWQgYm9hcmQsIHdpdGhvdXQgYW55IHprIHByb29mLgoKYGluZGV4YCBpcyB0aGUgcm93LW1ham9yIGNlbGwgaW5kZXgsIGBjZWxsYCB0aGUgY2xhaW1lZCBjb250ZW50cyAoMCB3YXRlciwgMSBzaGlwLAoyIG1pbmU7IGBJbnZhbGlkQm9hcmRgIGZvciBhbnl0aGluZyBlbHNlKSwgYHNhbHRgIHRoZSBjZWxsJ3Mgc2FsdCBhbmQgYHNpYmxpbmdzYCB0aGUKTWVya2xlIHBhdGggZnJvbSB0aGUgbGVhZiB1cCwgYXMgd3JpdHRlbiBieSB0aGUgaG9zdCdzIGBvcGVuLWNlbGxgIGNvbW1hbmQuIFJldHVybnMKd2hldGhlciB0aGUgb3BlbmluZyBtYXRjaGVzIHRoZSBwbGF5ZXIncyBib2FyZCBjb21taXQuAAAAAAATdmVyaWZ5X2NlbGxfb3BlbmluZwAAAAAGAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAAAAAAZwbGF5ZXIAAAAAABMAAAAAAAAABWluZGV4AAAAAAAABAAAAAAAAAAEY2VsbAAAAAQAAAAAAAAABHNhbHQAAAPuAAAAIAAAAAAAAAAIc2libGluZ3MAAAPqAAAD7gAAACAAAAABAAAD6QAAAAEAAAAD",
+        "AAAAAAAAAi1PcGVuIGEgcGxheWVyJ3Mgd2hvbGUgYm9hcmQgYWZ0ZXIgdGhlIG1hdGNoLCBmb3Igc2V0dGxlbWVudCB3aXRob3V0IGEgemsgcHJvb2YuCgpgY2VsbHNgIGFyZSB0aGUgMTYgcm93LW1ham9yIGRlbW8gY2VsbHMgKDEgPSBzaGlwKSBhbmQgYGJvYXJkX3NhbHRgIHRoZSBzZWNyZXQgdGhlCmNvbW1pdG1lbnQgd2FzIHNhbHRlZCB3aXRoOyB0b2dldGhlciB0aGV5IG11c3QgcmVidWlsZCB0aGUgcGxheWVyJ3MgYGJvYXJkX2NvbW1pdGAKKGBCb2FyZENvbW1pdE1pc21hdGNoYCBvdGhlcndpc2UpIGFuZCBob2xkIHR3byBzaXplLTIgc2hpcHMgKGBJbnZhbGlkQm9hcmRgKS4gT25seQpkZW1vIGdhbWVzIGNhbiBiZSByZXZlYWxlZCAoYFJ1bGVzTm90UmVwbGF5YWJsZWApLCBhbmQgb25seSB1bnRpbCB0aGV5IGFyZSBzZXR0bGVkLgoKUmV2ZWFsaW5nIHN0YXJ0cyBubyBkZWFkbGluZSBieSBpdHNlbGY7IHRoZSBvcHBvbmVudCBvZiBhIHBlbmRpbmcgYGNsYWltX3dpbmAgY2FuCnJldmVhbCB1bnRpbCBpdHMgZGVhZGxpbmUgKGBSZXZlYWxEZWFkbGluZVBhc3NlZGAgYWZ0ZXIgaXQpLgAAAAAAAAxyZXZlYWxfYm9hcmQAAAAEAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAAAAAAZwbGF5ZXIAAAAAABMAAAAAAAAABWNlbGxzAAAAAAAADgAAAAAAAAAKYm9hcmRfc2FsdAAAAAAD7gAAACAAAAABAAAD6QAAAAIAAAAD",
+        "AAAAAAAAAmdTZXR0bGUgYSBtYXRjaCB3aXRob3V0IGEgemsgcHJvb2YgYnkgcmVwbGF5aW5nIGl0cyBtb3ZlIGxvZyBhZ2FpbnN0IGJvdGggcmV2ZWFsZWQKYm9hcmRzLgoKVGhpcyBpcyB0aGUgdHJhbnNwYXJlbnQgZmFsbGJhY2sgdG8gYHN1Ym1pdF9yZXN1bHRgIGZvciBnYW1lcyB1bmRlciB0aGUgZGVtbyBydWxlc2V0CihgUnVsZXNOb3RSZXBsYXlhYmxlYCBvdGhlcndpc2UpOiBwbGF5ZXJzIGFsdGVybmF0ZSBzaW5nbGUgc2hvdHMsIHBsYXllciAxIGZpcnN0LAp1bnRpbCBvbmUgc2lkZSBoYXMgaGl0IGV2ZXJ5IG9wcG9zaW5nIHNoaXAgY2VsbC4gRWFjaCBzaG90IG11c3QgY2FycnkgaXRzIHNob290ZXIncwpzaWduYXR1cmUgb3ZlciB0aGUgc2FtZSBoYXNoLWNoYWluZWQgbWVzc2FnZSB0aGUgemsgZ3Vlc3QgY2hlY2tzLCBieSB0aGUgZWQyNTUxOSBrZXkKb2YgdGhlIHBsYXllcidzIGFjY291bnQuIEEgYmFkIHNpZ25hdHVyZSBhYm9ydHMgdGhlIGNhbGw7IGEgbG9nIHRoYXQgcmVwZWF0cyBhIHNob3QsCmxlYXZlcyB0aGUgYm9hcmQsIGNvbnRpbnVlcyBwYXN0IHRoZSB3aW5uaW5nIHNob3Qgb3IgZW5kcyB3aXRob3V0IGEgd2lubmVyIGZhaWxzIHdpdGgKYEludmFsaWRNb3ZlTG9nYC4AAAAAD3NldHRsZV9yZXZlYWxlZAAAAAADAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAAAAAAlzdWJtaXR0ZXIAAAAAAAATAAAAAAAAAAVtb3ZlcwAAAAAAA+oAAAfQAAAADFJldmVhbGVkU2hvdAAAAAEAAAPpAAAAEwAAAAM=",
+        "AAAAAAAAAptDbGFpbSBhIHdpbiB3aXRoIHRoZSBzaWduZWQgbW92ZSBsb2cgdXAgdG8gdGhlIGNsYWltYW50J3Mgd2lubmluZyBzaG90LCBzdGFydGluZyBhCndpbmRvdyBvZiBgUkVWRUFMX1dJTkRPV19MRURHRVJTYCBpbiB3aGljaCB0aGUgb3Bwb25lbnQgaGFzIHRvIHJldmVhbCB0aGVpciBib2FyZCBvcgpjb250ZXN0IHRoZSBjbGFpbS4KClRoZSBjbGFpbWFudCBtdXN0IGhhdmUgcmV2ZWFsZWQgdGhlaXIgb3duIGJvYXJkIGZpcnN0IChgQm9hcmROb3RSZXZlYWxlZGApOyBvbmNlIGJvdGgKYm9hcmRzIGFyZSBvcGVuLCBgc2V0dGxlX3JldmVhbGVkYCBkZWNpZGVzIGluc3RlYWQgKGBCb2FyZFJldmVhbGVkYCkuIFRoZSBsb2cgaXMKY2hlY2tlZCBsaWtlIGluIGBzZXR0bGVfcmV2ZWFsZWRgLCBtaW51cyB0aGUgb3Bwb25lbnQncyBib2FyZCwgd2hpY2ggaXMgc3RpbGwgY2xvc2VkOgppdCBtdXN0IGVuZCB3aXRoIGEgc2hvdCBvZiB0aGUgY2xhaW1hbnQsIGhvbGQgYXQgbGVhc3QgYXMgbWFueSBvZiB0aGVpciBzaG90cyBhcyB0aGVyZQphcmUgc2hpcCBjZWxscywgYW5kIG5vdCBhbHJlYWR5IHNpbmsgdGhlIGNsYWltYW50J3Mgb3duIGJvYXJkIChgSW52YWxpZE1vdmVMb2dgCm90aGVyd2lzZSkuIE9ubHkgb25lIGNsYWltIGNhbiBiZSBwZW5kaW5nIChgQ2xhaW1QZW5kaW5nYCkuAAAAAAljbGFpbV93aW4AAAAAAAADAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAAAAAAZwbGF5ZXIAAAAAABMAAAAAAAAABW1vdmVzAAAAAAAD6gAAB9AAAAAMUmV2ZWFsZWRTaG90AAAAAQAAA+kAAAACAAAAAw==",
+        "AAAAAAAAAX1XaXRoZHJhdyBhIHBlbmRpbmcgYGNsYWltX3dpbmAgYnkgc2hvd2luZyB0aGUgZ2FtZSB3ZW50IG9uIHBhc3QgdGhlIGNsYWltZWQgc2hvdC4KCmBtb3Zlc2AgbXVzdCBiZSBhIHNpZ25lZCBsb2cgdGhhdCBleHRlbmRzIHRoZSBjbGFpbWVkIG9uZSwgdGhlIHNhbWUgbW92ZXMgdXAgdG8gdGhlCmNsYWltJ3MgbGFzdCBoYXNoLCB3aXRoIGF0IGxlYXN0IG9uZSBtb3JlIHNob3QgYnkgdGhlIGNsYWltYW50IGFmdGVyIGl0CihgSW52YWxpZE1vdmVMb2dgIG90aGVyd2lzZSkuIE9ubHkgcG9zc2libGUgYmVmb3JlIHRoZSBjbGFpbSdzIGRlYWRsaW5lCihgUmV2ZWFsRGVhZGxpbmVQYXNzZWRgKTsgZmFpbHMgd2l0aCBgTm9QZW5kaW5nQ2xhaW1gIHdpdGhvdXQgYSBjbGFpbS4AAAAAAAANY29udGVzdF9jbGFpbQAAAAAAAAMAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAABnBsYXllcgAAAAAAEwAAAAAAAAAFbW92ZXMAAAAAAAPqAAAH0AAAAAxSZXZlYWxlZFNob3QAAAABAAAD6QAAAAIAAAAD",
+        "AAAAAAAAAZ1XaW4gYSBtYXRjaCB3aG9zZSBvcHBvbmVudCBsZXQgdGhlIGRlYWRsaW5lIG9mIHRoZSBjYWxsZXIncyBgY2xhaW1fd2luYCBwYXNzIHdpdGhvdXQKcmV2ZWFsaW5nIHRoZWlyIGJvYXJkIG9yIGNvbnRlc3RpbmcgdGhlIGNsYWltLgoKRmFpbHMgd2l0aCBgTm9QZW5kaW5nQ2xhaW1gIHVubGVzcyB0aGUgY2FsbGVyJ3MgY2xhaW0gaXMgcGVuZGluZywKYFJldmVhbERlYWRsaW5lTm90UmVhY2hlZGAgdW50aWwgaXRzIGRlYWRsaW5lIGhhcyBwYXNzZWQgYW5kIGBCb2FyZFJldmVhbGVkYCBpZiB0aGUKb3Bwb25lbnQgcmV2ZWFsZWQgaW4gdGltZSAoc2V0dGxlIHdpdGggYHNldHRsZV9yZXZlYWxlZGApLiBUaGUgZ2FtZSBpcyByZWNvcmRlZCBhcwpgRm9yZmVpdGVkYCwgd2l0aCB0aGUgY2xhaW1lZCBsb2cncyBtb3ZlIGNvdW50LgAAAAAAABRjbGFpbV9yZXZlYWxfZm9yZmVpdAAAAAIAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAABnBsYXllcgAAAAAAEwAAAAEAAAPpAAAAEwAAAAM=",
         "AAAAAAAAAThSZXZlYWwgdGhlIHdpbm5lciBvZiB0aGUgZ2FtZSBhbmQgc3VibWl0IG91dGNvbWUgdG8gR2FtZUh1Yi4KQ2FuIG9ubHkgYmUgY2FsbGVkIGFmdGVyIGJvdGggcGxheWVycyBoYXZlIHN1Ym1pdHRlZCBzaG90cy4KVGhpcyBnZW5lcmF0ZXMgdGhlIHdpbm5pbmcgbnVtYmVyLCBkZXRlcm1pbmVzIHRoZSB3aW5uZXIsIGFuZCBlbmRzIHRoZSBzZXNzaW9uLgoKIyBBcmd1bWVudHMKKiBgc2Vzc2lvbl9pZGAgLSBUaGUgc2Vzc2lvbiBJRCBvZiB0aGUgZ2FtZQoKIyBSZXR1cm5zCiogYEFkZHJlc3NgIC0gQWRkcmVzcyBvZiB0aGUgd2lubmluZyBwbGF5ZXIAAAANcmV2ZWFsX3dpbm5lcgAAAAAAAAEAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAABAAAD6QAAABMAAAAD",
-        "AAAAAAAAAR9TdWJtaXQgYSB6ay12ZXJpZmllZCBtYXRjaCByZXN1bHQgYW5kIHNldHRsZSB0aGUgZ2FtZSBpbiBHYW1lIEh1Yi4KClRoaXMgY2FsbCBwZXJmb3JtcyBvbi1jaGFpbiBwcm9vZiB2ZXJpZmljYXRpb24gdmlhIHRoZSBjb25maWd1cmVkIHZlcmlmaWVyIGNvbnRyYWN0LgpUaGUgdmVyaWZpZXIgYWRkcmVzcyBhbmQgaW1hZ2UgaWQgbXVzdCBiZSBjb25maWd1cmVkIGJ5IGFkbWluIHVzaW5nIGBzZXRfdmVyaWZpZXJgCmFuZCBgc2V0X2ltYWdlX2lkYCBiZWZvcmUgc3VibWlzc2lvbnMgYXJlIGFjY2VwdGVkLgAAAAANc3VibWl0X3Jlc3VsdAAAAAAAAAgAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAACXN1Ym1pdHRlcgAAAAAAABMAAAAAAAAABndpbm5lcgAAAAAABAAAAAAAAAALdG90YWxfbW92ZXMAAAAABAAAAAAAAAANYm9hcmRfaGFzaF9wMQAAAAAAA+4AAAAgAAAAAAAAAA1ib2FyZF9oYXNoX3AyAAAAAAAD7gAAACAAAAAAAAAAB2pvdXJuYWwAAAAADgAAAAAAAAAEc2VhbAAAAA4AAAABAAAD6QAAABMAAAAD",
-        "AAAAAAAAAG5TdG9yZSBhIHBsYXllcidzIGJvYXJkIGNvbW1pdG1lbnQgaGFzaCBvbi1jaGFpbi4KCkNvbW1pdCB2YWx1ZXMgYXJlIGVuZm9yY2VkIGR1cmluZyBgc3VibWl0X3Jlc3VsdGAgKHBoYXNlIDIpLgAAAAAAEHNldF9ib2FyZF9jb21taXQAAAADAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAAAAAAZwbGF5ZXIAAAAAABMAAAAAAAAADGJvYXJkX2NvbW1pdAAAA+4AAAAgAAAAAQAAA+kAAAACAAAAAw==" ]),
+        "AAAAAAAABABTdWJtaXQgYSB6ay12ZXJpZmllZCBtYXRjaCByZXN1bHQgYW5kIHNldHRsZSB0aGUgZ2FtZSBpbiBHYW1lIEh1Yi4KClRoaXMgY2FsbCBwZXJmb3JtcyBvbi1jaGFpbiBwcm9vZiB2ZXJpZmljYXRpb24gdmlhIHRoZSBjb25maWd1cmVkIHZlcmlmaWVyIGNvbnRyYWN0LgpUaGUgdmVyaWZpZXIgYWRkcmVzcyBhbmQgaW1hZ2UgaWQgbXVzdCBiZSBjb25maWd1cmVkIGJ5IGFkbWluIHVzaW5nIGBzZXRfdmVyaWZpZXJgCmFuZCBgc2V0X2ltYWdlX2lkYCBiZWZvcmUgc3VibWlzc2lvbnMgYXJlIGFjY2VwdGVkLiBQcm9vZnMgYWdncmVnYXRlZCBmcm9tIHBlci10dXJuCnJlY2VpcHRzIHZlcmlmeSB1bmRlciB0aGUgYWdncmVnYXRpb24gZ3Vlc3QncyBpZCBpbnN0ZWFkLCBzZXQgd2l0aApgc2V0X2FnZ3JlZ2F0ZV9pbWFnZV9pZGA7IHdpdGggYm90aCBzZXQsIHRoZSBzZWFsIGlzIGNoZWNrZWQgYWdhaW5zdCBlaXRoZXIuCgpgb3V0Y29tZWAgaXMgdGhlIGpvdXJuYWwncyByZWFzb24gY29kZTogMSB3aGVuIHRoZSB3aW5uZXIgc2FuayBldmVyeSBzaGlwLCAyIHdoZW4KdGhlIGxvc2VyIHJlc2lnbmVkLiBJdCBtdXN0IGJlIHRoZSBvdXRjb21lIHRoZSBqb3VybmFsIGNvbW1pdHMsIHdoaWNoIGlzIHdoYXQgdGhlCmdhbWUgcmVjb3JkcyAoYEludmFsaWRPdXRjb21lYCBvdGhlcndpc2UpOyBhIGpvdXJuYWwgdGhhdCBkb2VzIG5vdCBkZWNvZGUgYXMgdGhlCmd1ZXN0J3Mgb3V0cHV0IGZvciB0aGlzIHNlc3Npb24gZmFpbHMgd2l0aCBgSW52YWxpZEpvdXJuYWxgLgoKYHBsYXllcl9rZXlfcDFgL2BwbGF5ZXJfa2V5X3AyYCBhcmUgdGhlIGtleXMgdGhlIGd1ZXN0IGNoZWNrZWQgZXZlcnkgbW92ZSdzIHNpZ25hdHVyZQphZ2FpbnN0OyB0aGV5IG11c3QgYmUgdGhlIGVkMjU1MTkga2V5cyBvZiB0aGUgYHBsYXllcjFgL2BwbGF5ZXIyYCBhY2NvdW50cy4KClRoZSBqb3VybmFsIG11c3QgY29tbWl0IHRoZSBydWxlc2V0IHRoZSBnYW1lIHdhcyBzdGFydGVkIHdpdGggKGBSdWxlc2V0TWlzbWF0Y2hgKS4KCmB3aW5uAAAADXN1Ym1pdF9yZXN1bHQAAAAAAAALAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAAAAAAlzdWJtaXR0ZXIAAAAAAAATAAAAAAAAAAZ3aW5uZXIAAAAAAAQAAAAAAAAAB291dGNvbWUAAAAABAAAAAAAAAALdG90YWxfbW92ZXMAAAAABAAAAAAAAAANYm9hcmRfaGFzaF9wMQAAAAAAA+4AAAAgAAAAAAAAAA1ib2FyZF9oYXNoX3AyAAAAAAAD7gAAACAAAAAAAAAADXBsYXllcl9rZXlfcDEAAAAAAAPuAAAAIAAAAAAAAAANcGxheWVyX2tleV9wMgAAAAAAA+4AAAAgAAAAAAAAAAdqb3VybmFsAAAAAA4AAAAAAAAABHNlYWwAAAAOAAAAAQAAA+kAAAATAAAAAw==",
+        "AAAAAAAAAG5TdG9yZSBhIHBsYXllcidzIGJvYXJkIGNvbW1pdG1lbnQgaGFzaCBvbi1jaGFpbi4KCkNvbW1pdCB2YWx1ZXMgYXJlIGVuZm9yY2VkIGR1cmluZyBgc3VibWl0X3Jlc3VsdGAgKHBoYXNlIDIpLgAAAAAAEHNldF9ib2FyZF9jb21taXQAAAADAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAAAAAAZwbGF5ZXIAAAAAABMAAAAAAAAADGJvYXJkX2NvbW1pdAAAA+4AAAAgAAAAAQAAA+kAAAACAAAAAw==",
+        "AAAAAAAAAnpTdGFydCBhIGZyZWUtZm9yLWFsbCBiZXR3ZWVuIHRocmVlIG9yIGZvdXIgcGxheWVycy4KClRoZSBHYW1lIEh1YiBvbmx5IGtub3dzIHR3by1wbGF5ZXIgc2Vzc2lvbnMsIHNvIGV2ZXJ5IHBhaXIgb2YgcGxheWVycyBnZXRzIGl0cyBvd246CnRoZSBrLXRoIHBhaXIgaW4gYCgxLCAyKSwgKDEsIDMpLCAuLi4sICgyLCAzKSwgLi4uYCBvcmRlciBwbGF5cyBodWIgc2Vzc2lvbgpgc2Vzc2lvbl9pZCArIGtgLCBhbmQgZWFjaCBwbGF5ZXIgY29tbWl0cyB0aGVpciBwb2ludHMgdG8gZXZlcnkgb25lIG9mIHRoZWlyIGR1ZWxzLgpUaG9zZSBodWIgc2Vzc2lvbiBpZHMgbXVzdCBub3QgYmUgdXNlZCBieSBhbm90aGVyIGdhbWUgb2YgdGhpcyBjb250cmFjdAooYEh1YlNlc3Npb25UYWtlbmApIG5vciBydW4gcGFzdCBgdTMyOjpNQVhgIChgU2Vzc2lvbklkT3ZlcmZsb3dgKS4KCiMgQXJndW1lbnRzCiogYHNlc3Npb25faWRgIC0gU2Vzc2lvbiBvZiB0aGUgZnJlZS1mb3ItYWxsLCBhbmQgb2YgaXRzIGZpcnN0IGR1ZWwgaW4gdGhlIEdhbWUgSHViCiogYHBsYXllcnNgIC0gQWRkcmVzc2VzIGluIHNlYXQgb3JkZXIKKiBgcG9pbnRzYCAtIFBvaW50cyBlYWNoIHBsYXllciBjb21taXRzIHBlciBkdWVsLCBpbiBzZWF0IG9yZGVyAAAAAAASc3RhcnRfZnJlZV9mb3JfYWxsAAAAAAADAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAAAAAAdwbGF5ZXJzAAAAA+oAAAATAAAAAAAAAAZwb2ludHMAAAAAA+oAAAALAAAAAQAAA+kAAAACAAAAAw==",
+        "AAAAAAAAAGBTdG9yZSBhIGZyZWUtZm9yLWFsbCBwbGF5ZXIncyBib2FyZCBjb21taXRtZW50LCBhcyBgc2V0X2JvYXJkX2NvbW1pdGAgZG9lcyBmb3IKdHdvLXBsYXllciBnYW1lcy4AAAAXc2V0X2ZyZWVfZm9yX2FsbF9jb21taXQAAAAAAwAAAAAAAAAKc2Vzc2lvbl9pZAAAAAAABAAAAAAAAAAGcGxheWVyAAAAAAATAAAAAAAAAAxib2FyZF9jb21taXQAAAPuAAAAIAAAAAEAAAPpAAAAAgAAAAM=",
+        "AAAAAAAAAmBTdWJtaXQgYSB6ay12ZXJpZmllZCBmcmVlLWZvci1hbGwgcmFua2luZyBhbmQgc2V0dGxlIGV2ZXJ5IGR1ZWwgaW4gR2FtZSBIdWIuCgpUaGUgcHJvb2YgbXVzdCBiZSBmb3IgdGhlIGZyZWUtZm9yLWFsbCBndWVzdCwgd2hvc2UgaW1hZ2UgaWQgdGhlIGFkbWluIHNldHMgd2l0aApgc2V0X2ZyZWVfZm9yX2FsbF9pbWFnZV9pZGAuIGByYW5raW5nYCBsaXN0cyBldmVyeSBzZWF0ICgxLWJhc2VkKSBmcm9tIGZpcnN0IHRvIGxhc3QKcGxhY2U7IGVhY2ggZHVlbCBpcyB3b24gYnkgd2hpY2hldmVyIG9mIGl0cyB0d28gcGxheWVycyBwbGFjZWQgaGlnaGVyLgpgYm9hcmRfaGFzaGVzYCBhbmQgYHBsYXllcl9rZXlzYCBhcmUgaW4gc2VhdCBvcmRlciBhbmQgbXVzdCBtYXRjaCB0aGUgYm9hcmQgY29tbWl0cwphbmQgdGhlIHBsYXllcnMnIGFjY291bnQga2V5cywgYXMgaW4gYHN1Ym1pdF9yZXN1bHRgLiBUaGUgcmFua2luZywgYHRvdGFsX21vdmVzYCwgdGhlCmhhc2hlcyBhbmQgdGhlIGtleXMgbXVzdCBhbGwgYmUgd2hhdCB0aGUgam91cm5hbCBjb21taXRzLCBhbmQgYSBqb3VybmFsIHdpdGhvdXQKcGxheWVyIGtleXMgZmFpbHMgd2l0aCBgUGxheWVyS2V5TWlzbWF0Y2hgLgAAABpzdWJtaXRfZnJlZV9mb3JfYWxsX3Jlc3VsdAAAAAAACAAAAAAAAAAKc2Vzc2lvbl9pZAAAAAAABAAAAAAAAAAJc3VibWl0dGVyAAAAAAAAEwAAAAAAAAAHcmFua2luZwAAAAPqAAAABAAAAAAAAAALdG90YWxfbW92ZXMAAAAABAAAAAAAAAAMYm9hcmRfaGFzaGVzAAAD6gAAA+4AAAAgAAAAAAAAAAtwbGF5ZXJfa2V5cwAAAAPqAAAD7gAAACAAAAAAAAAAB2pvdXJuYWwAAAAADgAAAAAAAAAEc2VhbAAAAA4AAAABAAAD6QAAABMAAAAD",
+        "AAAAAAAAAB1HZXQgZnJlZS1mb3ItYWxsIGluZm9ybWF0aW9uLgAAAAAAABBnZXRfZnJlZV9mb3JfYWxsAAAAAQAAAAAAAAAKc2Vzc2lvbl9pZAAAAAAABAAAAAEAAAPpAAAH0AAAAA5GcmVlRm9yQWxsR2FtZQAAAAAAAw==",
+        "AAAAAAAAAIJTZXQgdGhlIGFnZ3JlZ2F0aW9uIGd1ZXN0J3MgaW1hZ2UgaWQgKGFkbWluIG9ubHkpCgpgc3VibWl0X3Jlc3VsdGAgYWNjZXB0cyBwcm9vZnMgdW5kZXIgdGhpcyBpZCBhcyB3ZWxsIGFzIHVuZGVyIGBzZXRfaW1hZ2VfaWRgJ3MuAAAAAAAWc2V0X2FnZ3JlZ2F0ZV9pbWFnZV9pZAAAAAAAAQAAAAAAAAAIaW1hZ2VfaWQAAAPuAAAAIAAAAAA=",
+        "AAAAAAAAACRHZXQgdGhlIGFnZ3JlZ2F0aW9uIGd1ZXN0J3MgaW1hZ2UgaWQAAAAWZ2V0X2FnZ3JlZ2F0ZV9pbWFnZV9pZAAAAAAAAAAAAAEAAAPpAAAD7gAAACAAAAAD",
+        "AAAAAAAAADJTZXQgdGhlIGZyZWUtZm9yLWFsbCBndWVzdCdzIGltYWdlIGlkIChhZG1pbiBvbmx5KQAAAAAAGXNldF9mcmVlX2Zvcl9hbGxfaW1hZ2VfaWQAAAAAAAABAAAAAAAAAAhpbWFnZV9pZAAAA+4AAAAgAAAAAA==",
+        "AAAAAAAAACVHZXQgdGhlIGZyZWUtZm9yLWFsbCBndWVzdCdzIGltYWdlIGlkAAAAAAAAGWdldF9mcmVlX2Zvcl9hbGxfaW1hZ2VfaWQAAAAAAAAAAAAAAQAAA+kAAAPuAAAAIAAAAAM=",
+        "AAAAAAAAAt9TdGFydCBhIDJ2MiB0ZWFtIGdhbWUuCgpTZWF0cyAxIGFuZCAzIHBsYXkgZm9yIHRlYW0gMSwgc2VhdHMgMiBhbmQgNCBmb3IgdGVhbSAyLiBUaGUgR2FtZSBIdWIgb25seSBrbm93cwp0d28tcGxheWVyIHNlc3Npb25zLCBzbyBlYWNoIHBsYXllciBpcyBtYXRjaGVkIGFnYWluc3QgdGhlIG9wcG9uZW50IGluIHRoZSBuZXh0CnNlYXQ6IHNlYXRzIDEgYW5kIDIgcGxheSBodWIgc2Vzc2lvbiBgc2Vzc2lvbl9pZGAsIHNlYXRzIDMgYW5kIDQgaHViIHNlc3Npb24KYHNlc3Npb25faWQgKyAxYC4gQm90aCBodWIgc2Vzc2lvbiBpZHMgbXVzdCBiZSBmcmVlIChgSHViU2Vzc2lvblRha2VuYCkgYW5kIHRoZQpzZWNvbmQgbXVzdCBub3QgcnVuIHBhc3QgYHUzMjo6TUFYYCAoYFNlc3Npb25JZE92ZXJmbG93YCkuIEV2ZXJ5IHBsYXllciBjb21taXRzCnRoZWlyIG93biBwb2ludHMgdG8gdGhlaXIgc2Vzc2lvbiwgc28gYSB0ZWFtJ3Mgd2lubmluZ3MgYXJlIHNwbGl0IGJldHdlZW4gaXRzCm1lbWJlcnMgYnkgd2hhdCBlYWNoIG9mIHRoZW0gc3Rha2VkLgoKIyBBcmd1bWVudHMKKiBgc2Vzc2lvbl9pZGAgLSBTZXNzaW9uIG9mIHRoZSB0ZWFtIGdhbWUsIGFuZCBvZiBpdHMgZmlyc3QgcGFpcmluZyBpbiB0aGUgR2FtZSBIdWIKKiBgcGxheWVyc2AgLSBUaGUgZm91ciBhZGRyZXNzZXMgaW4gc2VhdCBvcmRlcgoqIGBwb2ludHNgIC0gUG9pbnRzIGVhY2ggcGxheWVyIGNvbW1pdHMsIGluIHNlYXQgb3JkZXIAAAAAD3N0YXJ0X3RlYW1fZ2FtZQAAAAADAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAAAAAAdwbGF5ZXJzAAAAA+oAAAATAAAAAAAAAAZwb2ludHMAAAAAA+oAAAALAAAAAQAAA+kAAAACAAAAAw==",
+        "AAAAAAAAAQpTdG9yZSBhIHRlYW0ncyBzaGFyZWQgYm9hcmQgY29tbWl0bWVudCwgYnVpbHQgZnJvbSBib3RoIG1lbWJlcnMnIHNoaXBzLiBCb3RoCm1lbWJlcnMgbXVzdCBhdXRob3JpemUgaXQuCgojIEFyZ3VtZW50cwoqIGBzZXNzaW9uX2lkYCAtIFRoZSB0ZWFtIGdhbWUKKiBgdGVhbWAgLSAxIChzZWF0cyAxIGFuZCAzKSBvciAyIChzZWF0cyAyIGFuZCA0KQoqIGBib2FyZF9jb21taXRgIC0gTWVya2xlIHJvb3Qgb2YgdGhlIHRlYW0ncyBjb21iaW5lZCBib2FyZCBhbmQgc2FsdAAAAAAAD3NldF90ZWFtX2NvbW1pdAAAAAADAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAAAAAAR0ZWFtAAAABAAAAAAAAAAMYm9hcmRfY29tbWl0AAAD7gAAACAAAAABAAAD6QAAAAIAAAAD",
+        "AAAAAAAAAk1TdWJtaXQgYSB6ay12ZXJpZmllZCB0ZWFtIHJlc3VsdCBhbmQgc2V0dGxlIGJvdGggcGFpcmluZ3MgaW4gR2FtZSBIdWIuCgpUaGUgcHJvb2YgbXVzdCBiZSBmb3IgdGhlIHRlYW1zIGd1ZXN0LCB3aG9zZSBpbWFnZSBpZCB0aGUgYWRtaW4gc2V0cyB3aXRoCmBzZXRfdGVhbV9pbWFnZV9pZGAuIGBib2FyZF9oYXNoZXNgIGhvbGRzIHRoZSB0d28gdGVhbSBjb21taXRtZW50cyBhbmQKYHBsYXllcl9rZXlzYCB0aGUgZm91ciBwbGF5ZXJzJyBrZXlzIGluIHNlYXQgb3JkZXI7IHRoZXkgYXJlIGNoZWNrZWQgYWdhaW5zdCB0aGUKdGVhbSBjb21taXRzIGFuZCB0aGUgcGxheWVycycgYWNjb3VudCBrZXlzIGFzIGluIGBzdWJtaXRfcmVzdWx0YCwgYW5kIGxpa2UgYHdpbm5lcmAKYW5kIGB0b3RhbF9tb3Zlc2AgbXVzdCBiZSB3aGF0IHRoZSBqb3VybmFsIGNvbW1pdHMgKGEgam91cm5hbCB3aXRob3V0IHBsYXllciBrZXlzCmZhaWxzIHdpdGggYFBsYXllcktleU1pc21hdGNoYCkuIEV2ZXJ5IG1lbWJlciBvZiB0aGUgd2lubmluZyB0ZWFtIHdpbnMgdGhlaXIgcGFpcmluZy4KCiMgUmV0dXJucwoqIFRoZSB3aW5uaW5nIHRlYW0ncyBwbGF5ZXJzAAAAAAAAEnN1Ym1pdF90ZWFtX3Jlc3VsdAAAAAAACAAAAAAAAAAKc2Vzc2lvbl9pZAAAAAAABAAAAAAAAAAJc3VibWl0dGVyAAAAAAAAEwAAAAAAAAAGd2lubmVyAAAAAAAEAAAAAAAAAAt0b3RhbF9tb3ZlcwAAAAAEAAAAAAAAAAxib2FyZF9oYXNoZXMAAAPqAAAD7gAAACAAAAAAAAAAC3BsYXllcl9rZXlzAAAAA+oAAAPuAAAAIAAAAAAAAAAHam91cm5hbAAAAAAOAAAAAAAAAARzZWFsAAAADgAAAAEAAAPpAAAD6gAAABMAAAAD",
+        "AAAAAAAAABpHZXQgdGVhbSBnYW1lIGluZm9ybWF0aW9uLgAAAAAADWdldF90ZWFtX2dhbWUAAAAAAAABAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAQAAA+kAAAfQAAAACFRlYW1HYW1lAAAAAw==",
+        "AAAAAAAAACtTZXQgdGhlIHRlYW1zIGd1ZXN0J3MgaW1hZ2UgaWQgKGFkbWluIG9ubHkpAAAAABFzZXRfdGVhbV9pbWFnZV9pZAAAAAAAAAEAAAAAAAAACGltYWdlX2lkAAAD7gAAACAAAAAA",
+        "AAAAAAAAAB5HZXQgdGhlIHRlYW1zIGd1ZXN0J3MgaW1hZ2UgaWQAAAAAABFnZXRfdGVhbV9pbWFnZV9pZAAAAAAAAAAAAAABAAAD6QAAA+4AAAAgAAAAAw==" ]),
       options
     )
   }
@@ -269,12 +617,33 @@ export class Client extends ContractClient {
         set_admin: this.txFromJSON<null>,
         make_guess: this.txFromJSON<Result<void>>,
         start_game: this.txFromJSON<Result<void>>,
+        start_game_with_rules: this.txFromJSON<Result<void>>,
         get_image_id: this.txFromJSON<Result<Buffer>>,
         get_verifier: this.txFromJSON<Result<string>>,
         set_image_id: this.txFromJSON<null>,
         set_verifier: this.txFromJSON<null>,
+        verify_cell_opening: this.txFromJSON<Result<boolean>>,
+        reveal_board: this.txFromJSON<Result<void>>,
+        settle_revealed: this.txFromJSON<Result<string>>,
+        claim_win: this.txFromJSON<Result<void>>,
+        contest_claim: this.txFromJSON<Result<void>>,
+        claim_reveal_forfeit: this.txFromJSON<Result<string>>,
         reveal_winner: this.txFromJSON<Result<string>>,
         submit_result: this.txFromJSON<Result<string>>,
-        set_board_commit: this.txFromJSON<Result<void>>
+        set_board_commit: this.txFromJSON<Result<void>>,
+        start_free_for_all: this.txFromJSON<Result<void>>,
+        set_free_for_all_commit: this.txFromJSON<Result<void>>,
+        submit_free_for_all_result: this.txFromJSON<Result<string>>,
+        get_free_for_all: this.txFromJSON<Result<FreeForAllGame>>,
+        set_aggregate_image_id: this.txFromJSON<null>,
+        get_aggregate_image_id: this.txFromJSON<Result<Buffer>>,
+        set_free_for_all_image_id: this.txFromJSON<null>,
+        get_free_for_all_image_id: this.txFromJSON<Result<Buffer>>,
+        start_team_game: this.txFromJSON<Result<void>>,
+        set_team_commit: this.txFromJSON<Result<void>>,
+        submit_team_result: this.txFromJSON<Result<Array<string>>>,
+        get_team_game: this.txFromJSON<Result<TeamGame>>,
+        set_team_image_id: this.txFromJSON<null>,
+        get_team_image_id: this.txFromJSON<Result<Buffer>>
   }
 }
\ No newline at end of file
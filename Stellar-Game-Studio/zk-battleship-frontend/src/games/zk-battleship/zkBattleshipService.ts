@@ -11,9 +11,13 @@ type ClientOptions = contract.ClientOptions;
 
 export interface ZkProofPayload {
   winner: 1 | 2;
+  /** Journal outcome code: 1 = all ships sunk, 2 = resignation. */
+  outcome: 1 | 2;
   totalMoves: number;
   boardHashP1Hex: string;
   boardHashP2Hex: string;
+  /** Ed25519 keys the moves were signed with, P1 first. */
+  playerKeysHex: [string, string];
   journalHex: string;
   sealHex: string;
 }
@@ -721,6 +725,8 @@ export class ZkBattleshipService {
 
     const boardHashP1 = hexToBuffer(payload.boardHashP1Hex, 32);
     const boardHashP2 = hexToBuffer(payload.boardHashP2Hex, 32);
+    const playerKeyP1 = hexToBuffer(payload.playerKeysHex[0], 32);
+    const playerKeyP2 = hexToBuffer(payload.playerKeysHex[1], 32);
     const journal = hexToBuffer(payload.journalHex);
     const seal = hexToBuffer(normalizeSealHexForVerifier(payload.sealHex));
 
@@ -728,9 +734,12 @@ export class ZkBattleshipService {
       session_id: sessionId,
       submitter: submitterAddress,
       winner: payload.winner,
+      outcome: payload.outcome,
       total_moves: payload.totalMoves,
       board_hash_p1: boardHashP1,
       board_hash_p2: boardHashP2,
+      player_key_p1: playerKeyP1,
+      player_key_p2: playerKeyP2,
       journal,
       seal,
     }, DEFAULT_METHOD_OPTIONS);
@@ -99,6 +99,20 @@ class DevWalletService {
     this.keypairs = {};
   }
 
+  /**
+   * Sign raw bytes with the current player's ed25519 account key
+   * Used for move signatures, which the prover checks against the players' account keys
+   */
+  signMessage(message: Uint8Array): Uint8Array {
+    const playerKey = this.currentPlayer ? `player${this.currentPlayer}` : null;
+
+    if (!playerKey || !this.keypairs[playerKey]) {
+      throw new Error('No player initialized');
+    }
+
+    return Uint8Array.from(this.keypairs[playerKey].sign(Buffer.from(message)));
+  }
+
   /**
    * Get a signer for contract interactions
    * Uses actual keypair to sign transactions
@@ -0,0 +1,383 @@
+//! `host prove-batch`: prove many game inputs in one process.
+
+use battleship_prover::{
+    load_game_input, prove, write_json, CacheLookup, ProofCache, ProofStats, ProveOptions,
+    ProverError, ReceiptKind,
+};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
+
+/// File-name pattern used when the batch source is a directory.
+const DEFAULT_PATTERN: &str = "game-input-*.json";
+
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    /// A directory (scanned for `game-input-*.json`) or a glob pattern.
+    pub source: String,
+    pub out_dir: PathBuf,
+    pub concurrency: usize,
+    pub receipt_kind: ReceiptKind,
+    pub report_path: Option<PathBuf>,
+    /// Proof cache directory; `None` always proves from scratch.
+    pub cache_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum BatchStatus {
+    Succeeded,
+    /// The guest rejected the game (invalid board, turn order, duplicate shot...).
+    GuestFailure,
+    InvalidInput,
+    Failed,
+}
+
+#[derive(Debug, Serialize)]
+struct BatchEntry {
+    input: String,
+    session_id: Option<u32>,
+    status: BatchStatus,
+    winner: Option<u8>,
+    total_moves: Option<u32>,
+    error: Option<String>,
+    proof_output: Option<String>,
+    /// Whether the proof came from the proof cache.
+    cached: bool,
+    /// Cycle counts and phase timings; absent for cache hits and failures.
+    stats: Option<ProofStats>,
+    elapsed_ms: u128,
+}
+
+#[derive(Debug, Serialize)]
+struct BatchReport {
+    total: usize,
+    succeeded: usize,
+    guest_failures: usize,
+    invalid_inputs: usize,
+    failed: usize,
+    concurrency: usize,
+    receipt_kind: &'static str,
+    elapsed_ms: u128,
+    results: Vec<BatchEntry>,
+}
+
+impl BatchReport {
+    fn count(&self, status: BatchStatus) -> usize {
+        self.results
+            .iter()
+            .filter(|entry| entry.status == status)
+            .count()
+    }
+}
+
+fn classify(err: &ProverError) -> BatchStatus {
+    match err {
+        ProverError::Input(_) => BatchStatus::InvalidInput,
+        ProverError::Guest(_) => BatchStatus::GuestFailure,
+        ProverError::Prove(_)
+        | ProverError::Env(_)
+        | ProverError::Bundle(_)
+        | ProverError::Compress(_)
+        | ProverError::Verify(_)
+        | ProverError::Io(_) => BatchStatus::Failed,
+    }
+}
+
+/// Resolves the batch source into a sorted list of input files.
+fn discover_inputs(source: &str) -> Result<Vec<PathBuf>, String> {
+    let pattern = if Path::new(source).is_dir() {
+        Path::new(source)
+            .join(DEFAULT_PATTERN)
+            .to_string_lossy()
+            .into_owned()
+    } else {
+        source.to_string()
+    };
+
+    let paths = glob::glob(&pattern).map_err(|e| format!("invalid glob '{pattern}': {e}"))?;
+    let mut inputs: Vec<PathBuf> = paths
+        .filter_map(Result::ok)
+        .filter(|path| path.is_file())
+        .collect();
+    inputs.sort();
+
+    if inputs.is_empty() {
+        return Err(format!("no game inputs match '{pattern}'"));
+    }
+    Ok(inputs)
+}
+
+struct SessionArtifacts {
+    proof: PathBuf,
+    receipt: PathBuf,
+    bundle: PathBuf,
+}
+
+fn session_artifacts(out_dir: &Path, session_id: u32) -> SessionArtifacts {
+    SessionArtifacts {
+        proof: out_dir.join(format!("proof-output-{session_id}.json")),
+        receipt: out_dir.join(format!("receipt-{session_id}.bin")),
+        bundle: out_dir.join(format!("submission-bundle-{session_id}.json")),
+    }
+}
+
+fn prove_one(
+    path: &Path,
+    options: &BatchOptions,
+    seen_sessions: &Mutex<HashSet<u32>>,
+) -> BatchEntry {
+    let started = Instant::now();
+    let mut entry = BatchEntry {
+        input: path.display().to_string(),
+        session_id: None,
+        status: BatchStatus::Succeeded,
+        winner: None,
+        total_moves: None,
+        error: None,
+        proof_output: None,
+        cached: false,
+        stats: None,
+        elapsed_ms: 0,
+    };
+
+    let result = (|| {
+        let input = load_game_input(path)?;
+        entry.session_id = Some(input.session_id);
+        if !seen_sessions.lock().unwrap().insert(input.session_id) {
+            return Err(ProverError::Input(format!(
+                "session {} appears in more than one input",
+                input.session_id
+            )));
+        }
+        if input.player_keys.is_none() {
+            return Err(ProverError::Input(String::from(
+                "the game is unsigned: submit_result only settles games signed with both player keys",
+            )));
+        }
+
+        let opts = ProveOptions {
+            receipt_kind: options.receipt_kind,
+        };
+        let artifact = match &options.cache_dir {
+            Some(dir) => {
+                let (artifact, lookup) = ProofCache::new(dir).prove(&input, &opts)?;
+                entry.cached = lookup == CacheLookup::Hit;
+                artifact
+            }
+            None => prove(&input, &opts)?,
+        };
+
+        let files = session_artifacts(&options.out_dir, input.session_id);
+        artifact.write_receipt(&files.receipt.to_string_lossy())?;
+        write_json(
+            &artifact.submission_bundle(None)?,
+            &files.bundle.to_string_lossy(),
+        )?;
+        artifact.write_output(&files.proof.to_string_lossy())?;
+
+        entry.winner = Some(artifact.output.public_output.winner);
+        entry.total_moves = Some(artifact.output.public_output.total_moves);
+        entry.proof_output = Some(files.proof.display().to_string());
+        entry.stats = artifact.stats.clone();
+        Ok(())
+    })();
+
+    if let Err(err) = result {
+        entry.status = classify(&err);
+        entry.error = Some(err.to_string());
+    }
+    entry.elapsed_ms = started.elapsed().as_millis();
+    entry
+}
+
+/// Proves every input with up to `concurrency` workers. Returns whether every input succeeded.
+pub fn run(options: &BatchOptions) -> Result<bool, ProverError> {
+    let inputs = discover_inputs(&options.source).map_err(ProverError::Input)?;
+    fs::create_dir_all(&options.out_dir).map_err(|e| {
+        ProverError::Io(format!(
+            "failed to create output dir '{}': {e}",
+            options.out_dir.display()
+        ))
+    })?;
+
+    println!(
+        "proving {} inputs with concurrency {} ({} receipts)",
+        inputs.len(),
+        options.concurrency,
+        options.receipt_kind.as_str()
+    );
+
+    let started = Instant::now();
+    let next = AtomicUsize::new(0);
+    let seen_sessions = Mutex::new(HashSet::new());
+    let results: Mutex<Vec<(usize, BatchEntry)>> = Mutex::new(Vec::new());
+
+    thread::scope(|scope| {
+        for _ in 0..options.concurrency.min(inputs.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(path) = inputs.get(index) else {
+                    break;
+                };
+                let entry = prove_one(path, options, &seen_sessions);
+                println!(
+                    "[{}/{}] {}: {:?} in {} ms{}",
+                    index + 1,
+                    inputs.len(),
+                    entry.input,
+                    entry.status,
+                    entry.elapsed_ms,
+                    entry
+                        .error
+                        .as_deref()
+                        .map(|e| format!(" ({e})"))
+                        .unwrap_or_default()
+                );
+                results.lock().unwrap().push((index, entry));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut report = BatchReport {
+        total: inputs.len(),
+        succeeded: 0,
+        guest_failures: 0,
+        invalid_inputs: 0,
+        failed: 0,
+        concurrency: options.concurrency,
+        receipt_kind: options.receipt_kind.as_str(),
+        elapsed_ms: started.elapsed().as_millis(),
+        results: results.into_iter().map(|(_, entry)| entry).collect(),
+    };
+    report.succeeded = report.count(BatchStatus::Succeeded);
+    report.guest_failures = report.count(BatchStatus::GuestFailure);
+    report.invalid_inputs = report.count(BatchStatus::InvalidInput);
+    report.failed = report.count(BatchStatus::Failed);
+
+    println!(
+        "batch finished in {} ms: {} succeeded, {} guest failures, {} invalid inputs, {} failed",
+        report.elapsed_ms,
+        report.succeeded,
+        report.guest_failures,
+        report.invalid_inputs,
+        report.failed
+    );
+
+    let report_path = options
+        .report_path
+        .clone()
+        .unwrap_or_else(|| options.out_dir.join("batch-report.json"));
+    write_json(&report, &report_path.to_string_lossy())?;
+    println!("batch report saved: {}", report_path.display());
+
+    Ok(report.succeeded == report.total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("host-batch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn directory_source_matches_game_input_files_only() {
+        let dir = scratch_dir("discover");
+        for name in [
+            "game-input-2.json",
+            "game-input-1.json",
+            "notes.json",
+            "game-input-3.txt",
+        ] {
+            fs::write(dir.join(name), "{}").unwrap();
+        }
+
+        let inputs = discover_inputs(dir.to_str().unwrap()).unwrap();
+        let names: Vec<_> = inputs
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["game-input-1.json", "game-input-2.json"]);
+
+        let glob = dir.join("*.json");
+        assert_eq!(discover_inputs(glob.to_str().unwrap()).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn empty_source_is_an_error() {
+        let dir = scratch_dir("empty");
+        let err = discover_inputs(dir.to_str().unwrap()).expect_err("expected no inputs");
+        assert!(
+            err.contains("no game inputs match"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn invalid_and_duplicate_inputs_are_reported_without_proving() {
+        let dir = scratch_dir("invalid");
+        let path = dir.join("game-input-1.json");
+        fs::write(&path, "not json").unwrap();
+
+        let options = BatchOptions {
+            source: dir.display().to_string(),
+            out_dir: dir.join("out"),
+            concurrency: 1,
+            receipt_kind: ReceiptKind::Composite,
+            report_path: None,
+            cache_dir: None,
+        };
+        let seen = Mutex::new(HashSet::from([42]));
+
+        let entry = prove_one(&path, &options, &seen);
+        assert_eq!(entry.status, BatchStatus::InvalidInput);
+
+        let input = battleship_prover::default_game_input(42);
+        fs::write(&path, serde_json::to_vec(&input).unwrap()).unwrap();
+        let entry = prove_one(&path, &options, &seen);
+        assert_eq!(entry.status, BatchStatus::InvalidInput);
+        assert!(entry.error.unwrap().contains("more than one input"));
+
+        let input = battleship_prover::default_game_input(43);
+        fs::write(&path, serde_json::to_vec(&input).unwrap()).unwrap();
+        let entry = prove_one(&path, &options, &seen);
+        assert_eq!(entry.status, BatchStatus::InvalidInput);
+        assert!(entry.error.unwrap().contains("unsigned"));
+    }
+
+    #[test]
+    fn guest_rejections_are_classified_separately() {
+        assert_eq!(
+            classify(&ProverError::Guest(String::from("invalid turn order"))),
+            BatchStatus::GuestFailure
+        );
+        assert_eq!(
+            classify(&ProverError::Io(String::from("disk full"))),
+            BatchStatus::Failed
+        );
+    }
+
+    #[test]
+    fn host_side_failures_are_not_guest_failures() {
+        for err in [
+            ProverError::Env(String::from("failed to build executor env")),
+            ProverError::Bundle(String::from("invalid submitter address")),
+            ProverError::Prove(String::from("prove failed: out of memory")),
+        ] {
+            assert_eq!(classify(&err), BatchStatus::Failed);
+        }
+    }
+}
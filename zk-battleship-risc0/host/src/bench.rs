@@ -0,0 +1,178 @@
+//! `host bench`: prove fixed games and report their cost, to track regressions across guest
+//! changes.
+
+use battleship_prover::{
+    classic_game_input, default_game_input, full_board_game_input, hit_keeps_turn_game_input,
+    image_id_hex, prove, signed_game_input, weapons_game_input, write_json, GameInput, ProofStats,
+    ProveOptions, ProverError, ReceiptKind, RISC0_VERSION,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    pub receipt_kind: ReceiptKind,
+    pub runs: usize,
+    pub out_path: PathBuf,
+    /// A previous `bench-report.json` to compare against.
+    pub baseline: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct BenchCase {
+    name: String,
+    total_moves: u32,
+    /// The run with the lowest wall time; cycle counts are identical across runs.
+    best: ProofStats,
+    runs: Vec<ProofStats>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct BenchReport {
+    image_id_hex: String,
+    receipt_kind: String,
+    risc0_version: String,
+    host_version: String,
+    cases: Vec<BenchCase>,
+}
+
+fn cases() -> Vec<(&'static str, GameInput)> {
+    vec![
+        ("sample", default_game_input(42)),
+        ("full-board", full_board_game_input(42)),
+        ("classic", classic_game_input(42)),
+        ("hit-keeps-turn", hit_keeps_turn_game_input(42)),
+        ("weapons", weapons_game_input(42)),
+        ("signed", signed_game_input(42)),
+    ]
+}
+
+fn wall_ms(stats: &ProofStats) -> u64 {
+    stats.execute_ms + stats.prove_ms + stats.compress_ms
+}
+
+fn change(current: u64, baseline: u64) -> String {
+    if baseline == 0 {
+        return format!("{current} (baseline 0)");
+    }
+    let pct = (current as f64 - baseline as f64) * 100.0 / baseline as f64;
+    format!("{current} vs {baseline} ({pct:+.1}%)")
+}
+
+fn compare(current: &BenchCase, baseline: &BenchCase) -> Vec<String> {
+    let (now, then) = (&current.best, &baseline.best);
+    vec![
+        format!(
+            "total cycles: {}",
+            change(now.total_cycles, then.total_cycles)
+        ),
+        format!("user cycles: {}", change(now.user_cycles, then.user_cycles)),
+        format!(
+            "segments: {}",
+            change(now.segments as u64, then.segments as u64)
+        ),
+        format!("wall ms: {}", change(wall_ms(now), wall_ms(then))),
+    ]
+}
+
+fn load_baseline(path: &PathBuf) -> Result<BenchReport, ProverError> {
+    let raw = fs::read_to_string(path).map_err(|e| {
+        ProverError::Input(format!("Failed to read baseline '{}': {e}", path.display()))
+    })?;
+    serde_json::from_str(&raw).map_err(|e| {
+        ProverError::Input(format!(
+            "Failed to parse baseline '{}': {e}",
+            path.display()
+        ))
+    })
+}
+
+/// Proves each benchmark game `runs` times without the proof cache and writes the report.
+pub fn run(options: &BenchOptions) -> Result<(), ProverError> {
+    let baseline = options.baseline.as_ref().map(load_baseline).transpose()?;
+    let opts = ProveOptions {
+        receipt_kind: options.receipt_kind,
+    };
+
+    let mut report = BenchReport {
+        image_id_hex: image_id_hex(),
+        receipt_kind: options.receipt_kind.as_str().to_string(),
+        risc0_version: String::from(RISC0_VERSION),
+        host_version: String::from(env!("CARGO_PKG_VERSION")),
+        cases: Vec::new(),
+    };
+
+    for (name, input) in cases() {
+        let mut runs = Vec::with_capacity(options.runs);
+        let mut total_moves = 0;
+        for run in 1..=options.runs {
+            let artifact = prove(&input, &opts)?;
+            total_moves = artifact.output.public_output.total_moves;
+            let stats = artifact.stats.unwrap_or_default();
+            println!("{name} run {run}/{}: {} ms", options.runs, wall_ms(&stats));
+            runs.push(stats);
+        }
+
+        let best = runs
+            .iter()
+            .min_by_key(|stats| wall_ms(stats))
+            .cloned()
+            .unwrap_or_default();
+        println!("{name} ({total_moves} moves):\n{best}");
+
+        let case = BenchCase {
+            name: name.to_string(),
+            total_moves,
+            best,
+            runs,
+        };
+        if let Some(previous) = baseline
+            .as_ref()
+            .and_then(|b| b.cases.iter().find(|c| c.name == case.name))
+        {
+            for line in compare(&case, previous) {
+                println!("  {line}");
+            }
+        }
+        report.cases.push(case);
+    }
+
+    write_json(&report, &options.out_path.to_string_lossy())?;
+    println!("bench report saved: {}", options.out_path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(total_cycles: u64, prove_ms: u64) -> BenchCase {
+        let best = ProofStats {
+            total_cycles,
+            user_cycles: total_cycles / 2,
+            segments: 1,
+            prove_ms,
+            ..ProofStats::default()
+        };
+        BenchCase {
+            name: String::from("sample"),
+            total_moves: 7,
+            runs: vec![best.clone()],
+            best,
+        }
+    }
+
+    #[test]
+    fn comparison_reports_relative_change() {
+        let lines = compare(&case(150_000, 900), &case(100_000, 1_000));
+        assert_eq!(lines[0], "total cycles: 150000 vs 100000 (+50.0%)");
+        assert_eq!(lines[2], "segments: 1 vs 1 (+0.0%)");
+        assert_eq!(lines[3], "wall ms: 900 vs 1000 (-10.0%)");
+    }
+
+    #[test]
+    fn zero_baseline_is_not_divided_by() {
+        assert_eq!(change(5, 0), "5 (baseline 0)");
+    }
+}
@@ -0,0 +1,111 @@
+//! `host cache`: list, verify and prune the proof cache.
+
+use battleship_prover::{image_id_hex, ProofCache, PruneOptions};
+
+/// Default cache directory, shared by `prove`, `prove-batch` and `cache`.
+pub const DEFAULT_CACHE_DIR: &str = "proof-cache";
+
+#[derive(Debug)]
+pub enum CacheAction {
+    List,
+    /// Verify the given keys, or every entry when empty.
+    Verify(Vec<String>),
+    Prune(PruneOptions),
+}
+
+#[derive(Debug)]
+pub struct CacheOptions {
+    pub cache_dir: String,
+    pub action: CacheAction,
+}
+
+fn list(cache: &ProofCache) -> i32 {
+    let entries = match cache.list() {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            return 2;
+        }
+    };
+
+    let current_image_id = image_id_hex();
+    for entry in &entries {
+        let stale = if entry.image_id_hex == current_image_id {
+            ""
+        } else {
+            " (stale image id)"
+        };
+        println!(
+            "{}  session {}  {}  {} bytes  created {}{}",
+            entry.key,
+            entry.session_id,
+            entry.receipt_kind,
+            entry.size_bytes,
+            entry.created_at,
+            stale
+        );
+    }
+    println!(
+        "{} entries, {} bytes in {}",
+        entries.len(),
+        entries.iter().map(|entry| entry.size_bytes).sum::<u64>(),
+        cache.root().display()
+    );
+    0
+}
+
+fn verify(cache: &ProofCache, keys: &[String]) -> i32 {
+    let keys = if keys.is_empty() {
+        match cache.keys() {
+            Ok(keys) => keys,
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                return 2;
+            }
+        }
+    } else {
+        keys.to_vec()
+    };
+
+    let mut failed = 0;
+    for key in &keys {
+        match cache.verify(key) {
+            Ok(output) => println!(
+                "{key}: ok (session {}, winner {})",
+                output.session_id, output.winner
+            ),
+            Err(err) => {
+                failed += 1;
+                println!("{key}: FAILED: {err}");
+            }
+        }
+    }
+    println!("{} verified, {failed} failed", keys.len() - failed);
+    i32::from(failed > 0)
+}
+
+fn prune(cache: &ProofCache, options: &PruneOptions) -> i32 {
+    match cache.prune(options) {
+        Ok(removed) => {
+            for key in &removed {
+                println!("removed {key}");
+            }
+            println!("{} entries removed", removed.len());
+            0
+        }
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            2
+        }
+    }
+}
+
+/// Runs a `cache` subcommand. Returns the process exit code.
+pub fn run(options: &CacheOptions) -> i32 {
+    let cache = ProofCache::new(&options.cache_dir);
+    match &options.action {
+        CacheAction::List => list(&cache),
+        CacheAction::Verify(keys) => verify(&cache, keys),
+        CacheAction::Prune(prune_options) => prune(&cache, prune_options),
+    }
+}
@@ -0,0 +1,89 @@
+//! `free-for-all`: proves a three- or four-player match and writes the
+//! `submit_free_for_all_result` bundle.
+
+use battleship_prover::{
+    free_for_all_image_id_hex, load_free_for_all_input, prove_free_for_all, write_json,
+    ProveOptions, ProverError, ReceiptKind,
+};
+
+pub const DEFAULT_PROOF: &str = "free-for-all-proof.json";
+pub const DEFAULT_RECEIPT: &str = "free-for-all-receipt.bin";
+pub const DEFAULT_BUNDLE: &str = "free-for-all-bundle.json";
+
+/// Options of the `free-for-all` and `teams` commands.
+pub struct MatchOptions {
+    pub input_path: String,
+    pub proof_out_path: String,
+    pub receipt_out_path: String,
+    pub bundle_out_path: String,
+    pub submitter: Option<String>,
+    pub receipt_kind: ReceiptKind,
+}
+
+pub fn run(options: &MatchOptions) -> Result<(), ProverError> {
+    let input = load_free_for_all_input(&options.input_path)?;
+    println!("session_id: {}", input.session_id);
+    println!("players: {}", input.seats.len());
+    println!("receipt kind requested: {}", options.receipt_kind.as_str());
+
+    let opts = ProveOptions {
+        receipt_kind: options.receipt_kind,
+    };
+    let artifact = prove_free_for_all(&input, &opts)?;
+    let output = &artifact.output.public_output;
+
+    println!("mode: {}", output.mode.as_str());
+    if output.ranking.is_empty() {
+        println!("ranking: none yet, the match is unfinished");
+    } else {
+        let ranking: Vec<String> = output
+            .ranking
+            .iter()
+            .map(|seat| format!("P{seat}"))
+            .collect();
+        println!(
+            "ranking: {} ({})",
+            ranking.join(", "),
+            output.outcome.as_str()
+        );
+    }
+    println!("total_moves: {}", output.total_moves);
+    println!("transcript_hash: {}", output.transcript_hash);
+    for (seat, (stats, board_hash)) in output.stats.iter().zip(&output.board_hashes).enumerate() {
+        println!(
+            "P{}: {} ships sunk, {} hits, {} misses, board {}",
+            seat + 1,
+            stats.ships_sunk,
+            stats.hits,
+            stats.misses,
+            board_hash
+        );
+    }
+    println!("{}", artifact.stats);
+
+    artifact.write_receipt(&options.receipt_out_path)?;
+    println!("receipt saved: {}", options.receipt_out_path);
+    artifact.write_output(&options.proof_out_path)?;
+    println!("proof output saved: {}", options.proof_out_path);
+
+    if output.ranking.is_empty() {
+        println!("no submission bundle: submit_free_for_all_result needs a finished match");
+        return Ok(());
+    }
+    if output.player_keys.is_none() {
+        println!("no submission bundle: submit_free_for_all_result only settles signed matches");
+        return Ok(());
+    }
+    let bundle = artifact.submission(options.submitter.as_deref())?;
+    write_json(&bundle, &options.bundle_out_path)?;
+    println!("submission bundle saved: {}", options.bundle_out_path);
+    if bundle.seal_hex.is_none() {
+        println!("submission bundle has no seal; the contract needs a --receipt-kind groth16 proof outside dev mode.");
+    }
+    println!("submit command: {}", bundle.stellar_invoke_command);
+    println!(
+        "the contract accepts it once its free-for-all image id is {} (set_free_for_all_image_id)",
+        free_for_all_image_id_hex()
+    );
+    Ok(())
+}
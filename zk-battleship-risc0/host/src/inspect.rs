@@ -0,0 +1,174 @@
+use battleship_prover::{aggregate_image_id_hex, image_id_hex, journal_digest, ProofOutputFile};
+use serde_json::Value;
+use std::fs;
+
+fn load_artifact(path: &str) -> Result<(ProofOutputFile, Value), String> {
+    let raw = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read artifact '{}': {e}", path))?;
+    let artifact = serde_json::from_str::<ProofOutputFile>(&raw)
+        .map_err(|e| format!("Failed to parse artifact '{}': {e}", path))?;
+    let value = serde_json::to_value(&artifact)
+        .map_err(|e| format!("Failed to encode artifact '{}': {e}", path))?;
+    Ok((artifact, value))
+}
+
+/// Flattens nested objects into `parent.child` keys so fields can be compared one by one.
+fn flatten(prefix: &str, value: &Value, out: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&path, child, out);
+            }
+        }
+        Value::String(s) if s.is_empty() => out.push((prefix.to_string(), String::from("<missing>"))),
+        Value::String(s) => out.push((prefix.to_string(), s.clone())),
+        Value::Null => out.push((prefix.to_string(), String::from("<null>"))),
+        other => out.push((prefix.to_string(), other.to_string())),
+    }
+}
+
+/// Checks that the artifact's metadata is self-consistent and matches this host build.
+fn check_artifact(artifact: &ProofOutputFile) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    match hex::decode(&artifact.journal_hex) {
+        Ok(journal) => {
+            let digest = hex::encode(journal_digest(&journal));
+            if !artifact.journal_digest_hex.is_empty() && artifact.journal_digest_hex != digest {
+                problems.push(format!(
+                    "journal_digest_hex does not match sha256(journal): expected {digest}"
+                ));
+            }
+        }
+        Err(e) => problems.push(format!("journal_hex is not valid hex: {e}")),
+    }
+
+    let current_image_id = image_id_hex();
+    if artifact.image_id_hex.is_empty() {
+        problems.push(String::from("image_id_hex is missing (artifact predates metadata)"));
+    } else if artifact.image_id_hex != current_image_id
+        && artifact.image_id_hex != aggregate_image_id_hex()
+    {
+        problems.push(format!(
+            "image_id_hex differs from this build's METHOD_ID ({current_image_id}) and AGGREGATE_ID"
+        ));
+    }
+
+    problems
+}
+
+fn print_artifact(path: &str, fields: &[(String, String)], artifact: &ProofOutputFile) -> i32 {
+    println!("artifact: {}", path);
+    for (key, value) in fields {
+        println!("  {key}: {value}");
+    }
+
+    let problems = check_artifact(artifact);
+    if problems.is_empty() {
+        println!("checks: ok");
+        return 0;
+    }
+
+    for problem in &problems {
+        println!("check failed: {problem}");
+    }
+    1
+}
+
+fn diff_fields(left: &[(String, String)], right: &[(String, String)]) -> Vec<String> {
+    let mut keys: Vec<&String> = left.iter().chain(right.iter()).map(|(key, _)| key).collect();
+    keys.sort();
+    keys.dedup();
+
+    let lookup = |fields: &[(String, String)], key: &str| {
+        fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.clone())
+            .unwrap_or_else(|| String::from("<absent>"))
+    };
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let a = lookup(left, key);
+            let b = lookup(right, key);
+            (a != b).then(|| format!("{key}:\n  - {a}\n  + {b}"))
+        })
+        .collect()
+}
+
+/// Runs `inspect` for one artifact, or diffs two. Returns the process exit code.
+pub fn run(paths: &[String]) -> i32 {
+    let mut loaded = Vec::new();
+    for path in paths {
+        match load_artifact(path) {
+            Ok((artifact, value)) => {
+                let mut fields = Vec::new();
+                flatten("", &value, &mut fields);
+                loaded.push((artifact, fields));
+            }
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                return 2;
+            }
+        }
+    }
+
+    if let [(artifact, fields)] = loaded.as_slice() {
+        return print_artifact(&paths[0], fields, artifact);
+    }
+
+    let differences = diff_fields(&loaded[0].1, &loaded[1].1);
+    if differences.is_empty() {
+        println!("artifacts are identical");
+        return 0;
+    }
+
+    println!("--- {}\n+++ {}", paths[0], paths[1]);
+    for difference in differences {
+        println!("{difference}");
+    }
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields_of(value: Value) -> Vec<(String, String)> {
+        let mut fields = Vec::new();
+        flatten("", &value, &mut fields);
+        fields
+    }
+
+    #[test]
+    fn diff_reports_changed_and_missing_fields() {
+        let left = fields_of(json!({
+            "journal_hex": "aa",
+            "image_id_hex": "",
+            "public_output": { "winner": 1 }
+        }));
+        let right = fields_of(json!({
+            "journal_hex": "aa",
+            "image_id_hex": "bb",
+            "public_output": { "winner": 2 }
+        }));
+
+        let differences = diff_fields(&left, &right);
+        assert_eq!(differences.len(), 2);
+        assert!(differences[0].starts_with("image_id_hex:\n  - <missing>\n  + bb"));
+        assert!(differences[1].starts_with("public_output.winner:"));
+    }
+
+    #[test]
+    fn identical_fields_have_no_diff() {
+        let fields = fields_of(json!({ "seal_hex": null, "receipt_kind": "groth16" }));
+        assert!(diff_fields(&fields, &fields).is_empty());
+    }
+}
@@ -1,48 +1,43 @@
-use methods::{METHOD_ELF, METHOD_ID};
-use risc0_zkvm::{default_prover, ExecutorEnv, ProverOpts};
-use serde::{Deserialize, Serialize};
-use std::env;
-use std::fs::{self, File};
-use std::io::Write;
-
-#[derive(Debug, Deserialize, Serialize)]
-struct Move {
-    player: u8,
-    x: u8,
-    y: u8,
-}
+mod batch;
+mod bench;
+mod cache;
+mod free_for_all;
+mod inspect;
+mod opening;
+mod reveal;
+mod serve;
+mod teams;
+mod transcript;
+mod turns;
 
-#[derive(Debug, Deserialize, Serialize)]
-struct GameInput {
-    session_id: u32,
-    board_p1: [u8; 16],
-    board_p2: [u8; 16],
-    moves: Vec<Move>,
-}
+use battleship_prover::{
+    aggregate, aggregate_image_id_hex, load_game_input, output_target, parse_submitter, prove,
+    signed_game_input, validate_output_path, write_json, CacheLookup, GameInput, ProofCache,
+    ProveOptions, ProverError, PruneOptions, ReceiptKind, Weapons, AGGREGATE_ID,
+};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-#[derive(Debug, Deserialize, Serialize)]
-struct PublicOutput {
-    session_id: u32,
-    winner: u8,
-    board_hash_p1: [u8; 32],
-    board_hash_p2: [u8; 32],
-    total_moves: u32,
-}
+/// Exit codes, so scripts can tell a rejected game from a bad output path or a failed write.
+const EXIT_PROOF_FAILED: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+const EXIT_INVALID_OUTPUT_PATH: i32 = 3;
+const EXIT_WRITE_FAILED: i32 = 4;
 
-#[derive(Debug, Serialize)]
-struct PublicOutputJson {
-    session_id: u32,
-    winner: u8,
-    board_hash_p1: String,
-    board_hash_p2: String,
-    total_moves: u32,
-}
-
-#[derive(Debug, Serialize)]
-struct ProofOutputFile {
-    journal_hex: String,
-    seal_hex: Option<String>,
-    public_output: PublicOutputJson,
+enum Command {
+    Prove(CliOptions),
+    Inspect(Vec<String>),
+    Serve(serve::ServeOptions),
+    ProveBatch(batch::BatchOptions),
+    Cache(cache::CacheOptions),
+    Bench(bench::BenchOptions),
+    Transcript(transcript::TranscriptOptions),
+    ProveTurns(turns::TurnsOptions),
+    OpenCell(opening::OpeningOptions),
+    Reveal(reveal::RevealOptions),
+    FreeForAll(free_for_all::MatchOptions),
+    Teams(free_for_all::MatchOptions),
 }
 
 #[derive(Debug)]
@@ -51,19 +46,76 @@ struct CliOptions {
     input_path: Option<String>,
     proof_out_path: String,
     receipt_out_path: String,
+    bundle_out_path: String,
+    /// Whether `--bundle` or `--submitter` was given; an unsigned game is then an error
+    /// instead of a game without a bundle.
+    bundle_requested: bool,
+    stats_out_path: String,
+    submitter: Option<String>,
+    receipt_kind: ReceiptKind,
+    cache_dir: Option<String>,
+    /// Set by `aggregate`: fold the turn receipts in this dir instead of proving the game.
+    turns_dir: Option<String>,
 }
 
 fn usage() -> &'static str {
-    "Usage: cargo run -- [--session <u32>] [--input <game-input.json>] [--proof <proof-output.json>] [--receipt <receipt.bin>]\n\nExamples:\n  cargo run -- --session 149478304\n  cargo run -- --input ./game-input.json\n  cargo run -- --input ./game-input.json --session 149478304 --proof ./proof-output.json --receipt ./receipt.bin\n"
+    "Usage: cargo run -- [--session <u32>] [--input <game-input.json>] [--proof <proof-output.json>] [--receipt <receipt.bin>] [--bundle <submission-bundle.json>] [--stats <proof-stats.json>] [--submitter <G...>] [--receipt-kind composite|succinct|groth16] [--cache-dir <dir> | --no-cache]\n\nExamples:\n  cargo run -- --session 149478304\n  cargo run -- --input ./game-input.json\n  cargo run -- --input ./game-input.json --session 149478304 --proof ./proof-output.json --receipt ./receipt.bin\n  cargo run -- --input ./game-input.json --bundle ./submission-bundle.json --submitter GABC...\n  RISC0_DEV_MODE=1 cargo run -- --input ./game-input.json --receipt-kind composite\n  cargo run -- inspect ./proof-output.json [./other-proof-output.json]\n  cargo run -- serve [--addr 127.0.0.1:8787] [--workers 1] [--max-queue 16] [--jobs-dir ./prover-jobs] [--allow-origin http://localhost:3000]... [--receipt-kind groth16]\n  cargo run -- prove-batch <dir|glob> [--out-dir ./batch-out] [--concurrency 1] [--receipt-kind groth16] [--report <batch-report.json>]\n  cargo run -- cache list|verify [<key>...]|prune [--stale] [--older-than-days <n>] [--all] [--cache-dir ./proof-cache]\n  cargo run -- bench [--runs 1] [--receipt-kind groth16] [--out ./bench-report.json] [--baseline <bench-report.json>]\n  cargo run -- transcript <game-input.json> [--proof <proof-output.json>]\n  cargo run -- prove-turns <game-input.json> [--turns ./turns]\n  cargo run -- open-cell <game-input.json> --player <1|2> --cell <x>,<y> [--out <opening.json>]\n  cargo run -- reveal <game-input.json> [--out ./reveal-bundle.json]\n  cargo run -- free-for-all <free-for-all-input.json> [--proof ...] [--receipt ...] [--bundle ...] [--submitter <G...>] [--receipt-kind groth16]\n  cargo run -- teams <team-input.json> [--proof ...] [--receipt ...] [--bundle ...] [--submitter <G...>] [--receipt-kind groth16]\n  cargo run -- aggregate --input <game-input.json> [--turns ./turns] [--proof ...] [--receipt ...] [--bundle ...] [--receipt-kind groth16]\n"
 }
 
-fn parse_cli_args() -> Result<CliOptions, String> {
+fn parse_cli_args() -> Result<Command, String> {
+    let mut args = env::args().skip(1);
+    let first = args.next();
+    match first.as_deref() {
+        Some("inspect") => {
+            let paths: Vec<String> = args.collect();
+            if paths.is_empty() || paths.len() > 2 {
+                return Err(String::from("inspect expects one or two proof-output.json paths"));
+            }
+            Ok(Command::Inspect(paths))
+        }
+        Some("serve") => parse_serve_args(args).map(Command::Serve),
+        Some("prove-batch") => parse_batch_args(args).map(Command::ProveBatch),
+        Some("cache") => parse_cache_args(args).map(Command::Cache),
+        Some("bench") => parse_bench_args(args).map(Command::Bench),
+        Some("transcript") => parse_transcript_args(args).map(Command::Transcript),
+        Some("prove-turns") => parse_turns_args(args).map(Command::ProveTurns),
+        Some("open-cell") => parse_open_cell_args(args).map(Command::OpenCell),
+        Some("reveal") => parse_reveal_args(args).map(Command::Reveal),
+        Some("free-for-all") => {
+            let defaults = [
+                free_for_all::DEFAULT_PROOF,
+                free_for_all::DEFAULT_RECEIPT,
+                free_for_all::DEFAULT_BUNDLE,
+            ];
+            parse_match_args("free-for-all", defaults, args).map(Command::FreeForAll)
+        }
+        Some("teams") => {
+            let defaults = [teams::DEFAULT_PROOF, teams::DEFAULT_RECEIPT, teams::DEFAULT_BUNDLE];
+            parse_match_args("teams", defaults, args).map(Command::Teams)
+        }
+        Some("aggregate") => parse_prove_args(args, true).map(Command::Prove),
+        // No subcommand: the first argument is already a `prove` flag.
+        _ => parse_prove_args(first.into_iter().chain(args), false).map(Command::Prove),
+    }
+}
+
+/// Options for proving a game, or with `aggregating` for folding its turn receipts.
+fn parse_prove_args(
+    mut args: impl Iterator<Item = String>,
+    aggregating: bool,
+) -> Result<CliOptions, String> {
     let mut session_id: Option<u32> = None;
     let mut input_path: Option<String> = None;
     let mut proof_out_path = String::from("proof-output.json");
     let mut receipt_out_path = String::from("receipt.bin");
+    let mut bundle_out_path = String::from("submission-bundle.json");
+    let mut bundle_requested = false;
+    let mut stats_out_path = String::from("proof-stats.json");
+    let mut submitter: Option<String> = None;
+    let mut receipt_kind = ReceiptKind::Groth16;
+    let mut cache_dir = Some(String::from(cache::DEFAULT_CACHE_DIR));
+    let mut turns_dir = aggregating.then(|| String::from(turns::DEFAULT_TURNS_DIR));
 
-    let mut args = env::args().skip(1);
     while let Some(arg) = args.next() {
         match arg.as_str() {
             "--help" | "-h" => {
@@ -107,6 +159,42 @@ fn parse_cli_args() -> Result<CliOptions, String> {
                     return Err(String::from("--receipt path cannot be empty"));
                 }
             }
+            "--bundle" => {
+                bundle_out_path = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --bundle"))?;
+                if bundle_out_path.trim().is_empty() {
+                    return Err(String::from("--bundle path cannot be empty"));
+                }
+                bundle_requested = true;
+            }
+            "--stats" => {
+                stats_out_path = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --stats"))?;
+                if stats_out_path.trim().is_empty() {
+                    return Err(String::from("--stats path cannot be empty"));
+                }
+            }
+            "--submitter" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --submitter"))?;
+                submitter = Some(parse_submitter(value.trim())?);
+                bundle_requested = true;
+            }
+            "--receipt-kind" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --receipt-kind"))?;
+                receipt_kind = value
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("--receipt-kind: {e}"))?;
+            }
+            "--cache-dir" if !aggregating => cache_dir = Some(parse_cache_dir(args.next())?),
+            "--no-cache" if !aggregating => cache_dir = None,
+            "--turns" if aggregating => turns_dir = Some(parse_turns_dir(args.next())?),
             other => {
                 return Err(format!("Unknown argument: {other}"));
             }
@@ -118,132 +206,585 @@ fn parse_cli_args() -> Result<CliOptions, String> {
         input_path,
         proof_out_path,
         receipt_out_path,
+        bundle_out_path,
+        bundle_requested,
+        stats_out_path,
+        submitter,
+        receipt_kind,
+        cache_dir,
+        turns_dir,
     })
 }
 
-fn default_game_input(session_id: u32) -> GameInput {
-    GameInput {
-        session_id,
-        board_p1: [
-            1, 1, 0, 0, // y=0 (ship size 2)
-            0, 0, 0, 0, // y=1
-            1, 1, 0, 0, // y=2 (ship size 2)
-            0, 0, 0, 0, // y=3
-        ],
-        board_p2: [
-            1, 1, 0, 0, // y=0 (ship size 2)
-            0, 0, 0, 0, // y=1
-            1, 1, 0, 0, // y=2 (ship size 2)
-            0, 0, 0, 0, // y=3
-        ],
-        moves: vec![
-            Move {
-                player: 1,
-                x: 0,
-                y: 0,
-            },
-            Move {
-                player: 2,
-                x: 3,
-                y: 3,
-            },
-            Move {
-                player: 1,
-                x: 1,
-                y: 0,
-            },
-            Move {
-                player: 2,
-                x: 3,
-                y: 2,
-            },
-            Move {
-                player: 1,
-                x: 0,
-                y: 2,
-            },
-            Move {
-                player: 2,
-                x: 2,
-                y: 2,
-            },
-            Move {
-                player: 1,
-                x: 1,
-                y: 2,
-            },
-        ],
+fn parse_cache_dir(value: Option<String>) -> Result<String, String> {
+    let value = value.ok_or_else(|| String::from("Missing value for --cache-dir"))?;
+    if value.trim().is_empty() {
+        return Err(String::from("--cache-dir path cannot be empty"));
+    }
+    Ok(value)
+}
+
+fn parse_count(flag: &str, value: Option<String>) -> Result<usize, String> {
+    let value = value.ok_or_else(|| format!("Missing value for {flag}"))?;
+    match value.parse::<usize>() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(format!("{flag} must be a positive integer")),
+    }
+}
+
+fn parse_serve_args(mut args: impl Iterator<Item = String>) -> Result<serve::ServeOptions, String> {
+    let mut options = serve::ServeOptions {
+        addr: String::from("127.0.0.1:8787"),
+        workers: 1,
+        max_queue: 16,
+        jobs_dir: PathBuf::from("prover-jobs"),
+        receipt_kind: ReceiptKind::Groth16,
+        allowed_origins: Vec::new(),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                println!("{}", usage());
+                std::process::exit(0);
+            }
+            "--allow-origin" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --allow-origin"))?;
+                options
+                    .allowed_origins
+                    .push(value.trim().trim_end_matches('/').to_owned());
+            }
+            "--addr" => {
+                options.addr = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --addr"))?;
+            }
+            "--workers" => options.workers = parse_count("--workers", args.next())?,
+            "--max-queue" => options.max_queue = parse_count("--max-queue", args.next())?,
+            "--jobs-dir" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --jobs-dir"))?;
+                if value.trim().is_empty() {
+                    return Err(String::from("--jobs-dir path cannot be empty"));
+                }
+                options.jobs_dir = PathBuf::from(value);
+            }
+            "--receipt-kind" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --receipt-kind"))?;
+                options.receipt_kind = value
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("--receipt-kind: {e}"))?;
+            }
+            other => {
+                return Err(format!("Unknown argument: {other}"));
+            }
+        }
+    }
+
+    if options.allowed_origins.is_empty() {
+        options
+            .allowed_origins
+            .push(String::from(serve::DEFAULT_ALLOWED_ORIGIN));
+    }
+
+    Ok(options)
+}
+
+fn parse_batch_args(mut args: impl Iterator<Item = String>) -> Result<batch::BatchOptions, String> {
+    let mut source: Option<String> = None;
+    let mut options = batch::BatchOptions {
+        source: String::new(),
+        out_dir: PathBuf::from("batch-out"),
+        concurrency: 1,
+        receipt_kind: ReceiptKind::Groth16,
+        report_path: None,
+        cache_dir: Some(PathBuf::from(cache::DEFAULT_CACHE_DIR)),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                println!("{}", usage());
+                std::process::exit(0);
+            }
+            "--out-dir" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --out-dir"))?;
+                if value.trim().is_empty() {
+                    return Err(String::from("--out-dir path cannot be empty"));
+                }
+                options.out_dir = PathBuf::from(value);
+            }
+            "--concurrency" => options.concurrency = parse_count("--concurrency", args.next())?,
+            "--receipt-kind" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --receipt-kind"))?;
+                options.receipt_kind = value
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("--receipt-kind: {e}"))?;
+            }
+            "--report" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --report"))?;
+                if value.trim().is_empty() {
+                    return Err(String::from("--report path cannot be empty"));
+                }
+                options.report_path = Some(PathBuf::from(value));
+            }
+            "--cache-dir" => {
+                options.cache_dir = Some(PathBuf::from(parse_cache_dir(args.next())?));
+            }
+            "--no-cache" => options.cache_dir = None,
+            other if other.starts_with("--") => {
+                return Err(format!("Unknown argument: {other}"));
+            }
+            other => {
+                if source.is_some() {
+                    return Err(String::from("prove-batch expects exactly one <dir|glob>"));
+                }
+                source = Some(other.to_string());
+            }
+        }
+    }
+
+    options.source =
+        source.ok_or_else(|| String::from("prove-batch expects a directory or glob of inputs"))?;
+    Ok(options)
+}
+
+fn parse_cache_args(mut args: impl Iterator<Item = String>) -> Result<cache::CacheOptions, String> {
+    let action = args
+        .next()
+        .ok_or_else(|| String::from("cache expects list, verify or prune"))?;
+    let mut cache_dir = String::from(cache::DEFAULT_CACHE_DIR);
+    let mut keys = Vec::new();
+    let mut prune = PruneOptions::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--cache-dir" => cache_dir = parse_cache_dir(args.next())?,
+            "--stale" if action == "prune" => prune.stale = true,
+            "--all" if action == "prune" => prune.all = true,
+            "--older-than-days" if action == "prune" => {
+                let days = parse_count("--older-than-days", args.next())? as u64;
+                prune.older_than = Some(Duration::from_secs(days * 86_400));
+            }
+            other if other.starts_with("--") => {
+                return Err(format!("Unknown argument: {other}"));
+            }
+            other if action == "verify" => keys.push(other.to_string()),
+            other => return Err(format!("Unexpected argument: {other}")),
+        }
+    }
+
+    let action = match action.as_str() {
+        "list" => cache::CacheAction::List,
+        "verify" => cache::CacheAction::Verify(keys),
+        "prune" => {
+            if !prune.stale && !prune.all && prune.older_than.is_none() {
+                return Err(String::from(
+                    "cache prune needs --stale, --older-than-days <n> or --all",
+                ));
+            }
+            cache::CacheAction::Prune(prune)
+        }
+        other => return Err(format!("cache expects list, verify or prune, got '{other}'")),
+    };
+
+    Ok(cache::CacheOptions { cache_dir, action })
+}
+
+fn parse_bench_args(mut args: impl Iterator<Item = String>) -> Result<bench::BenchOptions, String> {
+    let mut options = bench::BenchOptions {
+        receipt_kind: ReceiptKind::Groth16,
+        runs: 1,
+        out_path: PathBuf::from("bench-report.json"),
+        baseline: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                println!("{}", usage());
+                std::process::exit(0);
+            }
+            "--runs" => options.runs = parse_count("--runs", args.next())?,
+            "--receipt-kind" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --receipt-kind"))?;
+                options.receipt_kind = value
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("--receipt-kind: {e}"))?;
+            }
+            "--out" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --out"))?;
+                if value.trim().is_empty() {
+                    return Err(String::from("--out path cannot be empty"));
+                }
+                options.out_path = PathBuf::from(value);
+            }
+            "--baseline" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --baseline"))?;
+                options.baseline = Some(PathBuf::from(value));
+            }
+            other => {
+                return Err(format!("Unknown argument: {other}"));
+            }
+        }
+    }
+
+    Ok(options)
+}
+
+fn parse_transcript_args(
+    mut args: impl Iterator<Item = String>,
+) -> Result<transcript::TranscriptOptions, String> {
+    let mut input_path: Option<String> = None;
+    let mut proof_path: Option<String> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                println!("{}", usage());
+                std::process::exit(0);
+            }
+            "--proof" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --proof"))?;
+                if value.trim().is_empty() {
+                    return Err(String::from("--proof path cannot be empty"));
+                }
+                proof_path = Some(value);
+            }
+            other if other.starts_with("--") => {
+                return Err(format!("Unknown argument: {other}"));
+            }
+            other => {
+                if input_path.is_some() {
+                    return Err(String::from("transcript expects exactly one game input"));
+                }
+                input_path = Some(other.to_string());
+            }
+        }
+    }
+
+    Ok(transcript::TranscriptOptions {
+        input_path: input_path
+            .ok_or_else(|| String::from("transcript expects a game-input.json path"))?,
+        proof_path,
+    })
+}
+
+fn parse_turns_dir(value: Option<String>) -> Result<String, String> {
+    let value = value.ok_or_else(|| String::from("Missing value for --turns"))?;
+    if value.trim().is_empty() {
+        return Err(String::from("--turns path cannot be empty"));
     }
+    Ok(value)
 }
 
-fn load_game_input(path: &str) -> Result<GameInput, String> {
-    let raw = fs::read_to_string(path)
-        .map_err(|e| format!("Failed to read input file '{}': {e}", path))?;
-    serde_json::from_str::<GameInput>(&raw)
-        .map_err(|e| format!("Failed to parse JSON in '{}': {e}", path))
+fn parse_turns_args(
+    mut args: impl Iterator<Item = String>,
+) -> Result<turns::TurnsOptions, String> {
+    let mut input_path: Option<String> = None;
+    let mut turns_dir = String::from(turns::DEFAULT_TURNS_DIR);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                println!("{}", usage());
+                std::process::exit(0);
+            }
+            "--turns" => turns_dir = parse_turns_dir(args.next())?,
+            other if other.starts_with("--") => {
+                return Err(format!("Unknown argument: {other}"));
+            }
+            other => {
+                if input_path.is_some() {
+                    return Err(String::from("prove-turns expects exactly one game input"));
+                }
+                input_path = Some(other.to_string());
+            }
+        }
+    }
+
+    Ok(turns::TurnsOptions {
+        input_path: input_path
+            .ok_or_else(|| String::from("prove-turns expects a game-input.json path"))?,
+        turns_dir: PathBuf::from(turns_dir),
+    })
+}
+
+fn parse_reveal_args(
+    mut args: impl Iterator<Item = String>,
+) -> Result<reveal::RevealOptions, String> {
+    let mut input_path: Option<String> = None;
+    let mut out_path = String::from(reveal::DEFAULT_REVEAL_BUNDLE);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                println!("{}", usage());
+                std::process::exit(0);
+            }
+            "--out" => {
+                out_path = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --out"))?;
+                if out_path.trim().is_empty() {
+                    return Err(String::from("--out path cannot be empty"));
+                }
+            }
+            other if other.starts_with("--") => {
+                return Err(format!("Unknown argument: {other}"));
+            }
+            other => {
+                if input_path.is_some() {
+                    return Err(String::from("reveal expects exactly one game input"));
+                }
+                input_path = Some(other.to_string());
+            }
+        }
+    }
+
+    Ok(reveal::RevealOptions {
+        input_path: input_path
+            .ok_or_else(|| String::from("reveal expects a game-input.json path"))?,
+        out_path,
+    })
+}
+
+/// Parses the arguments of `free-for-all` or `teams`; `defaults` are the proof, receipt
+/// and bundle paths.
+fn parse_match_args(
+    command: &str,
+    defaults: [&str; 3],
+    mut args: impl Iterator<Item = String>,
+) -> Result<free_for_all::MatchOptions, String> {
+    let [proof, receipt, bundle] = defaults;
+    let mut input_path: Option<String> = None;
+    let mut proof_out_path = String::from(proof);
+    let mut receipt_out_path = String::from(receipt);
+    let mut bundle_out_path = String::from(bundle);
+    let mut submitter: Option<String> = None;
+    let mut receipt_kind = ReceiptKind::Groth16;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                println!("{}", usage());
+                std::process::exit(0);
+            }
+            flag @ ("--proof" | "--receipt" | "--bundle") => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("Missing value for {flag}"))?;
+                if value.trim().is_empty() {
+                    return Err(format!("{flag} path cannot be empty"));
+                }
+                match flag {
+                    "--proof" => proof_out_path = value,
+                    "--receipt" => receipt_out_path = value,
+                    _ => bundle_out_path = value,
+                }
+            }
+            "--submitter" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --submitter"))?;
+                submitter = Some(parse_submitter(value.trim())?);
+            }
+            "--receipt-kind" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --receipt-kind"))?;
+                receipt_kind = value
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("--receipt-kind: {e}"))?;
+            }
+            other if other.starts_with("--") => {
+                return Err(format!("Unknown argument: {other}"));
+            }
+            other => {
+                if input_path.is_some() {
+                    return Err(format!("{command} expects exactly one input"));
+                }
+                input_path = Some(other.to_string());
+            }
+        }
+    }
+
+    Ok(free_for_all::MatchOptions {
+        input_path: input_path.ok_or_else(|| format!("{command} expects an input json path"))?,
+        proof_out_path,
+        receipt_out_path,
+        bundle_out_path,
+        submitter,
+        receipt_kind,
+    })
 }
 
-fn build_game_input(cli: &CliOptions) -> Result<GameInput, String> {
+/// Exits with `EXIT_INVALID_OUTPUT_PATH` unless every output path of `options` is usable.
+fn validate_match_outputs(options: &free_for_all::MatchOptions) {
+    let outputs = [
+        ("--proof", &options.proof_out_path),
+        ("--receipt", &options.receipt_out_path),
+        ("--bundle", &options.bundle_out_path),
+    ];
+    if let Err(err) = validate_distinct_outputs(&outputs) {
+        eprintln!("Error: {}", err);
+        std::process::exit(EXIT_INVALID_OUTPUT_PATH);
+    }
+}
+
+fn parse_open_cell_args(
+    mut args: impl Iterator<Item = String>,
+) -> Result<opening::OpeningOptions, String> {
+    let mut input_path: Option<String> = None;
+    let mut player: Option<u8> = None;
+    let mut cell: Option<(u32, u32)> = None;
+    let mut out_path: Option<String> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                println!("{}", usage());
+                std::process::exit(0);
+            }
+            "--player" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --player"))?;
+                player = match value.trim() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    _ => return Err(String::from("--player must be 1 or 2")),
+                };
+            }
+            "--cell" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --cell"))?;
+                let parsed = value
+                    .split_once(',')
+                    .and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?)));
+                cell = Some(parsed.ok_or_else(|| String::from("--cell must be <x>,<y>"))?);
+            }
+            "--out" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --out"))?;
+                if value.trim().is_empty() {
+                    return Err(String::from("--out path cannot be empty"));
+                }
+                out_path = Some(value);
+            }
+            other if other.starts_with("--") => {
+                return Err(format!("Unknown argument: {other}"));
+            }
+            other => {
+                if input_path.is_some() {
+                    return Err(String::from("open-cell expects exactly one game input"));
+                }
+                input_path = Some(other.to_string());
+            }
+        }
+    }
+
+    let (x, y) = cell.ok_or_else(|| String::from("open-cell needs --cell <x>,<y>"))?;
+    Ok(opening::OpeningOptions {
+        input_path: input_path
+            .ok_or_else(|| String::from("open-cell expects a game-input.json path"))?,
+        player: player.ok_or_else(|| String::from("open-cell needs --player <1|2>"))?,
+        x,
+        y,
+        out_path,
+    })
+}
+
+fn build_game_input(cli: &CliOptions) -> Result<GameInput, ProverError> {
     let mut input = if let Some(path) = &cli.input_path {
         load_game_input(path)?
     } else {
-        default_game_input(cli.session_id.unwrap_or(42))
+        signed_game_input(cli.session_id.unwrap_or(42))
     };
 
     if let Some(session_id) = cli.session_id {
+        // Move signatures cover the session id, so a signed game cannot be moved to another.
+        if input.player_keys.is_some() && input.session_id != session_id {
+            return Err(ProverError::Input(format!(
+                "--session {session_id} does not match the signed game's session {}; sign the moves for that session instead",
+                input.session_id
+            )));
+        }
         input.session_id = session_id;
     }
 
     if input.session_id == 0 {
-        return Err(String::from("game input session_id must be greater than 0"));
+        return Err(ProverError::Input(String::from(
+            "game input session_id must be greater than 0",
+        )));
     }
 
     Ok(input)
 }
 
-fn write_receipt_bin(bytes: &[u8], path: &str) {
-    let mut file = File::create(path).unwrap();
-    file.write_all(bytes).unwrap();
-    println!("receipt saved: {}", path);
+fn validate_output_paths(cli: &CliOptions) -> Result<(), String> {
+    validate_distinct_outputs(&[
+        ("--proof", &cli.proof_out_path),
+        ("--receipt", &cli.receipt_out_path),
+        ("--bundle", &cli.bundle_out_path),
+        ("--stats", &cli.stats_out_path),
+    ])
 }
 
-fn write_proof_output_json(content: &ProofOutputFile, path: &str) {
-    let json = serde_json::to_string_pretty(content).unwrap();
-    fs::write(path, json).unwrap();
-    println!("proof output saved: {}", path);
+/// Checks every output path is writable and that no two of them name the same file.
+fn validate_distinct_outputs(outputs: &[(&str, &String)]) -> Result<(), String> {
+    let targets: Vec<_> = outputs.iter().map(|(_, path)| output_target(path)).collect();
+    for (i, (flag, path)) in outputs.iter().enumerate() {
+        if let Some(other) = targets[..i].iter().position(|target| *target == targets[i]) {
+            let other_flag = outputs[other].0;
+            return Err(format!("{flag} and {other_flag} point to the same file '{path}'"));
+        }
+        validate_output_path(flag, path)?;
+    }
+
+    Ok(())
 }
 
-fn run_proof(input: &GameInput) -> Result<(risc0_zkvm::Receipt, PublicOutputJson), String> {
-    let env = ExecutorEnv::builder()
-        .write(input)
-        .map_err(|e| format!("failed to write executor input: {e}"))?
-        .build()
-        .map_err(|e| format!("failed to build executor env: {e}"))?;
-
-    let prover = default_prover();
-    let prove_info = prover
-        .prove(env, METHOD_ELF)
-        .map_err(|e| format!("prove failed: {e}"))?;
-    let receipt = prove_info.receipt;
-
-    receipt
-        .verify(METHOD_ID)
-        .map_err(|e| format!("receipt verification failed: {e}"))?;
-
-    let output: PublicOutput = receipt
-        .journal
-        .decode()
-        .map_err(|e| format!("journal decode failed: {e}"))?;
-
-    let public_output_json = PublicOutputJson {
-        session_id: output.session_id,
-        winner: output.winner,
-        board_hash_p1: hex::encode(output.board_hash_p1),
-        board_hash_p2: hex::encode(output.board_hash_p2),
-        total_moves: output.total_moves,
-    };
+fn exit_code(err: &ProverError) -> i32 {
+    match err {
+        ProverError::Input(_) => EXIT_USAGE,
+        ProverError::Guest(_)
+        | ProverError::Prove(_)
+        | ProverError::Env(_)
+        | ProverError::Compress(_)
+        | ProverError::Verify(_) => EXIT_PROOF_FAILED,
+        ProverError::Bundle(_) | ProverError::Io(_) => EXIT_WRITE_FAILED,
+    }
+}
 
-    Ok((receipt, public_output_json))
+fn fail(err: ProverError) -> ! {
+    eprintln!("Error: {}", err);
+    std::process::exit(exit_code(&err));
 }
 
 fn main() {
@@ -252,10 +793,71 @@ fn main() {
         .init();
 
     let cli = match parse_cli_args() {
-        Ok(options) => options,
+        Ok(Command::Prove(options)) => options,
+        Ok(Command::Inspect(paths)) => {
+            std::process::exit(inspect::run(&paths));
+        }
+        Ok(Command::Serve(options)) => {
+            if let Err(err) = serve::run(&options) {
+                eprintln!("Error: {}", err);
+                std::process::exit(EXIT_INVALID_OUTPUT_PATH);
+            }
+            return;
+        }
+        Ok(Command::Cache(options)) => {
+            std::process::exit(cache::run(&options));
+        }
+        Ok(Command::Bench(options)) => {
+            if let Err(err) = validate_output_path("--out", &options.out_path.to_string_lossy()) {
+                eprintln!("Error: {}", err);
+                std::process::exit(EXIT_INVALID_OUTPUT_PATH);
+            }
+            bench::run(&options).unwrap_or_else(|err| fail(err));
+            return;
+        }
+        Ok(Command::Transcript(options)) => {
+            std::process::exit(transcript::run(&options));
+        }
+        Ok(Command::ProveTurns(options)) => {
+            turns::run(&options).unwrap_or_else(|err| fail(err));
+            return;
+        }
+        Ok(Command::OpenCell(options)) => {
+            if let Some(path) = &options.out_path {
+                if let Err(err) = validate_output_path("--out", path) {
+                    eprintln!("Error: {}", err);
+                    std::process::exit(EXIT_INVALID_OUTPUT_PATH);
+                }
+            }
+            opening::run(&options).unwrap_or_else(|err| fail(err));
+            return;
+        }
+        Ok(Command::Reveal(options)) => {
+            if let Err(err) = validate_output_path("--out", &options.out_path) {
+                eprintln!("Error: {}", err);
+                std::process::exit(EXIT_INVALID_OUTPUT_PATH);
+            }
+            reveal::run(&options).unwrap_or_else(|err| fail(err));
+            return;
+        }
+        Ok(Command::FreeForAll(options)) => {
+            validate_match_outputs(&options);
+            free_for_all::run(&options).unwrap_or_else(|err| fail(err));
+            return;
+        }
+        Ok(Command::Teams(options)) => {
+            validate_match_outputs(&options);
+            teams::run(&options).unwrap_or_else(|err| fail(err));
+            return;
+        }
+        Ok(Command::ProveBatch(options)) => match batch::run(&options) {
+            Ok(true) => return,
+            Ok(false) => std::process::exit(EXIT_PROOF_FAILED),
+            Err(err) => fail(err),
+        },
         Err(err) => {
             eprintln!("Error: {}\n\n{}", err, usage());
-            std::process::exit(2);
+            std::process::exit(EXIT_USAGE);
         }
     };
 
@@ -263,151 +865,160 @@ fn main() {
         Ok(input) => input,
         Err(err) => {
             eprintln!("Error: {}\n\n{}", err, usage());
-            std::process::exit(2);
+            std::process::exit(EXIT_USAGE);
         }
     };
 
+    if let Err(err) = validate_output_paths(&cli) {
+        eprintln!("Error: {}", err);
+        std::process::exit(EXIT_INVALID_OUTPUT_PATH);
+    }
+
     println!("session_id: {}", input.session_id);
     println!("input path: {}", cli.input_path.as_deref().unwrap_or("<built-in sample>"));
     println!("proof output path: {}", cli.proof_out_path);
     println!("receipt output path: {}", cli.receipt_out_path);
+    println!("receipt kind requested: {}", cli.receipt_kind.as_str());
 
-    let prover = default_prover();
-
-    let (receipt, public_output_json) = match run_proof(&input) {
-        Ok(data) => data,
-        Err(err) => {
-            eprintln!("Error: {}", err);
-            std::process::exit(1);
-        }
+    let opts = ProveOptions {
+        receipt_kind: cli.receipt_kind,
     };
-
-    println!("winner: {}", public_output_json.winner);
-    println!("total_moves: {}", public_output_json.total_moves);
-    println!("board_hash_p1: {}", public_output_json.board_hash_p1);
-    println!("board_hash_p2: {}", public_output_json.board_hash_p2);
-
-    match prover.compress(&ProverOpts::groth16(), &receipt) {
-        Ok(compressed_receipt) => {
-            let receipt_bytes = bincode::serialize(&compressed_receipt).unwrap();
-            write_receipt_bin(&receipt_bytes, &cli.receipt_out_path);
-
-            let journal_hex = hex::encode(&compressed_receipt.journal.bytes);
-            println!("Journal HEX: {}", journal_hex);
-
-            let seal_hex = match compressed_receipt.inner.groth16() {
-                Ok(groth16) => {
-                    let seal = hex::encode(&groth16.seal);
-                    println!("Seal HEX: {}", seal);
-                    Some(seal)
-                }
-                Err(_) => {
-                    println!(
-                        "Groth16 seal is unavailable in this mode (expected in RISC0_DEV_MODE=1)."
-                    );
-                    None
-                }
-            };
-
-            let artifact = ProofOutputFile {
-                journal_hex,
-                seal_hex,
-                public_output: public_output_json,
-            };
-            write_proof_output_json(&artifact, &cli.proof_out_path);
+    let artifact = match (&cli.turns_dir, &cli.cache_dir) {
+        (Some(dir), _) => {
+            println!("proving turn by turn: {dir}");
+            let receipts =
+                turns::update(&input, Path::new(dir)).unwrap_or_else(|err| fail(err));
+            aggregate(&input, &receipts, &opts).unwrap_or_else(|err| fail(err))
         }
-        Err(err) => {
-            println!("Skipping Groth16 compression: {err}");
-
-            let artifact = ProofOutputFile {
-                journal_hex: hex::encode(&receipt.journal.bytes),
-                seal_hex: None,
-                public_output: public_output_json,
-            };
-            write_proof_output_json(&artifact, &cli.proof_out_path);
+        (None, Some(dir)) => {
+            let (artifact, lookup) = ProofCache::new(dir)
+                .prove(&input, &opts)
+                .unwrap_or_else(|err| fail(err));
+            match lookup {
+                CacheLookup::Hit => println!("proof cache: hit ({dir})"),
+                CacheLookup::Miss => println!("proof cache: miss ({dir})"),
+            }
+            artifact
         }
-    }
-}
-
+        (None, None) => prove(&input, &opts).unwrap_or_else(|err| fail(err)),
+    };
+    let output = &artifact.output;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn valid_input_proves_successfully() {
-        let input = default_game_input(777);
-        let result = run_proof(&input);
-        assert!(result.is_ok(), "expected valid input to prove, got: {:?}", result.err());
-
-        let (_, out) = result.unwrap();
-        assert_eq!(out.session_id, 777);
-        assert_eq!(out.total_moves, 7);
-    }
-
-    #[test]
-    fn duplicate_shot_is_rejected_by_guest() {
-        let mut input = default_game_input(778);
-        // Duplicate shot for player 1 at (0,0)
-        input.moves = vec![
-            Move { player: 1, x: 0, y: 0 },
-            Move { player: 2, x: 1, y: 0 },
-            Move { player: 1, x: 0, y: 0 },
-            Move { player: 2, x: 2, y: 0 },
-        ];
-
-        let err = run_proof(&input).expect_err("expected duplicate shot to fail");
-        assert!(
-            err.contains("duplicate shot by player 1"),
-            "unexpected error message: {err}"
+    let rules: Vec<&str> = [
+        (output.public_output.no_touching, "no touching"),
+        (output.public_output.salvo, "salvo"),
+        (output.public_output.hit_keeps_turn, "hit keeps turn"),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, rule)| enabled.then_some(rule))
+    .collect();
+    if rules.is_empty() {
+        println!("mode: {}", output.public_output.mode.as_str());
+    } else {
+        println!("mode: {} ({})", output.public_output.mode.as_str(), rules.join(", "));
+    }
+    println!("winner: {}", output.public_output.winner);
+    println!("outcome: {}", output.public_output.outcome.as_str());
+    println!("total_moves: {}", output.public_output.total_moves);
+    println!("transcript_hash: {}", output.public_output.transcript_hash);
+    let weapons = &output.public_output.weapons;
+    if *weapons != Weapons::default() {
+        println!(
+            "weapons per player: {} bombs, {} line strikes, {} sonar pings",
+            weapons.bombs, weapons.line_strikes, weapons.sonar_pings
         );
     }
-
-
-    #[test]
-    fn invalid_board_layout_is_rejected() {
-        let mut input = default_game_input(7781);
-        // 2x2 contiguous block is one ship of size 4, which is invalid for the new rule.
-        input.board_p1 = [
-            1, 1, 0, 0,
-            1, 1, 0, 0,
-            0, 0, 0, 0,
-            0, 0, 0, 0,
-        ];
-
-        let err = run_proof(&input).expect_err("expected invalid board layout to fail");
-        assert!(
-            err.contains("invalid board layout P1"),
-            "unexpected error message: {err}"
+    for ping in &output.public_output.sonar {
+        println!(
+            "sonar: move {} by P{} at ({}, {}) found {} ship cells",
+            ping.move_index, ping.player, ping.x, ping.y, ping.ship_cells
         );
     }
+    if output.public_output.mines != 0 {
+        println!(
+            "mines per player: {} ({})",
+            output.public_output.mines,
+            output.public_output.mine_penalty.as_str()
+        );
+    }
+    for hit in &output.public_output.mine_hits {
+        match hit.revealed {
+            Some(cell) => println!(
+                "mine: move {} by P{} at ({}, {}) revealed P{}'s ({}, {})",
+                hit.move_index, hit.player, hit.x, hit.y, hit.player, cell.x, cell.y
+            ),
+            None => println!(
+                "mine: move {} by P{} at ({}, {})",
+                hit.move_index, hit.player, hit.x, hit.y
+            ),
+        }
+    }
+    for (player, stats) in [(1, &output.public_output.stats_p1), (2, &output.public_output.stats_p2)] {
+        println!(
+            "P{}: {} ships sunk, {} hits, {} misses, {}.{:02}% accuracy",
+            player,
+            stats.ships_sunk,
+            stats.hits,
+            stats.misses,
+            stats.accuracy_bps / 100,
+            stats.accuracy_bps % 100
+        );
+    }
+    println!("board_hash_p1: {}", output.public_output.board_hash_p1);
+    println!("board_hash_p2: {}", output.public_output.board_hash_p2);
+    match &output.public_output.player_keys {
+        Some([p1, p2]) => println!("signed by: P1 {p1}, P2 {p2}"),
+        None => println!("signed by: nobody (submit_result needs a signed game)"),
+    }
+    println!("receipt kind: {}", output.receipt_kind);
+    match &artifact.stats {
+        Some(stats) => {
+            println!("{}", stats);
+            write_json(stats, &cli.stats_out_path).unwrap_or_else(|err| fail(err));
+            println!("proof stats saved: {}", cli.stats_out_path);
+        }
+        None => println!("proof stats: not recorded for cached proofs (rerun with --no-cache)"),
+    }
 
+    artifact
+        .write_receipt(&cli.receipt_out_path)
+        .unwrap_or_else(|err| fail(err));
+    println!("receipt saved: {}", cli.receipt_out_path);
 
-    #[test]
-    fn moves_after_game_over_are_rejected() {
-        let mut input = default_game_input(779);
-        // P1 wins in 7 moves in default sample; add an extra move after game over.
-        input.moves.push(Move { player: 2, x: 2, y: 2 });
-
-        let err = run_proof(&input).expect_err("expected moves-after-game-over to fail");
-        assert!(
-            err.contains("moves after game over are not allowed"),
-            "unexpected error message: {err}"
-        );
+    println!("Journal HEX: {}", output.journal_hex);
+    match &output.seal_hex {
+        Some(seal) => println!("Seal HEX: {}", seal),
+        None if cli.receipt_kind == ReceiptKind::Groth16 => {
+            println!("Groth16 seal is unavailable in this mode (expected in RISC0_DEV_MODE=1).");
+        }
+        None => {}
     }
 
-    #[test]
-    fn invalid_turn_order_is_rejected() {
-        let mut input = default_game_input(780);
-        input.moves = vec![
-            Move { player: 1, x: 0, y: 0 },
-            Move { player: 1, x: 1, y: 0 }, // invalid: player 1 twice
-        ];
+    artifact
+        .write_output(&cli.proof_out_path)
+        .unwrap_or_else(|err| fail(err));
+    println!("proof output saved: {}", cli.proof_out_path);
 
-        let err = run_proof(&input).expect_err("expected invalid turn order to fail");
-        assert!(
-            err.contains("invalid turn order"),
-            "unexpected error message: {err}"
+    // An unsigned game can never settle through submit_result, so it gets no bundle; that is
+    // only an error when a bundle was asked for.
+    let bundle = match artifact.submission_bundle(cli.submitter.as_deref()) {
+        Ok(bundle) => bundle,
+        Err(err) if !cli.bundle_requested && output.public_output.player_keys.is_none() => {
+            println!("no submission bundle: {err}");
+            return;
+        }
+        Err(err) => fail(err),
+    };
+    write_json(&bundle, &cli.bundle_out_path).unwrap_or_else(|err| fail(err));
+    println!("submission bundle saved: {}", cli.bundle_out_path);
+    if bundle.seal_hex.is_none() {
+        println!("submission bundle has no seal; submit_result needs a --receipt-kind groth16 proof outside dev mode.");
+    }
+    println!("submit command: {}", bundle.stellar_invoke_command);
+    if artifact.image_id == AGGREGATE_ID {
+        println!(
+            "aggregated proof: submit_result accepts it once the contract's aggregate image id is {} (set_aggregate_image_id)",
+            aggregate_image_id_hex()
         );
     }
 }
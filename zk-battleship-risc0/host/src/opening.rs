@@ -0,0 +1,147 @@
+//! `open-cell`: reveals one cell of a committed board with its Merkle path, for
+//! `verify_cell_opening` or an opponent to check a hit/miss answer without a proof.
+
+use battleship_prover::merkle::{board_root, open_cell, verify_opening};
+use battleship_prover::{cell_maps, load_game_input, write_json, GameInput, ProverError, MINE};
+use serde::Serialize;
+
+pub struct OpeningOptions {
+    pub input_path: String,
+    /// 1 or 2: whose board to open.
+    pub player: u8,
+    pub x: u32,
+    pub y: u32,
+    pub out_path: Option<String>,
+}
+
+/// Contents of the opening file: the `verify_cell_opening` arguments, hex-encoded.
+#[derive(Debug, Serialize)]
+pub struct OpeningFile {
+    pub session_id: u32,
+    pub player: u8,
+    pub x: u32,
+    pub y: u32,
+    pub board_commit: String,
+    pub index: u32,
+    /// The cell as committed: 0 for water, 1 for a ship, [`MINE`] for a mine.
+    pub cell: u8,
+    pub salt: String,
+    pub siblings: Vec<String>,
+}
+
+pub fn build(input: &GameInput, player: u8, x: u32, y: u32) -> Result<OpeningFile, ProverError> {
+    let size = input.mode.board_size();
+    if x >= size || y >= size {
+        return Err(ProverError::Input(format!(
+            "cell ({x}, {y}) is off the {size}x{size} board"
+        )));
+    }
+    let (cells, salt) = match player {
+        1 => (0, &input.board_salt_p1),
+        2 => (1, &input.board_salt_p2),
+        _ => return Err(ProverError::Input(String::from("--player must be 1 or 2"))),
+    };
+    let cells = &cell_maps(input).map_err(|e| ProverError::Input(e.to_string()))?[cells];
+
+    let root = board_root(cells, salt);
+    let opening = open_cell(cells, salt, y * size + x)
+        .ok_or_else(|| ProverError::Input(format!("cell ({x}, {y}) is not on the board")))?;
+    if !verify_opening(&root, &opening) {
+        return Err(ProverError::Verify(String::from(
+            "opening does not verify against the board commitment",
+        )));
+    }
+
+    Ok(OpeningFile {
+        session_id: input.session_id,
+        player,
+        x,
+        y,
+        board_commit: hex::encode(root),
+        index: opening.index,
+        cell: if opening.mine {
+            MINE
+        } else {
+            u8::from(opening.ship)
+        },
+        salt: hex::encode(opening.salt),
+        siblings: opening.siblings.iter().map(hex::encode).collect(),
+    })
+}
+
+pub fn run(options: &OpeningOptions) -> Result<(), ProverError> {
+    let input = load_game_input(&options.input_path)?;
+    let opening = build(&input, options.player, options.x, options.y)?;
+
+    println!("session_id: {}", opening.session_id);
+    println!("board_commit_p{}: {}", opening.player, opening.board_commit);
+    println!(
+        "cell ({}, {}) (index {}): {}",
+        opening.x,
+        opening.y,
+        opening.index,
+        match opening.cell {
+            0 => "water",
+            MINE => "mine",
+            _ => "ship",
+        }
+    );
+    println!("salt: {}", opening.salt);
+    println!("siblings: {}", opening.siblings.join(","));
+    if let Some(path) = &options.out_path {
+        write_json(&opening, path)?;
+        println!("opening saved: {path}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use battleship_prover::merkle::CellOpening;
+    use battleship_prover::{classic_game_input, default_game_input, mines_game_input};
+
+    fn parse(opening: &OpeningFile) -> ([u8; 32], CellOpening) {
+        let hash = |value: &str| -> [u8; 32] { hex::decode(value).unwrap().try_into().unwrap() };
+        let cell = CellOpening {
+            index: opening.index,
+            ship: opening.cell == 1,
+            mine: opening.cell == MINE,
+            salt: hash(&opening.salt),
+            siblings: opening.siblings.iter().map(|s| hash(s)).collect(),
+        };
+        (hash(&opening.board_commit), cell)
+    }
+
+    #[test]
+    fn openings_verify_against_the_salted_board_commitment() {
+        let mut input = default_game_input(42);
+        input.board_salt_p2 = [3; 32];
+        let cells = &cell_maps(&input).unwrap()[1];
+        let opening = build(&input, 2, 1, 2).unwrap();
+        assert_eq!(opening.index, 9);
+        assert_eq!(opening.cell, 1);
+        assert_eq!(
+            opening.board_commit,
+            hex::encode(board_root(cells, &[3; 32]))
+        );
+        let (root, cell) = parse(&opening);
+        assert!(verify_opening(&root, &cell));
+
+        let water = build(&classic_game_input(7), 1, 9, 9).unwrap();
+        assert_eq!(water.cell, 0);
+        assert_eq!(water.siblings.len(), 7);
+
+        let mine = build(&mines_game_input(42), 1, 3, 1).unwrap();
+        assert_eq!(mine.cell, MINE);
+        let (root, cell) = parse(&mine);
+        assert!(verify_opening(&root, &cell));
+    }
+
+    #[test]
+    fn cells_off_the_board_are_rejected() {
+        let input = default_game_input(42);
+        assert!(matches!(build(&input, 1, 4, 0), Err(ProverError::Input(_))));
+        assert!(matches!(build(&input, 3, 0, 0), Err(ProverError::Input(_))));
+    }
+}
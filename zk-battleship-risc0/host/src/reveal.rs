@@ -0,0 +1,177 @@
+//! `reveal`: the arguments for settling a finished demo game without a proof, through the
+//! contract's `reveal_board` (once per player) and `settle_revealed`.
+
+use battleship_prover::{
+    cell_maps, load_game_input, merkle, play, write_json, Action, GameInput, GameMode, Outcome,
+    ProverError, Weapons,
+};
+use serde::Serialize;
+
+pub const DEFAULT_REVEAL_BUNDLE: &str = "reveal-bundle.json";
+
+pub struct RevealOptions {
+    pub input_path: String,
+    pub out_path: String,
+}
+
+/// One player's `reveal_board` arguments, hex-encoded.
+#[derive(Debug, Serialize)]
+pub struct BoardReveal {
+    pub player: u8,
+    pub board_commit: String,
+    /// The 16 row-major cells, one byte each (1 = ship).
+    pub cells: String,
+    pub board_salt: String,
+}
+
+/// One entry of the `settle_revealed` move log.
+#[derive(Debug, Serialize)]
+pub struct RevealedShot {
+    pub x: u32,
+    pub y: u32,
+    pub signature: String,
+}
+
+/// Contents of `reveal-bundle.json`.
+#[derive(Debug, Serialize)]
+pub struct RevealBundle {
+    pub session_id: u32,
+    /// The winner the contract will find, as replayed by the rules engine.
+    pub winner: u8,
+    pub total_moves: u32,
+    pub boards: [BoardReveal; 2],
+    pub moves: Vec<RevealedShot>,
+    pub stellar_invoke_commands: Vec<String>,
+}
+
+/// Checks that `input` is a finished, signed game under the rules `settle_revealed`
+/// replays and collects its arguments.
+pub fn build(input: &GameInput) -> Result<RevealBundle, ProverError> {
+    if input.mode != GameMode::Demo
+        || input.no_touching
+        || input.salvo
+        || input.hit_keeps_turn
+        || input.weapons != Weapons::default()
+        || input.mines != 0
+    {
+        return Err(ProverError::Input(String::from(
+            "settle_revealed only replays the default demo rules",
+        )));
+    }
+    if input.player_keys.is_none() {
+        return Err(ProverError::Input(String::from(
+            "settle_revealed needs moves signed with both player keys",
+        )));
+    }
+    if input.moves.iter().any(|mv| mv.action != Action::Shot) {
+        return Err(ProverError::Input(String::from(
+            "settle_revealed only replays plain shots",
+        )));
+    }
+
+    let output = play(input).map_err(|e| ProverError::Input(e.to_string()))?;
+    if output.outcome != Outcome::AllSunk {
+        return Err(ProverError::Input(String::from(
+            "the game has no winner yet",
+        )));
+    }
+
+    let [cells_p1, cells_p2] = cell_maps(input).map_err(|e| ProverError::Input(e.to_string()))?;
+    let reveal = |player: u8, cells: Vec<u8>, salt: &[u8; 32]| BoardReveal {
+        player,
+        board_commit: hex::encode(merkle::board_root(&cells, salt)),
+        cells: hex::encode(cells),
+        board_salt: hex::encode(salt),
+    };
+    let boards = [
+        reveal(1, cells_p1, &input.board_salt_p1),
+        reveal(2, cells_p2, &input.board_salt_p2),
+    ];
+
+    let moves: Vec<RevealedShot> = input
+        .moves
+        .iter()
+        .map(|mv| RevealedShot {
+            x: u32::from(mv.x),
+            y: u32::from(mv.y),
+            signature: hex::encode(&mv.signature),
+        })
+        .collect();
+
+    let invoke = "stellar contract invoke --id <CONTRACT_ID> --network testnet";
+    let mut stellar_invoke_commands: Vec<String> = boards
+        .iter()
+        .map(|board| {
+            format!(
+                "{invoke} --source-account <PLAYER{p}> -- reveal_board --session_id {} --player <PLAYER{p}_ADDRESS> --cells {} --board_salt {}",
+                input.session_id,
+                board.cells,
+                board.board_salt,
+                p = board.player,
+            )
+        })
+        .collect();
+    let log = serde_json::to_string(&moves)
+        .map_err(|e| ProverError::Io(format!("failed to encode moves: {e}")))?;
+    stellar_invoke_commands.push(format!(
+        "{invoke} --source-account <SUBMITTER> -- settle_revealed --session_id {} --submitter <SUBMITTER_ADDRESS> --moves '{log}'",
+        input.session_id
+    ));
+
+    Ok(RevealBundle {
+        session_id: input.session_id,
+        winner: output.winner,
+        total_moves: output.total_moves,
+        boards,
+        moves,
+        stellar_invoke_commands,
+    })
+}
+
+pub fn run(options: &RevealOptions) -> Result<(), ProverError> {
+    let input = load_game_input(&options.input_path)?;
+    let bundle = build(&input)?;
+    write_json(&bundle, &options.out_path)?;
+
+    println!("session_id: {}", bundle.session_id);
+    println!(
+        "winner: P{} after {} moves",
+        bundle.winner, bundle.total_moves
+    );
+    for command in &bundle.stellar_invoke_commands {
+        println!("{command}");
+    }
+    println!("reveal bundle saved: {}", options.out_path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use battleship_prover::{default_game_input, signed_game_input};
+
+    #[test]
+    fn signed_demo_games_reveal_both_boards_and_every_move() {
+        let input = signed_game_input(42);
+        let bundle = build(&input).unwrap();
+        assert_eq!(bundle.winner, 1);
+        assert_eq!(bundle.moves.len(), input.moves.len());
+        assert_eq!(
+            bundle.moves[0].signature,
+            hex::encode(&input.moves[0].signature)
+        );
+        assert_eq!(bundle.boards[0].cells, "01010000000000000101000000000000");
+        assert_eq!(bundle.boards[1].board_salt, hex::encode([0u8; 32]));
+        assert_eq!(bundle.stellar_invoke_commands.len(), 3);
+
+        let unsigned = build(&default_game_input(42)).unwrap_err();
+        assert!(unsigned.to_string().contains("signed"));
+
+        let mut unfinished = input.clone();
+        unfinished.moves.pop();
+        assert!(build(&unfinished)
+            .unwrap_err()
+            .to_string()
+            .contains("no winner"));
+    }
+}
@@ -0,0 +1,693 @@
+//! `host serve`: a local HTTP proving service.
+//!
+//! Jobs are queued in memory, proven by a fixed pool of worker threads, and persisted under
+//! `<jobs-dir>/<job-id>/` so a restarted service picks up where it left off.
+
+use battleship_prover::{prove, write_json, GameInput, ProveOptions, ProverError, ReceiptKind};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::io::Read;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MAX_BODY_BYTES: u64 = 1024 * 1024;
+const INPUT_FILE: &str = "input.json";
+const STATUS_FILE: &str = "status.json";
+const PROOF_FILE: &str = "proof-output.json";
+const RECEIPT_FILE: &str = "receipt.bin";
+const BUNDLE_FILE: &str = "submission-bundle.json";
+
+/// Origin of the frontend's Vite dev server, the only browser origin allowed by default.
+pub const DEFAULT_ALLOWED_ORIGIN: &str = "http://localhost:3000";
+
+#[derive(Debug, Clone)]
+pub struct ServeOptions {
+    pub addr: String,
+    pub workers: usize,
+    pub max_queue: usize,
+    pub jobs_dir: PathBuf,
+    pub receipt_kind: ReceiptKind,
+    /// Browser origins allowed to call the API. Requests from any other origin are refused;
+    /// requests without an `Origin` header (curl, scripts) are always served.
+    pub allowed_origins: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    fn is_finished(self) -> bool {
+        matches!(self, Self::Succeeded | Self::Failed | Self::Cancelled)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct JobRecord {
+    job_id: String,
+    status: JobStatus,
+    session_id: u32,
+    receipt_kind: String,
+    error: Option<String>,
+    /// Set when a running job is cancelled; its result is discarded when the prover returns.
+    cancel_requested: bool,
+    created_at: u64,
+    started_at: Option<u64>,
+    finished_at: Option<u64>,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+struct JobStore {
+    dir: PathBuf,
+    jobs: HashMap<String, JobRecord>,
+    queue: VecDeque<String>,
+    max_queue: usize,
+    next_id: u64,
+}
+
+impl JobStore {
+    /// Loads persisted jobs. Jobs that were queued or running when the service stopped are
+    /// queued again, oldest first.
+    fn open(dir: &Path, max_queue: usize) -> Result<Self, String> {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("failed to create jobs dir '{}': {e}", dir.display()))?;
+
+        let mut jobs = HashMap::new();
+        let entries = fs::read_dir(dir)
+            .map_err(|e| format!("failed to read jobs dir '{}': {e}", dir.display()))?;
+        for entry in entries.flatten() {
+            let status_path = entry.path().join(STATUS_FILE);
+            let Ok(raw) = fs::read_to_string(&status_path) else {
+                continue;
+            };
+            match serde_json::from_str::<JobRecord>(&raw) {
+                Ok(record) => {
+                    jobs.insert(record.job_id.clone(), record);
+                }
+                Err(e) => eprintln!("skipping job '{}': {e}", status_path.display()),
+            }
+        }
+
+        let mut store = Self {
+            dir: dir.to_path_buf(),
+            jobs,
+            queue: VecDeque::new(),
+            max_queue,
+            next_id: 1,
+        };
+
+        let mut pending: Vec<JobRecord> = store
+            .jobs
+            .values()
+            .filter(|record| !record.status.is_finished())
+            .cloned()
+            .collect();
+        pending.sort_by(|a, b| a.job_id.cmp(&b.job_id));
+        for mut record in pending {
+            if record.cancel_requested {
+                record.status = JobStatus::Cancelled;
+                record.finished_at = Some(now());
+            } else {
+                record.status = JobStatus::Queued;
+                record.started_at = None;
+                store.queue.push_back(record.job_id.clone());
+            }
+            store.save(record)?;
+        }
+
+        store.next_id = store
+            .jobs
+            .keys()
+            .filter_map(|id| id.strip_prefix("job-")?.parse::<u64>().ok())
+            .max()
+            .map_or(1, |max| max + 1);
+
+        Ok(store)
+    }
+
+    fn job_dir(&self, job_id: &str) -> PathBuf {
+        self.dir.join(job_id)
+    }
+
+    fn file(&self, job_id: &str, name: &str) -> String {
+        self.job_dir(job_id)
+            .join(name)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn save(&mut self, record: JobRecord) -> Result<(), String> {
+        write_json(&record, &self.file(&record.job_id, STATUS_FILE)).map_err(|e| e.to_string())?;
+        self.jobs.insert(record.job_id.clone(), record);
+        Ok(())
+    }
+
+    fn is_full(&self) -> bool {
+        self.queue.len() >= self.max_queue
+    }
+
+    fn submit(
+        &mut self,
+        input: &GameInput,
+        receipt_kind: ReceiptKind,
+    ) -> Result<JobRecord, String> {
+        let job_id = format!("job-{:06}", self.next_id);
+        self.next_id += 1;
+
+        let job_dir = self.job_dir(&job_id);
+        fs::create_dir_all(&job_dir)
+            .map_err(|e| format!("failed to create '{}': {e}", job_dir.display()))?;
+        write_json(input, &self.file(&job_id, INPUT_FILE)).map_err(|e| e.to_string())?;
+
+        let record = JobRecord {
+            job_id: job_id.clone(),
+            status: JobStatus::Queued,
+            session_id: input.session_id,
+            receipt_kind: String::from(receipt_kind.as_str()),
+            error: None,
+            cancel_requested: false,
+            created_at: now(),
+            started_at: None,
+            finished_at: None,
+        };
+        self.save(record.clone())?;
+        self.queue.push_back(job_id);
+        Ok(record)
+    }
+
+    fn cancel(&mut self, job_id: &str) -> Option<Result<JobRecord, String>> {
+        let mut record = self.jobs.get(job_id)?.clone();
+        match record.status {
+            JobStatus::Queued => {
+                self.queue.retain(|id| id != job_id);
+                record.status = JobStatus::Cancelled;
+                record.finished_at = Some(now());
+            }
+            JobStatus::Running => record.cancel_requested = true,
+            _ => return Some(Ok(record)),
+        }
+        Some(self.save(record.clone()).map(|_| record))
+    }
+
+    /// Pops the next queued job and marks it running.
+    fn start_next(&mut self) -> Option<(JobRecord, PathBuf)> {
+        while let Some(job_id) = self.queue.pop_front() {
+            let Some(mut record) = self.jobs.get(&job_id).cloned() else {
+                continue;
+            };
+            if record.status != JobStatus::Queued {
+                continue;
+            }
+            record.status = JobStatus::Running;
+            record.started_at = Some(now());
+            if let Err(err) = self.save(record.clone()) {
+                eprintln!("failed to persist {job_id}: {err}");
+            }
+            return Some((record, self.job_dir(&job_id)));
+        }
+        None
+    }
+
+    fn finish(&mut self, job_id: &str, result: Result<(), String>) {
+        let Some(mut record) = self.jobs.get(job_id).cloned() else {
+            return;
+        };
+
+        record.finished_at = Some(now());
+        if record.cancel_requested {
+            record.status = JobStatus::Cancelled;
+            for name in [PROOF_FILE, RECEIPT_FILE, BUNDLE_FILE] {
+                let _ = fs::remove_file(self.job_dir(job_id).join(name));
+            }
+        } else {
+            match result {
+                Ok(()) => record.status = JobStatus::Succeeded,
+                Err(err) => {
+                    record.status = JobStatus::Failed;
+                    record.error = Some(err);
+                }
+            }
+        }
+
+        if let Err(err) = self.save(record) {
+            eprintln!("failed to persist {job_id}: {err}");
+        }
+    }
+}
+
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn json(status: u16, value: serde_json::Value) -> Self {
+        Self {
+            status,
+            body: value.to_string(),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self::json(status, json!({ "error": message.into() }))
+    }
+}
+
+pub struct ProvingService {
+    store: Mutex<JobStore>,
+    work_ready: Condvar,
+    receipt_kind: ReceiptKind,
+    allowed_origins: Vec<String>,
+}
+
+impl ProvingService {
+    fn new(options: &ServeOptions) -> Result<Self, String> {
+        Ok(Self {
+            store: Mutex::new(JobStore::open(&options.jobs_dir, options.max_queue)?),
+            work_ready: Condvar::new(),
+            receipt_kind: options.receipt_kind,
+            allowed_origins: options.allowed_origins.clone(),
+        })
+    }
+
+    fn allows_origin(&self, origin: &str) -> bool {
+        self.allowed_origins.iter().any(|allowed| allowed == origin)
+    }
+
+    fn route(&self, method: &str, url: &str, body: &[u8]) -> Response {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+        match (method, segments.as_slice()) {
+            ("GET", ["health"]) => Response::json(200, json!({ "status": "ok" })),
+            ("POST", ["jobs"]) => self.submit(query, body),
+            ("GET", ["jobs"]) => self.list(),
+            ("GET", ["jobs", job_id]) => self.status(job_id),
+            ("GET", ["jobs", job_id, "artifact"]) => self.job_file(job_id, PROOF_FILE),
+            ("GET", ["jobs", job_id, "bundle"]) => self.job_file(job_id, BUNDLE_FILE),
+            ("DELETE", ["jobs", job_id]) | ("POST", ["jobs", job_id, "cancel"]) => {
+                self.cancel(job_id)
+            }
+            _ => Response::error(404, format!("no route for {method} {path}")),
+        }
+    }
+
+    fn submit(&self, query: &str, body: &[u8]) -> Response {
+        let input: GameInput = match serde_json::from_slice(body) {
+            Ok(input) => input,
+            Err(e) => return Response::error(400, format!("invalid GameInput JSON: {e}")),
+        };
+        if input.session_id == 0 {
+            return Response::error(400, "game input session_id must be greater than 0");
+        }
+        if input.player_keys.is_none() {
+            return Response::error(
+                400,
+                "the game is unsigned: submit_result only settles games signed with both player keys",
+            );
+        }
+
+        let mut receipt_kind = self.receipt_kind;
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            match pair.split_once('=') {
+                Some(("receipt_kind", value)) => match value.parse() {
+                    Ok(kind) => receipt_kind = kind,
+                    Err(e) => return Response::error(400, e),
+                },
+                _ => return Response::error(400, format!("unknown query parameter '{pair}'")),
+            }
+        }
+
+        let mut store = self.store.lock().unwrap();
+        if store.is_full() {
+            return Response::error(
+                503,
+                format!("job queue is full ({} queued)", store.max_queue),
+            );
+        }
+        match store.submit(&input, receipt_kind) {
+            Ok(record) => {
+                self.work_ready.notify_one();
+                Response::json(202, json!(record))
+            }
+            Err(err) => Response::error(500, err),
+        }
+    }
+
+    fn list(&self) -> Response {
+        let store = self.store.lock().unwrap();
+        let mut jobs: Vec<&JobRecord> = store.jobs.values().collect();
+        jobs.sort_by(|a, b| a.job_id.cmp(&b.job_id));
+        Response::json(200, json!({ "jobs": jobs }))
+    }
+
+    fn status(&self, job_id: &str) -> Response {
+        let store = self.store.lock().unwrap();
+        match store.jobs.get(job_id) {
+            Some(record) => Response::json(200, json!(record)),
+            None => Response::error(404, format!("unknown job '{job_id}'")),
+        }
+    }
+
+    fn job_file(&self, job_id: &str, name: &str) -> Response {
+        let path = {
+            let store = self.store.lock().unwrap();
+            match store.jobs.get(job_id) {
+                Some(record) if record.status == JobStatus::Succeeded => {
+                    store.job_dir(job_id).join(name)
+                }
+                Some(record) => {
+                    return Response::error(
+                        409,
+                        format!(
+                            "job '{job_id}' has no artifact (status: {:?})",
+                            record.status
+                        ),
+                    )
+                }
+                None => return Response::error(404, format!("unknown job '{job_id}'")),
+            }
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(body) => Response { status: 200, body },
+            Err(e) => Response::error(500, format!("failed to read '{}': {e}", path.display())),
+        }
+    }
+
+    fn cancel(&self, job_id: &str) -> Response {
+        let mut store = self.store.lock().unwrap();
+        match store.cancel(job_id) {
+            Some(Ok(record)) if record.status == JobStatus::Running => {
+                let mut body = json!(record);
+                body["note"] = json!(
+                    "the prover cannot be interrupted; the job keeps its worker until proving \
+                     returns, then it is marked cancelled and its artifacts are discarded"
+                );
+                Response::json(202, body)
+            }
+            Some(Ok(record)) => Response::json(200, json!(record)),
+            Some(Err(err)) => Response::error(500, err),
+            None => Response::error(404, format!("unknown job '{job_id}'")),
+        }
+    }
+
+    fn run_worker(&self) {
+        loop {
+            let (record, job_dir) = {
+                let mut store = self.store.lock().unwrap();
+                loop {
+                    if let Some(job) = store.start_next() {
+                        break job;
+                    }
+                    store = self.work_ready.wait(store).unwrap();
+                }
+            };
+
+            println!("{}: proving session {}", record.job_id, record.session_id);
+            let result = catch_panic(|| prove_job(&record, &job_dir).map_err(|e| e.to_string()));
+            match &result {
+                Ok(()) => println!("{}: succeeded", record.job_id),
+                Err(err) => println!("{}: failed: {err}", record.job_id),
+            }
+
+            self.store.lock().unwrap().finish(&record.job_id, result);
+        }
+    }
+}
+
+fn prove_job(record: &JobRecord, job_dir: &Path) -> Result<(), ProverError> {
+    let input = battleship_prover::load_game_input(job_dir.join(INPUT_FILE))?;
+    let receipt_kind = record.receipt_kind.parse().map_err(ProverError::Input)?;
+
+    let artifact = prove(&input, &ProveOptions { receipt_kind })?;
+    let path = |name: &str| job_dir.join(name).to_string_lossy().into_owned();
+
+    artifact.write_receipt(&path(RECEIPT_FILE))?;
+    write_json(&artifact.submission_bundle(None)?, &path(BUNDLE_FILE))?;
+    artifact.write_output(&path(PROOF_FILE))
+}
+
+/// Runs `job`, turning a panic into an error so the job is marked failed and the worker
+/// survives to take the next one.
+fn catch_panic(job: impl FnOnce() -> Result<(), String>) -> Result<(), String> {
+    panic::catch_unwind(AssertUnwindSafe(job)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|msg| String::from(*msg))
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| String::from("unknown panic"));
+        Err(format!("prover panicked: {message}"))
+    })
+}
+
+fn respond(service: &ProvingService, mut request: tiny_http::Request) {
+    let mut body = Vec::new();
+    let read = request
+        .as_reader()
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_end(&mut body);
+
+    let origin = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Origin"))
+        .map(|header| header.value.as_str().to_owned());
+    let allowed_origin = origin
+        .as_deref()
+        .filter(|origin| service.allows_origin(origin));
+
+    let response = match read {
+        _ if origin.is_some() && allowed_origin.is_none() => Response::error(
+            403,
+            format!(
+                "origin '{}' is not allowed; start the service with --allow-origin",
+                origin.as_deref().unwrap_or_default()
+            ),
+        ),
+        Err(e) => Response::error(400, format!("failed to read request body: {e}")),
+        Ok(_) if body.len() as u64 > MAX_BODY_BYTES => {
+            Response::error(413, "request body is too large")
+        }
+        Ok(_) if request.method() == &tiny_http::Method::Options => Response {
+            status: 204,
+            body: String::new(),
+        },
+        Ok(_) => service.route(request.method().as_str(), request.url(), &body),
+    };
+
+    let mut headers = vec![("Content-Type", "application/json"), ("Vary", "Origin")];
+    if let Some(origin) = allowed_origin {
+        headers.extend([
+            ("Access-Control-Allow-Origin", origin),
+            ("Access-Control-Allow-Methods", "GET, POST, DELETE, OPTIONS"),
+            ("Access-Control-Allow-Headers", "Content-Type"),
+        ]);
+    }
+    let mut http =
+        tiny_http::Response::from_string(response.body).with_status_code(response.status);
+    for (name, value) in headers {
+        if let Ok(header) = tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes()) {
+            http.add_header(header);
+        }
+    }
+
+    if let Err(e) = request.respond(http) {
+        eprintln!("failed to send response: {e}");
+    }
+}
+
+/// Starts the workers and serves HTTP until the process is stopped.
+pub fn run(options: &ServeOptions) -> Result<(), String> {
+    let service = Arc::new(ProvingService::new(options)?);
+    let server = tiny_http::Server::http(&options.addr)
+        .map_err(|e| format!("failed to bind {}: {e}", options.addr))?;
+
+    for _ in 0..options.workers {
+        let service = Arc::clone(&service);
+        thread::spawn(move || service.run_worker());
+    }
+
+    println!(
+        "proving service listening on http://{} ({} workers, jobs in {}, origins: {})",
+        options.addr,
+        options.workers,
+        options.jobs_dir.display(),
+        options.allowed_origins.join(", ")
+    );
+
+    for request in server.incoming_requests() {
+        respond(&service, request);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use battleship_prover::{default_game_input, signed_game_input};
+
+    fn options(name: &str, max_queue: usize) -> ServeOptions {
+        let jobs_dir =
+            std::env::temp_dir().join(format!("host-serve-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&jobs_dir);
+        ServeOptions {
+            addr: String::from("127.0.0.1:0"),
+            workers: 1,
+            max_queue,
+            jobs_dir,
+            receipt_kind: ReceiptKind::Composite,
+            allowed_origins: vec![String::from(DEFAULT_ALLOWED_ORIGIN)],
+        }
+    }
+
+    fn submit(service: &ProvingService, session_id: u32) -> Response {
+        let body = serde_json::to_vec(&signed_game_input(session_id)).unwrap();
+        service.route("POST", "/jobs", &body)
+    }
+
+    fn field(response: &Response, name: &str) -> serde_json::Value {
+        serde_json::from_str::<serde_json::Value>(&response.body).unwrap()[name].clone()
+    }
+
+    #[test]
+    fn submitted_job_is_queued_and_persisted() {
+        let opts = options("submit", 4);
+        let service = ProvingService::new(&opts).unwrap();
+
+        let response = submit(&service, 42);
+        assert_eq!(
+            response.status, 202,
+            "unexpected response: {}",
+            response.body
+        );
+        assert_eq!(field(&response, "job_id"), "job-000001");
+        assert_eq!(field(&response, "status"), "queued");
+
+        assert!(opts.jobs_dir.join("job-000001").join(INPUT_FILE).is_file());
+        let status = service.route("GET", "/jobs/job-000001", &[]);
+        assert_eq!(field(&status, "session_id"), 42);
+
+        let artifact = service.route("GET", "/jobs/job-000001/artifact", &[]);
+        assert_eq!(artifact.status, 409);
+    }
+
+    #[test]
+    fn queue_is_bounded_and_rejects_bad_input() {
+        let service = ProvingService::new(&options("bounded", 1)).unwrap();
+
+        assert_eq!(submit(&service, 1).status, 202);
+        assert_eq!(submit(&service, 2).status, 503);
+        assert_eq!(service.route("POST", "/jobs", b"{}").status, 400);
+        assert_eq!(submit(&service, 0).status, 400);
+        assert_eq!(
+            service
+                .route(
+                    "POST",
+                    "/jobs?receipt_kind=stark",
+                    &serde_json::to_vec(&signed_game_input(3)).unwrap()
+                )
+                .status,
+            400
+        );
+        assert_eq!(
+            service
+                .route(
+                    "POST",
+                    "/jobs",
+                    &serde_json::to_vec(&default_game_input(4)).unwrap()
+                )
+                .status,
+            400
+        );
+    }
+
+    #[test]
+    fn cancelling_queued_job_removes_it_from_queue() {
+        let service = ProvingService::new(&options("cancel", 4)).unwrap();
+        submit(&service, 7);
+
+        let response = service.route("DELETE", "/jobs/job-000001", &[]);
+        assert_eq!(field(&response, "status"), "cancelled");
+        assert!(service.store.lock().unwrap().start_next().is_none());
+        assert_eq!(service.route("DELETE", "/jobs/job-404", &[]).status, 404);
+    }
+
+    #[test]
+    fn restart_requeues_interrupted_jobs() {
+        let opts = options("restart", 4);
+        {
+            let service = ProvingService::new(&opts).unwrap();
+            submit(&service, 10);
+            submit(&service, 11);
+            // Simulate a crash while job 1 is running.
+            service.store.lock().unwrap().start_next().unwrap();
+        }
+
+        let service = ProvingService::new(&opts).unwrap();
+        let mut store = service.store.lock().unwrap();
+        assert_eq!(store.queue, ["job-000001", "job-000002"]);
+        assert_eq!(store.jobs["job-000001"].status, JobStatus::Queued);
+        assert_eq!(store.next_id, 3);
+
+        let (record, _) = store.start_next().unwrap();
+        store.cancel(&record.job_id).unwrap().unwrap();
+        store.finish(&record.job_id, Ok(()));
+        assert_eq!(store.jobs["job-000001"].status, JobStatus::Cancelled);
+    }
+
+    #[test]
+    fn cancelling_running_job_says_it_keeps_proving() {
+        let service = ProvingService::new(&options("cancel-running", 4)).unwrap();
+        submit(&service, 8);
+        service.store.lock().unwrap().start_next().unwrap();
+
+        let response = service.route("DELETE", "/jobs/job-000001", &[]);
+        assert_eq!(response.status, 202);
+        assert_eq!(field(&response, "status"), "running");
+        assert_eq!(field(&response, "cancel_requested"), true);
+        assert!(field(&response, "note").is_string());
+    }
+
+    #[test]
+    fn prover_panic_fails_the_job() {
+        let service = ProvingService::new(&options("panic", 4)).unwrap();
+        submit(&service, 9);
+        let (record, _) = service.store.lock().unwrap().start_next().unwrap();
+
+        let result = catch_panic(|| panic!("backend exploded"));
+        assert_eq!(
+            result,
+            Err(String::from("prover panicked: backend exploded"))
+        );
+        service.store.lock().unwrap().finish(&record.job_id, result);
+
+        let status = service.route("GET", "/jobs/job-000001", &[]);
+        assert_eq!(field(&status, "status"), "failed");
+    }
+
+    #[test]
+    fn only_listed_origins_are_allowed() {
+        let service = ProvingService::new(&options("origins", 4)).unwrap();
+        assert!(service.allows_origin(DEFAULT_ALLOWED_ORIGIN));
+        assert!(!service.allows_origin("https://evil.example"));
+    }
+}
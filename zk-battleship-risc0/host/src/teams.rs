@@ -0,0 +1,74 @@
+//! `teams`: proves a 2v2 match and writes the `submit_team_result` bundle.
+
+use crate::free_for_all::MatchOptions;
+use battleship_prover::{
+    load_team_input, prove_teams, team_image_id_hex, write_json, ProveOptions, ProverError,
+};
+
+pub const DEFAULT_PROOF: &str = "team-proof.json";
+pub const DEFAULT_RECEIPT: &str = "team-receipt.bin";
+pub const DEFAULT_BUNDLE: &str = "team-bundle.json";
+
+pub fn run(options: &MatchOptions) -> Result<(), ProverError> {
+    let input = load_team_input(&options.input_path)?;
+    println!("session_id: {}", input.session_id);
+    println!("receipt kind requested: {}", options.receipt_kind.as_str());
+
+    let opts = ProveOptions {
+        receipt_kind: options.receipt_kind,
+    };
+    let artifact = prove_teams(&input, &opts)?;
+    let output = &artifact.output.public_output;
+
+    println!("mode: {}", output.mode.as_str());
+    if output.winner == 0 {
+        println!("winner: none yet, the match is unfinished");
+    } else {
+        println!(
+            "winner: team {} ({})",
+            output.winner,
+            output.outcome.as_str()
+        );
+    }
+    println!("total_moves: {}", output.total_moves);
+    println!("transcript_hash: {}", output.transcript_hash);
+    for (team, (stats, board_hash)) in output.stats.iter().zip(&output.board_hashes).enumerate() {
+        println!(
+            "team {} (P{} and P{}): {} ships sunk, {} hits, {} misses, board {}",
+            team + 1,
+            team + 1,
+            team + 3,
+            stats.ships_sunk,
+            stats.hits,
+            stats.misses,
+            board_hash
+        );
+    }
+    println!("{}", artifact.stats);
+
+    artifact.write_receipt(&options.receipt_out_path)?;
+    println!("receipt saved: {}", options.receipt_out_path);
+    artifact.write_output(&options.proof_out_path)?;
+    println!("proof output saved: {}", options.proof_out_path);
+
+    if output.winner == 0 {
+        println!("no submission bundle: submit_team_result needs a finished match");
+        return Ok(());
+    }
+    if output.player_keys.is_none() {
+        println!("no submission bundle: submit_team_result only settles signed matches");
+        return Ok(());
+    }
+    let bundle = artifact.submission(options.submitter.as_deref())?;
+    write_json(&bundle, &options.bundle_out_path)?;
+    println!("submission bundle saved: {}", options.bundle_out_path);
+    if bundle.seal_hex.is_none() {
+        println!("submission bundle has no seal; the contract needs a --receipt-kind groth16 proof outside dev mode.");
+    }
+    println!("submit command: {}", bundle.stellar_invoke_command);
+    println!(
+        "the contract accepts it once its team image id is {} (set_team_image_id)",
+        team_image_id_hex()
+    );
+    Ok(())
+}
@@ -0,0 +1,125 @@
+//! `transcript`: recomputes a game's move hash chain so the moves behind a proof can be
+//! audited.
+
+use battleship_prover::{load_game_input, transcript, Move, ProofOutputFile, PublicOutputJson};
+use std::fs;
+
+pub struct TranscriptOptions {
+    pub input_path: String,
+    /// Artifact whose committed `transcript_hash` the input must reproduce.
+    pub proof_path: Option<String>,
+}
+
+fn describe(mv: &Move) -> String {
+    let mut text = format!("P{} {:?} ({}, {})", mv.player, mv.action, mv.x, mv.y);
+    for shot in &mv.extra_shots {
+        text.push_str(&format!(" ({}, {})", shot.x, shot.y));
+    }
+    text
+}
+
+/// Checks that `output` commits to `session_id` and `transcript_hash`.
+fn check(session_id: u32, transcript_hash: &str, output: &PublicOutputJson) -> Vec<String> {
+    let mut problems = Vec::new();
+    if output.session_id != session_id {
+        problems.push(format!(
+            "session_id differs: input {session_id}, proof {}",
+            output.session_id
+        ));
+    }
+    if output.transcript_hash.is_empty() {
+        problems.push(String::from(
+            "transcript_hash is missing (artifact predates transcript hashes)",
+        ));
+    } else if output.transcript_hash != transcript_hash {
+        problems.push(format!(
+            "transcript_hash differs: proof commits {}",
+            output.transcript_hash
+        ));
+    }
+    problems
+}
+
+fn load_artifact(path: &str) -> Result<ProofOutputFile, String> {
+    let raw =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read artifact '{}': {e}", path))?;
+    serde_json::from_str(&raw).map_err(|e| format!("Failed to parse artifact '{}': {e}", path))
+}
+
+/// Prints the hash after every move and the transcript hash, and compares it with the
+/// artifact when one is given. Returns the process exit code.
+pub fn run(options: &TranscriptOptions) -> i32 {
+    let input = match load_game_input(&options.input_path) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            return 2;
+        }
+    };
+
+    let hashes = transcript::move_hashes(input.session_id, &input.moves);
+    println!("session_id: {}", input.session_id);
+    for (index, (mv, hash)) in input.moves.iter().zip(&hashes).enumerate() {
+        println!("{index:>4}  {}  {}", hex::encode(hash), describe(mv));
+    }
+    let transcript_hash = hex::encode(transcript::transcript_hash(input.session_id, &input.moves));
+    println!("transcript_hash: {transcript_hash}");
+
+    let Some(path) = &options.proof_path else {
+        return 0;
+    };
+    let artifact = match load_artifact(path) {
+        Ok(artifact) => artifact,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            return 2;
+        }
+    };
+    let problems = check(input.session_id, &transcript_hash, &artifact.public_output);
+    if problems.is_empty() {
+        println!("matches: {path}");
+        return 0;
+    }
+    for problem in &problems {
+        println!("check failed: {problem}");
+    }
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use battleship_prover::default_game_input;
+    use serde_json::json;
+
+    fn output(session_id: u32, transcript_hash: &str) -> PublicOutputJson {
+        serde_json::from_value(json!({
+            "session_id": session_id,
+            "winner": 1,
+            "board_hash_p1": "",
+            "board_hash_p2": "",
+            "transcript_hash": transcript_hash,
+            "total_moves": 7,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn check_accepts_only_the_recomputed_hash() {
+        let input = default_game_input(42);
+        let hash = hex::encode(transcript::transcript_hash(42, &input.moves));
+        assert!(check(42, &hash, &output(42, &hash)).is_empty());
+
+        let mut edited = input.moves.clone();
+        edited[6].x = 3;
+        let other = hex::encode(transcript::transcript_hash(42, &edited));
+        assert_eq!(check(42, &other, &output(42, &hash)).len(), 1);
+        assert_eq!(check(43, &hash, &output(42, &hash)).len(), 1);
+    }
+
+    #[test]
+    fn artifacts_without_a_transcript_hash_are_flagged() {
+        let problems = check(42, "00", &output(42, ""));
+        assert!(problems[0].contains("predates"));
+    }
+}
@@ -0,0 +1,98 @@
+//! `prove-turns`: proves every move of a game that has no turn receipt yet, so a match can
+//! be proven while it is played and `aggregate` only has to fold the receipts at the end.
+
+use battleship_prover::turn::{turn, MatchState};
+use battleship_prover::{
+    check_turn_receipt, load_game_input, prove_turn, read_turn_receipt, write_turn_receipt,
+    GameInput, ProverError, Receipt,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+pub const DEFAULT_TURNS_DIR: &str = "turns";
+
+pub struct TurnsOptions {
+    pub input_path: String,
+    pub turns_dir: PathBuf,
+}
+
+fn turn_path(dir: &Path, index: u32) -> PathBuf {
+    dir.join(format!("turn-{index:04}.bin"))
+}
+
+/// Returns one turn receipt per move of `input`, reusing the receipts in `dir` that still
+/// prove the same move from the same state and proving (and storing) the others.
+pub fn update(input: &GameInput, dir: &Path) -> Result<Vec<Receipt>, ProverError> {
+    fs::create_dir_all(dir).map_err(|e| {
+        ProverError::Io(format!(
+            "failed to create turns dir '{}': {e}",
+            dir.display()
+        ))
+    })?;
+
+    let mut state = MatchState::new(input).map_err(|e| ProverError::Input(e.to_string()))?;
+    let mut receipts = Vec::with_capacity(input.moves.len());
+    for mv in &input.moves {
+        let index = state.moves_applied();
+        let path = turn_path(dir, index);
+        let mut next = state.clone();
+        let expected =
+            turn(&mut next, mv).map_err(|e| ProverError::Input(format!("move {index}: {e}")))?;
+
+        let stored = read_turn_receipt(&path)
+            .ok()
+            .filter(|receipt| check_turn_receipt(receipt, &expected).is_ok());
+        let receipt = match stored {
+            Some(receipt) => {
+                println!("turn {index}: up to date ({})", path.display());
+                receipt
+            }
+            None => {
+                let started = Instant::now();
+                let (receipt, _) = prove_turn(&state, mv)?;
+                write_turn_receipt(&receipt, &path.to_string_lossy())?;
+                println!(
+                    "turn {index}: proven in {} ms ({})",
+                    started.elapsed().as_millis(),
+                    path.display()
+                );
+                receipt
+            }
+        };
+        receipts.push(receipt);
+        state = next;
+    }
+    Ok(receipts)
+}
+
+pub fn run(options: &TurnsOptions) -> Result<(), ProverError> {
+    let input = load_game_input(&options.input_path)?;
+    let receipts = update(&input, &options.turns_dir)?;
+    println!(
+        "{} turn receipts in {}; fold them with: cargo run -- aggregate --input {} --turns {}",
+        receipts.len(),
+        options.turns_dir.display(),
+        options.input_path,
+        options.turns_dir.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use battleship_prover::default_game_input;
+
+    #[test]
+    fn illegal_moves_fail_before_proving() {
+        let dir = std::env::temp_dir().join(format!("battleship-turns-{}", std::process::id()));
+        let mut input = default_game_input(42);
+        input.moves[0].player = 2;
+
+        let err = update(&input, &dir).unwrap_err();
+        assert!(matches!(err, ProverError::Input(_)));
+        assert_eq!(err.to_string(), "move 0: invalid turn order");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
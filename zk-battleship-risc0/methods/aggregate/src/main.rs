@@ -0,0 +1,23 @@
+use battleship_rules::turn::{aggregate, MatchState, TurnJournal};
+use battleship_rules::GameInput;
+use risc0_zkvm::guest::env;
+use risc0_zkvm::serde::to_vec;
+use turn_methods::TURN_ID;
+
+fn main() {
+    let setup: GameInput = env::read();
+    let turns: Vec<TurnJournal> = env::read();
+    let last: MatchState = env::read();
+
+    for turn in &turns {
+        let journal = to_vec(turn).expect("turn journal encodes");
+        env::verify(TURN_ID, &journal).expect("turn receipt verifies");
+    }
+
+    let output = match aggregate(&setup, &turns, &last) {
+        Ok(output) => output,
+        Err(err) => panic!("{}", err),
+    };
+
+    env::commit(&output);
+}
@@ -0,0 +1,13 @@
+use battleship_rules::free_for_all::{play_free_for_all, FreeForAllInput};
+use risc0_zkvm::guest::env;
+
+fn main() {
+    let input: FreeForAllInput = env::read();
+
+    let output = match play_free_for_all(&input) {
+        Ok(output) => output,
+        Err(err) => panic!("{}", err),
+    };
+
+    env::commit(&output);
+}
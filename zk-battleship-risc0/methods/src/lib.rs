@@ -1 +1,4 @@
 include!(concat!(env!("OUT_DIR"), "/methods.rs"));
+
+/// The turn guest lives in its own crate so the aggregation guest can embed `TURN_ID`.
+pub use turn_methods::{TURN_ELF, TURN_ID};
@@ -0,0 +1,13 @@
+use battleship_rules::teams::{play_teams, TeamInput};
+use risc0_zkvm::guest::env;
+
+fn main() {
+    let input: TeamInput = env::read();
+
+    let output = match play_teams(&input) {
+        Ok(output) => output,
+        Err(err) => panic!("{}", err),
+    };
+
+    env::commit(&output);
+}
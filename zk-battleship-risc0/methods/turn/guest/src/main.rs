@@ -0,0 +1,15 @@
+use battleship_rules::turn::{turn, MatchState};
+use battleship_rules::Move;
+use risc0_zkvm::guest::env;
+
+fn main() {
+    let mut state: MatchState = env::read();
+    let mv: Move = env::read();
+
+    let journal = match turn(&mut state, &mv) {
+        Ok(journal) => journal,
+        Err(err) => panic!("{}", err),
+    };
+
+    env::commit(&journal);
+}
@@ -0,0 +1,408 @@
+//! Content-addressed proof cache.
+//!
+//! Each entry lives in `<root>/<key>/` and holds the proven `input.json`, `receipt.bin`,
+//! `proof-output.json` and an `entry.json` summary. The key is
+//! sha256(canonical `GameInput` JSON || `METHOD_ID` || receipt kind), so a guest change or a
+//! different receipt kind never returns a stale proof.
+
+use crate::{
+    image_id_hex, input_hash, prove, verify_artifact, write_json, GameInput, ProofArtifact,
+    ProofOutputFile, ProveOptions, ProverError, PublicOutput, ReceiptKind, METHOD_ID,
+};
+use risc0_zkvm::{sha::Digest, Receipt};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const INPUT_FILE: &str = "input.json";
+const RECEIPT_FILE: &str = "receipt.bin";
+const OUTPUT_FILE: &str = "proof-output.json";
+const ENTRY_FILE: &str = "entry.json";
+
+/// Summary of a cache entry, stored as `entry.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CacheEntry {
+    pub key: String,
+    pub session_id: u32,
+    pub receipt_kind: String,
+    pub image_id_hex: String,
+    pub input_hash_hex: String,
+    /// Unix seconds.
+    pub created_at: u64,
+    pub size_bytes: u64,
+}
+
+/// Whether [`ProofCache::prove`] reused a stored proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheLookup {
+    Hit,
+    Miss,
+}
+
+/// Selects the entries [`ProofCache::prune`] removes. Criteria are combined with "or".
+#[derive(Debug, Clone, Default)]
+pub struct PruneOptions {
+    /// Remove entries built for another `METHOD_ID`, and unreadable entries.
+    pub stale: bool,
+    /// Remove entries created longer ago than this.
+    pub older_than: Option<Duration>,
+    /// Remove every entry.
+    pub all: bool,
+}
+
+/// Cache key for proving `input` into a `kind` receipt with this build's guest.
+pub fn cache_key(input: &GameInput, kind: ReceiptKind) -> Result<String, ProverError> {
+    let mut hasher = Sha256::new();
+    hasher.update(input_hash(input)?);
+    hasher.update(Digest::from(METHOD_ID).as_bytes());
+    hasher.update(kind.as_str().as_bytes());
+    Ok(hex::encode(hasher.finalize()))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn is_key(name: &str) -> bool {
+    name.len() == 64 && name.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn io_error(action: &str, path: &Path, err: impl std::fmt::Display) -> ProverError {
+    ProverError::Io(format!("failed to {action} '{}': {err}", path.display()))
+}
+
+pub struct ProofCache {
+    root: PathBuf,
+}
+
+impl ProofCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn entry_dir(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+
+    /// Loads an entry's artifact without verifying it.
+    fn load(&self, key: &str) -> Result<ProofArtifact, ProverError> {
+        let dir = self.entry_dir(key);
+
+        let receipt_path = dir.join(RECEIPT_FILE);
+        let bytes = fs::read(&receipt_path).map_err(|e| io_error("read", &receipt_path, e))?;
+        let receipt: Receipt = bincode::deserialize(&bytes)
+            .map_err(|e| io_error("decode receipt", &receipt_path, e))?;
+
+        let output_path = dir.join(OUTPUT_FILE);
+        let raw =
+            fs::read_to_string(&output_path).map_err(|e| io_error("read", &output_path, e))?;
+        let output: ProofOutputFile =
+            serde_json::from_str(&raw).map_err(|e| io_error("parse", &output_path, e))?;
+
+        Ok(ProofArtifact::new(receipt, output))
+    }
+
+    fn load_input(&self, key: &str) -> Result<GameInput, ProverError> {
+        let path = self.entry_dir(key).join(INPUT_FILE);
+        let raw = fs::read_to_string(&path).map_err(|e| io_error("read", &path, e))?;
+        serde_json::from_str(&raw).map_err(|e| io_error("parse", &path, e))
+    }
+
+    fn load_entry(&self, key: &str) -> Result<CacheEntry, ProverError> {
+        let path = self.entry_dir(key).join(ENTRY_FILE);
+        let raw = fs::read_to_string(&path).map_err(|e| io_error("read", &path, e))?;
+        serde_json::from_str(&raw).map_err(|e| io_error("parse", &path, e))
+    }
+
+    /// Returns the stored proof for `input`, or `None` when there is no usable entry.
+    ///
+    /// Entries are re-verified on every hit; one that no longer verifies is treated as a miss.
+    pub fn get(
+        &self,
+        input: &GameInput,
+        kind: ReceiptKind,
+    ) -> Result<Option<ProofArtifact>, ProverError> {
+        let key = cache_key(input, kind)?;
+        if !self.entry_dir(&key).is_dir() {
+            return Ok(None);
+        }
+
+        match self.load(&key) {
+            Ok(artifact) if artifact.output.receipt_kind == kind.as_str() => {
+                Ok(verify_artifact(&artifact).ok().map(|_| artifact))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Stores a proof for `input`. Dev-mode fake receipts are never cached.
+    ///
+    /// The entry is written to a temp directory and renamed into place, so readers never
+    /// see a partial entry.
+    pub fn put(
+        &self,
+        input: &GameInput,
+        kind: ReceiptKind,
+        artifact: &ProofArtifact,
+    ) -> Result<(), ProverError> {
+        if artifact.output.receipt_kind != kind.as_str() {
+            return Ok(());
+        }
+
+        let key = cache_key(input, kind)?;
+        fs::create_dir_all(&self.root).map_err(|e| io_error("create", &self.root, e))?;
+
+        let temp = self.root.join(format!(".{key}.tmp-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&temp);
+        fs::create_dir(&temp).map_err(|e| io_error("create", &temp, e))?;
+
+        let result = (|| {
+            write_json(input, &temp.join(INPUT_FILE).to_string_lossy())?;
+            artifact.write_receipt(&temp.join(RECEIPT_FILE).to_string_lossy())?;
+            artifact.write_output(&temp.join(OUTPUT_FILE).to_string_lossy())?;
+
+            let size_bytes = [INPUT_FILE, RECEIPT_FILE, OUTPUT_FILE]
+                .iter()
+                .filter_map(|name| fs::metadata(temp.join(name)).ok())
+                .map(|meta| meta.len())
+                .sum();
+            let entry = CacheEntry {
+                key: key.clone(),
+                session_id: input.session_id,
+                receipt_kind: kind.as_str().to_string(),
+                image_id_hex: image_id_hex(),
+                input_hash_hex: artifact.output.input_hash_hex.clone(),
+                created_at: now(),
+                size_bytes,
+            };
+            write_json(&entry, &temp.join(ENTRY_FILE).to_string_lossy())?;
+
+            let target = self.entry_dir(&key);
+            let _ = fs::remove_dir_all(&target);
+            fs::rename(&temp, &target).map_err(|e| io_error("publish", &target, e))
+        })();
+
+        if result.is_err() {
+            let _ = fs::remove_dir_all(&temp);
+        }
+        result
+    }
+
+    /// Returns the cached proof for `input`, proving and storing it on a miss.
+    pub fn prove(
+        &self,
+        input: &GameInput,
+        opts: &ProveOptions,
+    ) -> Result<(ProofArtifact, CacheLookup), ProverError> {
+        if let Some(artifact) = self.get(input, opts.receipt_kind)? {
+            return Ok((artifact, CacheLookup::Hit));
+        }
+
+        let artifact = prove(input, opts)?;
+        self.put(input, opts.receipt_kind, &artifact)?;
+        Ok((artifact, CacheLookup::Miss))
+    }
+
+    /// Keys of every entry directory, readable or not.
+    pub fn keys(&self) -> Result<Vec<String>, ProverError> {
+        let dir = match fs::read_dir(&self.root) {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(io_error("read", &self.root, e)),
+        };
+
+        let mut keys: Vec<String> = dir
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .filter(|name| is_key(name))
+            .collect();
+        keys.sort();
+        Ok(keys)
+    }
+
+    /// Lists readable entries, oldest first.
+    pub fn list(&self) -> Result<Vec<CacheEntry>, ProverError> {
+        let mut entries: Vec<CacheEntry> = self
+            .keys()?
+            .iter()
+            .filter_map(|key| self.load_entry(key).ok())
+            .collect();
+        entries.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.key.cmp(&b.key)));
+        Ok(entries)
+    }
+
+    /// Checks one entry: the receipt verifies, the artifact matches its journal, and the
+    /// stored input still hashes to the entry's key.
+    pub fn verify(&self, key: &str) -> Result<PublicOutput, ProverError> {
+        if !is_key(key) || !self.entry_dir(key).is_dir() {
+            return Err(ProverError::Input(format!("no cache entry '{key}'")));
+        }
+
+        let entry = self.load_entry(key)?;
+        let kind: ReceiptKind = entry
+            .receipt_kind
+            .parse()
+            .map_err(|e| ProverError::Verify(format!("entry.json: {e}")))?;
+        let input = self.load_input(key)?;
+        if cache_key(&input, kind)? != key {
+            return Err(ProverError::Verify(String::from(
+                "stored input does not hash to the entry key (stale METHOD_ID or edited input)",
+            )));
+        }
+
+        let artifact = self.load(key)?;
+        if artifact.output.input_hash_hex != hex::encode(input_hash(&input)?) {
+            return Err(ProverError::Verify(String::from(
+                "input_hash_hex does not match the stored input",
+            )));
+        }
+        verify_artifact(&artifact)
+    }
+
+    /// Removes the entries selected by `options` and returns their keys.
+    pub fn prune(&self, options: &PruneOptions) -> Result<Vec<String>, ProverError> {
+        let current_image_id = image_id_hex();
+        let cutoff = options
+            .older_than
+            .map(|age| now().saturating_sub(age.as_secs()));
+
+        let mut removed = Vec::new();
+        for key in self.keys()? {
+            let remove = options.all
+                || match self.load_entry(&key) {
+                    Ok(entry) => {
+                        (options.stale && entry.image_id_hex != current_image_id)
+                            || cutoff.is_some_and(|cutoff| entry.created_at < cutoff)
+                    }
+                    Err(_) => options.stale,
+                };
+            if remove {
+                let dir = self.entry_dir(&key);
+                fs::remove_dir_all(&dir).map_err(|e| io_error("remove", &dir, e))?;
+                removed.push(key);
+            }
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default_game_input;
+
+    fn scratch_cache(name: &str) -> ProofCache {
+        let dir = std::env::temp_dir().join(format!("prover-cache-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        ProofCache::new(dir)
+    }
+
+    fn write_entry(cache: &ProofCache, key: &str, image_id_hex: &str, created_at: u64) {
+        let dir = cache.entry_dir(key);
+        fs::create_dir_all(&dir).unwrap();
+        let entry = CacheEntry {
+            key: key.to_string(),
+            session_id: 42,
+            receipt_kind: String::from("groth16"),
+            image_id_hex: image_id_hex.to_string(),
+            input_hash_hex: String::new(),
+            created_at,
+            size_bytes: 0,
+        };
+        fs::write(dir.join(ENTRY_FILE), serde_json::to_vec(&entry).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn key_depends_on_input_and_receipt_kind() {
+        let input = default_game_input(42);
+        let key = cache_key(&input, ReceiptKind::Groth16).unwrap();
+        assert!(is_key(&key));
+        assert_eq!(
+            key,
+            cache_key(&input.clone(), ReceiptKind::Groth16).unwrap()
+        );
+        assert_ne!(key, cache_key(&input, ReceiptKind::Succinct).unwrap());
+
+        let mut other = input.clone();
+        other.moves[0].x = 3;
+        assert_ne!(key, cache_key(&other, ReceiptKind::Groth16).unwrap());
+        assert_ne!(
+            key,
+            cache_key(&default_game_input(43), ReceiptKind::Groth16).unwrap()
+        );
+    }
+
+    #[test]
+    fn missing_cache_lists_nothing_and_misses() {
+        let cache = ProofCache::new(std::env::temp_dir().join("prover-cache-does-not-exist"));
+        assert!(cache.list().unwrap().is_empty());
+        assert!(cache
+            .get(&default_game_input(42), ReceiptKind::Groth16)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn prune_removes_stale_old_and_unreadable_entries() {
+        let cache = scratch_cache("prune");
+        let current = image_id_hex();
+        let fresh = "a".repeat(64);
+        let stale = "b".repeat(64);
+        let old = "c".repeat(64);
+        let broken = "d".repeat(64);
+        write_entry(&cache, &fresh, &current, now());
+        write_entry(&cache, &stale, &"ff".repeat(32), now());
+        write_entry(&cache, &old, &current, now() - 10 * 86_400);
+        fs::create_dir_all(cache.entry_dir(&broken)).unwrap();
+        fs::create_dir_all(cache.root().join("not-a-key")).unwrap();
+
+        let listed: Vec<String> = cache.list().unwrap().into_iter().map(|e| e.key).collect();
+        assert_eq!(listed, [old.clone(), fresh.clone(), stale.clone()]);
+
+        let removed = cache
+            .prune(&PruneOptions {
+                stale: true,
+                ..PruneOptions::default()
+            })
+            .unwrap();
+        assert_eq!(removed, [stale, broken]);
+
+        let removed = cache
+            .prune(&PruneOptions {
+                older_than: Some(Duration::from_secs(86_400)),
+                ..PruneOptions::default()
+            })
+            .unwrap();
+        assert_eq!(removed, [old]);
+
+        let removed = cache
+            .prune(&PruneOptions {
+                all: true,
+                ..PruneOptions::default()
+            })
+            .unwrap();
+        assert_eq!(removed, [fresh]);
+        assert!(cache.root().join("not-a-key").is_dir());
+    }
+
+    #[test]
+    fn verify_rejects_unknown_keys() {
+        let cache = scratch_cache("verify");
+        let err = cache.verify("not-a-key").expect_err("expected unknown key");
+        assert!(
+            matches!(err, ProverError::Input(_)),
+            "unexpected error: {err}"
+        );
+    }
+}
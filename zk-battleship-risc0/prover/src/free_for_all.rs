@@ -0,0 +1,230 @@
+//! Free-for-all proving: the free-for-all guest replays a three- or four-player match (see
+//! [`battleship_rules::free_for_all`]) and commits its ranking, which
+//! `submit_free_for_all_result` settles as one Game Hub session per pair of players.
+
+use crate::submission::{self, FreeForAllSubmission};
+use crate::{
+    compress_receipt, elapsed_ms, groth16_seal, journal_digest, peak_memory_bytes, receipt_kind,
+    run_guest, transcript, write_atomic, write_json, GameMode, Move, Outcome, PlayerStats,
+    ProofStats, ProveOptions, ProverError, ReceiptKind, FREE_FOR_ALL_ELF, FREE_FOR_ALL_ID,
+    RISC0_VERSION,
+};
+use battleship_rules::free_for_all::{FreeForAllInput, FreeForAllOutput, Seat};
+use risc0_zkvm::{sha::Digest, ExecutorEnv, Receipt};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+/// Secret keys the free-for-all sample is signed with: the two of
+/// [`SAMPLE_SECRET_KEYS`](crate::SAMPLE_SECRET_KEYS) and a third. Public test values.
+pub const FREE_FOR_ALL_SECRET_KEYS: [[u8; 32]; 3] = [[1; 32], [2; 32], [3; 32]];
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FreeForAllOutputJson {
+    pub session_id: u32,
+    pub players: u8,
+    pub winner: u8,
+    pub outcome: Outcome,
+    pub ranking: Vec<u8>,
+    pub board_hashes: Vec<String>,
+    pub transcript_hash: String,
+    pub total_moves: u32,
+    pub mode: GameMode,
+    pub no_touching: bool,
+    pub stats: Vec<PlayerStats>,
+    /// Hex ed25519 keys in seat order for a signed match.
+    pub player_keys: Option<Vec<String>>,
+}
+
+impl From<&FreeForAllOutput> for FreeForAllOutputJson {
+    fn from(output: &FreeForAllOutput) -> Self {
+        Self {
+            session_id: output.session_id,
+            players: output.players,
+            winner: output.winner,
+            outcome: output.outcome,
+            ranking: output.ranking.clone(),
+            board_hashes: output.board_hashes.iter().map(hex::encode).collect(),
+            transcript_hash: hex::encode(output.transcript_hash),
+            total_moves: output.total_moves,
+            mode: output.mode,
+            no_touching: output.no_touching,
+            stats: output.stats.clone(),
+            player_keys: output
+                .player_keys
+                .as_ref()
+                .map(|keys| keys.iter().map(hex::encode).collect()),
+        }
+    }
+}
+
+/// Contents of the free-for-all `proof-output.json`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FreeForAllProofFile {
+    pub journal_hex: String,
+    pub seal_hex: Option<String>,
+    pub public_output: FreeForAllOutputJson,
+    pub image_id_hex: String,
+    /// sha256(journal), the digest `submit_free_for_all_result` passes to the verifier.
+    pub journal_digest_hex: String,
+    pub receipt_kind: String,
+    pub risc0_version: String,
+    pub host_version: String,
+    /// sha256 of the compact JSON encoding of the proven `FreeForAllInput`.
+    pub input_hash_hex: String,
+}
+
+/// A proven free-for-all: the receipt plus everything derived from it.
+#[derive(Debug)]
+pub struct FreeForAllArtifact {
+    pub receipt: Receipt,
+    pub output: FreeForAllProofFile,
+    /// Raw Groth16 seal, present only for real Groth16 receipts.
+    pub groth16_seal: Option<Vec<u8>>,
+    pub selector: Option<[u8; 4]>,
+    pub stats: ProofStats,
+}
+
+impl FreeForAllArtifact {
+    /// Builds the `submit_free_for_all_result` arguments for this proof.
+    pub fn submission(&self, submitter: Option<&str>) -> Result<FreeForAllSubmission, ProverError> {
+        submission::build_free_for_all_submission(
+            &self.output.public_output,
+            &self.receipt.journal.bytes,
+            self.groth16_seal.as_deref(),
+            self.selector,
+            Digest::from(FREE_FOR_ALL_ID).as_bytes(),
+            submitter,
+        )
+        .map_err(ProverError::Bundle)
+    }
+
+    pub fn write_receipt(&self, path: &str) -> Result<(), ProverError> {
+        let bytes = bincode::serialize(&self.receipt)
+            .map_err(|e| ProverError::Io(format!("failed to serialize receipt: {e}")))?;
+        write_atomic(path, &bytes).map_err(ProverError::Io)
+    }
+
+    pub fn write_output(&self, path: &str) -> Result<(), ProverError> {
+        write_json(&self.output, path)
+    }
+}
+
+/// Hex of the free-for-all guest's image id, as stored by `set_free_for_all_image_id`.
+pub fn free_for_all_image_id_hex() -> String {
+    hex::encode(Digest::from(FREE_FOR_ALL_ID).as_bytes())
+}
+
+/// Built-in three-player match (`game-input.free-for-all.example.json`), signed with
+/// [`FREE_FOR_ALL_SECRET_KEYS`]: every seat has the sample board, P1 and P2 sink P3
+/// together, then P1 sinks P2. 12 moves, ranking P1, P2, P3.
+pub fn free_for_all_game_input(session_id: u32) -> FreeForAllInput {
+    let seat = Seat {
+        board: [
+            1, 1, 0, 0, // y=0 (ship size 2)
+            0, 0, 0, 0, // y=1
+            1, 1, 0, 0, // y=2 (ship size 2)
+            0, 0, 0, 0, // y=3
+        ],
+        fleet: Vec::new(),
+        board_salt: [0; 32],
+    };
+    let mut moves = vec![
+        Move::at(1, 3, 0, 0),
+        Move::at(2, 3, 1, 0),
+        Move::at(3, 1, 3, 3),
+        Move::at(1, 3, 0, 2),
+        Move::at(2, 3, 1, 2), // P3 is out
+        Move::at(1, 2, 0, 0),
+        Move::at(2, 1, 3, 2),
+        Move::at(1, 2, 1, 0),
+        Move::at(2, 1, 2, 2),
+        Move::at(1, 2, 0, 2),
+        Move::at(2, 1, 2, 3),
+        Move::at(1, 2, 1, 2),
+    ];
+    transcript::sign_moves(session_id, &mut moves, &FREE_FOR_ALL_SECRET_KEYS);
+    FreeForAllInput {
+        session_id,
+        mode: GameMode::Demo,
+        no_touching: false,
+        seats: vec![seat; 3],
+        player_keys: Some(
+            FREE_FOR_ALL_SECRET_KEYS
+                .iter()
+                .map(transcript::public_key)
+                .collect(),
+        ),
+        moves,
+    }
+}
+
+pub fn load_free_for_all_input(path: impl AsRef<Path>) -> Result<FreeForAllInput, ProverError> {
+    let path = path.as_ref();
+    let raw = fs::read_to_string(path).map_err(|e| {
+        ProverError::Input(format!(
+            "Failed to read input file '{}': {e}",
+            path.display()
+        ))
+    })?;
+    serde_json::from_str::<FreeForAllInput>(&raw).map_err(|e| {
+        ProverError::Input(format!("Failed to parse JSON in '{}': {e}", path.display()))
+    })
+}
+
+/// Proves a free-for-all and packages the result.
+pub fn prove_free_for_all(
+    input: &FreeForAllInput,
+    opts: &ProveOptions,
+) -> Result<FreeForAllArtifact, ProverError> {
+    if input.session_id == 0 {
+        return Err(ProverError::Input(String::from(
+            "game input session_id must be greater than 0",
+        )));
+    }
+    let canonical = serde_json::to_vec(input)
+        .map_err(|e| ProverError::Input(format!("failed to encode game input: {e}")))?;
+    let input_hash_hex = hex::encode(Sha256::digest(&canonical));
+
+    let env = || {
+        ExecutorEnv::builder()
+            .write(input)
+            .map_err(|e| ProverError::Env(format!("failed to write executor input: {e}")))?
+            .build()
+            .map_err(|e| ProverError::Env(format!("failed to build executor env: {e}")))
+    };
+    let mut stats = ProofStats::default();
+    let (receipt, public_output): (Receipt, FreeForAllOutput) =
+        run_guest(env, FREE_FOR_ALL_ELF, FREE_FOR_ALL_ID, &mut stats)?;
+
+    let started = Instant::now();
+    let receipt = compress_receipt(receipt, opts.receipt_kind)?;
+    if opts.receipt_kind != ReceiptKind::Composite {
+        stats.compress_ms = elapsed_ms(started);
+    }
+    stats.peak_memory_bytes = peak_memory_bytes();
+
+    let (groth16_seal, selector) = groth16_seal(&receipt);
+    let journal = &receipt.journal.bytes;
+    let output = FreeForAllProofFile {
+        journal_hex: hex::encode(journal),
+        seal_hex: groth16_seal.as_deref().map(hex::encode),
+        public_output: FreeForAllOutputJson::from(&public_output),
+        image_id_hex: free_for_all_image_id_hex(),
+        journal_digest_hex: hex::encode(journal_digest(journal)),
+        receipt_kind: String::from(receipt_kind(&receipt)),
+        risc0_version: String::from(RISC0_VERSION),
+        host_version: String::from(env!("CARGO_PKG_VERSION")),
+        input_hash_hex,
+    };
+
+    Ok(FreeForAllArtifact {
+        receipt,
+        output,
+        groth16_seal,
+        selector,
+        stats,
+    })
+}
@@ -0,0 +1,1099 @@
+//! Proving library for the zk-battleship RISC0 guest.
+//!
+//! `prove` runs the guest over a [`GameInput`], compresses the receipt into the requested
+//! [`ReceiptKind`], and returns a [`ProofArtifact`] holding the receipt, the
+//! `proof-output.json` contents, and the seal material `submit_result` needs.
+//! [`prove_turn`] and [`aggregate`] prove the same journal one move at a time instead, and
+//! [`prove_free_for_all`] proves a three- or four-player match and [`prove_teams`] a 2v2
+//! match.
+
+mod cache;
+mod free_for_all;
+mod output;
+mod stats;
+mod submission;
+mod teams;
+mod turns;
+
+pub use battleship_rules::{
+    cell_maps, merkle, play, transcript, turn, Action, GameInput, GameMode, MineHit, MinePenalty,
+    Move, Orientation, Outcome, PlayerStats, PublicOutput, Ship, Shot, SonarPing, Weapons, MINE,
+};
+pub use battleship_rules::free_for_all::{FreeForAllInput, FreeForAllOutput, Seat};
+pub use battleship_rules::teams::{Member, Team, TeamInput, TeamOutput};
+pub use cache::{cache_key, CacheEntry, CacheLookup, ProofCache, PruneOptions};
+pub use free_for_all::{
+    free_for_all_game_input, free_for_all_image_id_hex, load_free_for_all_input,
+    prove_free_for_all, FreeForAllArtifact, FreeForAllOutputJson, FreeForAllProofFile,
+    FREE_FOR_ALL_SECRET_KEYS,
+};
+pub use methods::{
+    AGGREGATE_ELF, AGGREGATE_ID, FREE_FOR_ALL_ELF, FREE_FOR_ALL_ID, METHOD_ELF, METHOD_ID,
+    TEAMS_ELF, TEAMS_ID, TURN_ELF, TURN_ID,
+};
+pub use output::{output_target, validate_output_path, write_atomic};
+pub use risc0_zkvm::{Receipt, VERSION as RISC0_VERSION};
+pub use stats::{peak_memory_bytes, ProofStats};
+pub use submission::{
+    parse_submitter, FreeForAllSubmission, SubmissionArg, SubmissionBundle, TeamSubmission,
+};
+pub use teams::{
+    load_team_input, prove_teams, team_game_input, team_image_id_hex, TeamArtifact,
+    TeamOutputJson, TeamProofFile, TEAM_SECRET_KEYS,
+};
+pub use turns::{
+    aggregate, check_turn_receipt, prove_turn, read_turn_receipt, write_turn_receipt,
+};
+
+use risc0_zkvm::{
+    default_executor, default_prover, sha::Digest, ExecutorEnv, InnerReceipt, ProverOpts,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Instant;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PublicOutputJson {
+    pub session_id: u32,
+    pub winner: u8,
+    #[serde(default)]
+    pub outcome: Outcome,
+    pub board_hash_p1: String,
+    pub board_hash_p2: String,
+    /// Empty in artifacts from before the transcript hash was committed.
+    #[serde(default)]
+    pub transcript_hash: String,
+    pub total_moves: u32,
+    /// Defaults keep artifacts from before game modes loadable.
+    #[serde(default)]
+    pub mode: GameMode,
+    #[serde(default)]
+    pub no_touching: bool,
+    #[serde(default)]
+    pub salvo: bool,
+    #[serde(default)]
+    pub hit_keeps_turn: bool,
+    #[serde(default)]
+    pub weapons: Weapons,
+    #[serde(default)]
+    pub stats_p1: PlayerStats,
+    #[serde(default)]
+    pub stats_p2: PlayerStats,
+    #[serde(default)]
+    pub sonar: Vec<SonarPing>,
+    #[serde(default)]
+    pub mines: u8,
+    #[serde(default)]
+    pub mine_penalty: MinePenalty,
+    #[serde(default)]
+    pub mine_hits: Vec<MineHit>,
+    /// Hex ed25519 keys of P1 and P2 for a signed game.
+    #[serde(default)]
+    pub player_keys: Option<[String; 2]>,
+}
+
+impl From<&PublicOutput> for PublicOutputJson {
+    fn from(output: &PublicOutput) -> Self {
+        Self {
+            session_id: output.session_id,
+            winner: output.winner,
+            outcome: output.outcome,
+            board_hash_p1: hex::encode(output.board_hash_p1),
+            board_hash_p2: hex::encode(output.board_hash_p2),
+            transcript_hash: hex::encode(output.transcript_hash),
+            total_moves: output.total_moves,
+            mode: output.mode,
+            no_touching: output.no_touching,
+            salvo: output.salvo,
+            hit_keeps_turn: output.hit_keeps_turn,
+            weapons: output.weapons,
+            stats_p1: output.stats_p1,
+            stats_p2: output.stats_p2,
+            sonar: output.sonar.clone(),
+            mines: output.mines,
+            mine_penalty: output.mine_penalty,
+            mine_hits: output.mine_hits.clone(),
+            player_keys: output.player_keys.map(|keys| keys.map(hex::encode)),
+        }
+    }
+}
+
+/// Contents of `proof-output.json`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProofOutputFile {
+    pub journal_hex: String,
+    pub seal_hex: Option<String>,
+    pub public_output: PublicOutputJson,
+    /// Metadata fields default to empty so artifacts from older hosts still load in `inspect`.
+    #[serde(default)]
+    pub image_id_hex: String,
+    /// sha256(journal), the digest `submit_result` passes to the verifier.
+    #[serde(default)]
+    pub journal_digest_hex: String,
+    #[serde(default)]
+    pub receipt_kind: String,
+    #[serde(default)]
+    pub risc0_version: String,
+    #[serde(default)]
+    pub host_version: String,
+    /// sha256 of the compact JSON encoding of the proven `GameInput`.
+    #[serde(default)]
+    pub input_hash_hex: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiptKind {
+    Composite,
+    Succinct,
+    Groth16,
+}
+
+impl ReceiptKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Composite => "composite",
+            Self::Succinct => "succinct",
+            Self::Groth16 => "groth16",
+        }
+    }
+
+    fn prover_opts(self) -> ProverOpts {
+        match self {
+            Self::Composite => ProverOpts::composite(),
+            Self::Succinct => ProverOpts::succinct(),
+            Self::Groth16 => ProverOpts::groth16(),
+        }
+    }
+}
+
+impl FromStr for ReceiptKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "composite" => Ok(Self::Composite),
+            "succinct" => Ok(Self::Succinct),
+            "groth16" => Ok(Self::Groth16),
+            other => Err(format!(
+                "receipt kind must be composite, succinct or groth16, got '{other}'"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProveOptions {
+    pub receipt_kind: ReceiptKind,
+}
+
+impl Default for ProveOptions {
+    /// Groth16, the only receipt kind the on-chain verifier accepts.
+    fn default() -> Self {
+        Self {
+            receipt_kind: ReceiptKind::Groth16,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ProverError {
+    /// The game input could not be read or parsed, or is invalid.
+    Input(String),
+    /// The guest rejected the game when executed.
+    Guest(String),
+    /// The prover or its backend failed on a game the guest accepted.
+    Prove(String),
+    /// The executor environment for the guest could not be built.
+    Env(String),
+    /// The submission bundle could not be built from a finished proof.
+    Bundle(String),
+    /// The receipt could not be compressed into the requested kind.
+    Compress(String),
+    /// The receipt or artifact did not verify.
+    Verify(String),
+    /// An artifact could not be written.
+    Io(String),
+}
+
+impl fmt::Display for ProverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Input(msg)
+            | Self::Guest(msg)
+            | Self::Prove(msg)
+            | Self::Env(msg)
+            | Self::Bundle(msg)
+            | Self::Compress(msg)
+            | Self::Verify(msg)
+            | Self::Io(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for ProverError {}
+
+/// A proven match: the receipt plus everything derived from it.
+#[derive(Debug)]
+pub struct ProofArtifact {
+    pub receipt: Receipt,
+    pub output: ProofOutputFile,
+    /// Raw Groth16 seal, present only for real Groth16 receipts.
+    pub groth16_seal: Option<Vec<u8>>,
+    /// Verifier selector (first four bytes of the verifier parameters digest).
+    pub selector: Option<[u8; 4]>,
+    /// Proving cost; `None` when the artifact was not proven by this process (e.g. cache hits).
+    pub stats: Option<ProofStats>,
+    /// Image the receipt was proven for: `METHOD_ID`, or `AGGREGATE_ID` for a match proven
+    /// turn by turn.
+    pub image_id: [u32; 8],
+}
+
+impl ProofArtifact {
+    /// Wraps a receipt and its `proof-output.json` contents, extracting the Groth16 seal.
+    pub fn new(receipt: Receipt, output: ProofOutputFile) -> Self {
+        let (groth16_seal, selector) = groth16_seal(&receipt);
+        Self {
+            receipt,
+            output,
+            groth16_seal,
+            selector,
+            stats: None,
+            image_id: METHOD_ID,
+        }
+    }
+
+    /// Builds the `submit_result` bundle for this proof.
+    pub fn submission_bundle(
+        &self,
+        submitter: Option<&str>,
+    ) -> Result<SubmissionBundle, ProverError> {
+        submission::build_submission_bundle(
+            &self.output.public_output,
+            &self.receipt.journal.bytes,
+            self.groth16_seal.as_deref(),
+            self.selector,
+            Digest::from(self.image_id).as_bytes(),
+            submitter,
+        )
+        .map_err(ProverError::Bundle)
+    }
+
+    pub fn write_receipt(&self, path: &str) -> Result<(), ProverError> {
+        let bytes = bincode::serialize(&self.receipt)
+            .map_err(|e| ProverError::Io(format!("failed to serialize receipt: {e}")))?;
+        write_atomic(path, &bytes).map_err(ProverError::Io)
+    }
+
+    pub fn write_output(&self, path: &str) -> Result<(), ProverError> {
+        write_json(&self.output, path)
+    }
+}
+
+pub fn write_json<T: Serialize>(content: &T, path: &str) -> Result<(), ProverError> {
+    let json = serde_json::to_string_pretty(content)
+        .map_err(|e| ProverError::Io(format!("failed to encode '{path}': {e}")))?;
+    write_atomic(path, json.as_bytes()).map_err(ProverError::Io)
+}
+
+/// Hex of the guest image id, as stored by the contract's `set_image_id`.
+pub fn image_id_hex() -> String {
+    hex::encode(Digest::from(METHOD_ID).as_bytes())
+}
+
+/// Hex of the aggregation guest's image id, which the contract needs instead of
+/// [`image_id_hex`] to accept matches proven turn by turn.
+pub fn aggregate_image_id_hex() -> String {
+    hex::encode(Digest::from(AGGREGATE_ID).as_bytes())
+}
+
+/// sha256(journal), the digest the contract hands to the verifier.
+pub fn journal_digest(journal: &[u8]) -> [u8; 32] {
+    Sha256::digest(journal).into()
+}
+
+/// sha256 of the compact JSON encoding of `input`.
+pub fn input_hash(input: &GameInput) -> Result<[u8; 32], ProverError> {
+    let canonical = serde_json::to_vec(input)
+        .map_err(|e| ProverError::Input(format!("failed to encode game input: {e}")))?;
+    Ok(Sha256::digest(&canonical).into())
+}
+
+/// The raw Groth16 seal and verifier selector of a Groth16 receipt.
+fn groth16_seal(receipt: &Receipt) -> (Option<Vec<u8>>, Option<[u8; 4]>) {
+    match receipt.inner.groth16() {
+        Ok(groth16) => {
+            let mut selector = [0u8; 4];
+            selector.copy_from_slice(&groth16.verifier_parameters.as_bytes()[..4]);
+            (Some(groth16.seal.clone()), Some(selector))
+        }
+        Err(_) => (None, None),
+    }
+}
+
+fn receipt_kind(receipt: &Receipt) -> &'static str {
+    match &receipt.inner {
+        InnerReceipt::Composite(_) => "composite",
+        InnerReceipt::Succinct(_) => "succinct",
+        InnerReceipt::Groth16(_) => "groth16",
+        InnerReceipt::Fake(_) => "fake",
+        _ => "unknown",
+    }
+}
+
+/// Built-in sample match: both players place two horizontal size-2 ships, P1 wins in 7 moves.
+pub fn default_game_input(session_id: u32) -> GameInput {
+    GameInput {
+        session_id,
+        mode: GameMode::Demo,
+        no_touching: false,
+        salvo: false,
+        hit_keeps_turn: false,
+        weapons: Weapons::default(),
+        mines: 0,
+        mine_penalty: MinePenalty::default(),
+        board_p1: [
+            1, 1, 0, 0, // y=0 (ship size 2)
+            0, 0, 0, 0, // y=1
+            1, 1, 0, 0, // y=2 (ship size 2)
+            0, 0, 0, 0, // y=3
+        ],
+        board_p2: [
+            1, 1, 0, 0, // y=0 (ship size 2)
+            0, 0, 0, 0, // y=1
+            1, 1, 0, 0, // y=2 (ship size 2)
+            0, 0, 0, 0, // y=3
+        ],
+        fleet_p1: Vec::new(),
+        fleet_p2: Vec::new(),
+        mines_p1: Vec::new(),
+        mines_p2: Vec::new(),
+        board_salt_p1: [0; 32],
+        board_salt_p2: [0; 32],
+        player_keys: None,
+        moves: vec![
+            Move::new(1, 0, 0),
+            Move::new(2, 3, 3),
+            Move::new(1, 1, 0),
+            Move::new(2, 3, 2),
+            Move::new(1, 0, 2),
+            Move::new(2, 2, 2),
+            Move::new(1, 1, 2),
+        ],
+    }
+}
+
+/// Longest legal match on the sample boards, used as the worst case by `bench`.
+///
+/// P1 fires at every water cell before any ship cell and wins on its 16th shot; P2 fires at
+/// 15 cells, leaving one P1 ship cell unhit. 31 moves, every cell shot by P1.
+pub fn full_board_game_input(session_id: u32) -> GameInput {
+    let sample = default_game_input(session_id);
+
+    let cells = |board: &[u8; 16]| {
+        let mut cells: Vec<usize> = (0..16).collect();
+        cells.sort_by_key(|&idx| board[idx]);
+        cells
+    };
+    let p1_targets = cells(&sample.board_p2);
+    let p2_targets = cells(&sample.board_p1);
+
+    let mut moves = Vec::with_capacity(31);
+    for turn in 0..16 {
+        for (player, idx) in [(1, p1_targets[turn]), (2, p2_targets[turn])] {
+            if moves.len() == 31 {
+                break;
+            }
+            moves.push(Move::new(player, (idx % 4) as u8, (idx / 4) as u8));
+        }
+    }
+
+    GameInput { moves, ..sample }
+}
+
+/// Built-in match under the hit-keeps-turn rule (`game-input.hit-keeps-turn.example.json`):
+/// the sample boards, where P2 sinks a ship in a two-hit streak and P1 then sinks both P2
+/// ships in a four-hit streak. 8 moves, P1 wins.
+pub fn hit_keeps_turn_game_input(session_id: u32) -> GameInput {
+    let sample = default_game_input(session_id);
+    GameInput {
+        hit_keeps_turn: true,
+        moves: vec![
+            Move::new(1, 3, 3), // miss: turn passes
+            Move::new(2, 0, 0),
+            Move::new(2, 1, 0), // sinks P1's first ship
+            Move::new(2, 3, 3), // miss: turn passes
+            Move::new(1, 0, 0),
+            Move::new(1, 1, 0),
+            Move::new(1, 0, 2),
+            Move::new(1, 1, 2),
+        ],
+        ..sample
+    }
+}
+
+/// Built-in match with one mine per player (`game-input.mines.example.json`): the sample
+/// boards with a mine at (3, 1) on P1's and (3, 0) on P2's. P2 sets off P1's mine and skips
+/// a turn, so P1 fires twice in a row. 6 moves, P1 wins.
+pub fn mines_game_input(session_id: u32) -> GameInput {
+    let sample = default_game_input(session_id);
+    let mut board_p1 = sample.board_p1;
+    board_p1[7] = MINE;
+    let mut board_p2 = sample.board_p2;
+    board_p2[3] = MINE;
+    GameInput {
+        mines: 1,
+        board_p1,
+        board_p2,
+        moves: vec![
+            Move::new(1, 0, 0),
+            Move::new(2, 3, 1), // mine: P2 skips its next turn
+            Move::new(1, 1, 0),
+            Move::new(1, 0, 2),
+            Move::new(2, 3, 3),
+            Move::new(1, 1, 2),
+        ],
+        ..sample
+    }
+}
+
+fn fleet(ships: [(u8, u8, Orientation, u8); 5]) -> Vec<Ship> {
+    ships
+        .into_iter()
+        .map(|(x, y, orientation, length)| Ship {
+            x,
+            y,
+            orientation,
+            length,
+        })
+        .collect()
+}
+
+/// Built-in classic match (`game-input.classic.example.json`): 10x10, 5-4-3-3-2 fleets with
+/// the no-touching rule. P1 sinks every ship in 17 shots while P2 only finds water.
+pub fn classic_game_input(session_id: u32) -> GameInput {
+    use Orientation::{Horizontal as H, Vertical as V};
+
+    let fleet_p1 = fleet([(0, 0, H, 5), (7, 2, V, 4), (1, 3, H, 3), (4, 6, V, 3), (8, 8, H, 2)]);
+    let fleet_p2 = fleet([(2, 9, H, 5), (0, 1, V, 4), (5, 1, H, 3), (9, 4, V, 3), (3, 5, V, 2)]);
+
+    let grid = battleship_rules::classic_grid();
+    let occupied = |fleet: &[Ship], x: u8, y: u8| {
+        let cell = grid.cell(u32::from(x), u32::from(y)).unwrap_or_default();
+        fleet
+            .iter()
+            .any(|ship| ship.cells(&grid).unwrap_or_default() & cell != 0)
+    };
+    let cells = (0..10u8).flat_map(|y| (0..10u8).map(move |x| (x, y)));
+    let p1_targets: Vec<(u8, u8)> = cells
+        .clone()
+        .filter(|&(x, y)| occupied(&fleet_p2, x, y))
+        .collect();
+    let p2_targets: Vec<(u8, u8)> = cells.filter(|&(x, y)| !occupied(&fleet_p1, x, y)).collect();
+
+    let mut moves = Vec::new();
+    for (turn, &(x, y)) in p1_targets.iter().enumerate() {
+        moves.push(Move::new(1, x, y));
+        if turn + 1 < p1_targets.len() {
+            let (x, y) = p2_targets[turn];
+            moves.push(Move::new(2, x, y));
+        }
+    }
+
+    GameInput {
+        session_id,
+        mode: GameMode::Classic,
+        no_touching: true,
+        salvo: false,
+        hit_keeps_turn: false,
+        weapons: Weapons::default(),
+        mines: 0,
+        mine_penalty: MinePenalty::default(),
+        board_p1: [0; 16],
+        board_p2: [0; 16],
+        fleet_p1,
+        fleet_p2,
+        mines_p1: Vec::new(),
+        mines_p2: Vec::new(),
+        board_salt_p1: [0; 32],
+        board_salt_p2: [0; 32],
+        player_keys: None,
+        moves,
+    }
+}
+
+/// Built-in classic match with one of each special weapon per player
+/// (`game-input.weapons.example.json`): both players ping with sonar, then trade line
+/// strikes and bombs that sink two ships each, and P1 finishes P2's fleet with single shots.
+pub fn weapons_game_input(session_id: u32) -> GameInput {
+    let classic = classic_game_input(session_id);
+
+    let mut moves = vec![
+        Move::weapon(1, Action::Sonar, 6, 1),
+        Move::weapon(2, Action::Sonar, 1, 1),
+        Move::weapon(1, Action::RowStrike, 0, 1),
+        Move::weapon(2, Action::ColumnStrike, 7, 0),
+        Move::weapon(1, Action::Bomb, 3, 6),
+        Move::weapon(2, Action::Bomb, 2, 2),
+    ];
+    // What the opening left afloat of P2's fleet, and water on P1's side.
+    let p1_targets = [
+        (0, 2), (0, 3), (0, 4), (2, 9), (3, 9), (4, 9), (5, 9), (6, 9), (9, 4), (9, 5), (9, 6),
+    ];
+    let p2_targets = [
+        (8, 0), (9, 0), (9, 1), (9, 2), (9, 3), (9, 4), (9, 5), (9, 6), (9, 7), (9, 9),
+    ];
+    for (turn, &(x, y)) in p1_targets.iter().enumerate() {
+        moves.push(Move::new(1, x, y));
+        if let Some(&(x, y)) = p2_targets.get(turn) {
+            moves.push(Move::new(2, x, y));
+        }
+    }
+
+    GameInput {
+        weapons: Weapons {
+            bombs: 1,
+            line_strikes: 1,
+            sonar_pings: 1,
+        },
+        moves,
+        ..classic
+    }
+}
+
+/// Secret keys the signed sample is signed with. Public test values: never use them for an
+/// account that holds anything.
+pub const SAMPLE_SECRET_KEYS: [[u8; 32]; 2] = [[1; 32], [2; 32]];
+
+/// The built-in sample match with every move signed by [`SAMPLE_SECRET_KEYS`]
+/// (`game-input.signed.example.json`).
+pub fn signed_game_input(session_id: u32) -> GameInput {
+    let mut input = GameInput {
+        player_keys: Some(SAMPLE_SECRET_KEYS.map(|key| transcript::public_key(&key))),
+        ..default_game_input(session_id)
+    };
+    transcript::sign_moves(session_id, &mut input.moves, &SAMPLE_SECRET_KEYS);
+    input
+}
+
+pub fn load_game_input(path: impl AsRef<Path>) -> Result<GameInput, ProverError> {
+    let path = path.as_ref();
+    let raw = fs::read_to_string(path).map_err(|e| {
+        ProverError::Input(format!("Failed to read input file '{}': {e}", path.display()))
+    })?;
+    serde_json::from_str::<GameInput>(&raw).map_err(|e| {
+        ProverError::Input(format!("Failed to parse JSON in '{}': {e}", path.display()))
+    })
+}
+
+fn executor_env(input: &GameInput) -> Result<ExecutorEnv<'static>, ProverError> {
+    ExecutorEnv::builder()
+        .write(input)
+        .map_err(|e| ProverError::Env(format!("failed to write executor input: {e}")))?
+        .build()
+        .map_err(|e| ProverError::Env(format!("failed to build executor env: {e}")))
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    started.elapsed().as_millis() as u64
+}
+
+/// Runs the guest `elf` over the environment `env` builds and returns a verified composite
+/// receipt with its decoded journal.
+///
+/// The guest is executed once without proving first, so rejected games fail fast and the
+/// execute phase can be timed on its own.
+fn run_guest<T: DeserializeOwned>(
+    env: impl Fn() -> Result<ExecutorEnv<'static>, ProverError>,
+    elf: &[u8],
+    image_id: [u32; 8],
+    stats: &mut ProofStats,
+) -> Result<(Receipt, T), ProverError> {
+    let started = Instant::now();
+    default_executor()
+        .execute(env()?, elf)
+        .map_err(|e| ProverError::Guest(format!("guest execution failed: {e}")))?;
+    stats.execute_ms = elapsed_ms(started);
+
+    let started = Instant::now();
+    let prove_info = default_prover()
+        .prove(env()?, elf)
+        .map_err(|e| ProverError::Prove(format!("prove failed: {e}")))?;
+    stats.prove_ms = elapsed_ms(started);
+    stats.total_cycles = prove_info.stats.total_cycles;
+    stats.user_cycles = prove_info.stats.user_cycles;
+    stats.segments = prove_info.stats.segments;
+    let receipt = prove_info.receipt;
+
+    receipt
+        .verify(image_id)
+        .map_err(|e| ProverError::Verify(format!("receipt verification failed: {e}")))?;
+
+    let output: T = receipt
+        .journal
+        .decode()
+        .map_err(|e| ProverError::Verify(format!("journal decode failed: {e}")))?;
+
+    Ok((receipt, output))
+}
+
+/// Compresses a composite receipt into the requested kind.
+///
+/// Fails instead of falling back when the requested kind cannot be produced. Dev-mode fake
+/// receipts are accepted for any kind since no real proof exists to compress.
+fn compress_receipt(receipt: Receipt, kind: ReceiptKind) -> Result<Receipt, ProverError> {
+    let receipt = if kind == ReceiptKind::Composite {
+        receipt
+    } else {
+        default_prover()
+            .compress(&kind.prover_opts(), &receipt)
+            .map_err(|e| {
+                ProverError::Compress(format!("failed to produce {} receipt: {e}", kind.as_str()))
+            })?
+    };
+
+    let produced = receipt_kind(&receipt);
+    if produced != kind.as_str() && produced != "fake" {
+        return Err(ProverError::Compress(format!(
+            "requested a {} receipt but the prover produced a {produced} receipt",
+            kind.as_str()
+        )));
+    }
+
+    Ok(receipt)
+}
+
+/// Proves `input` and packages the result.
+pub fn prove(input: &GameInput, opts: &ProveOptions) -> Result<ProofArtifact, ProverError> {
+    if input.session_id == 0 {
+        return Err(ProverError::Input(String::from(
+            "game input session_id must be greater than 0",
+        )));
+    }
+    let input_hash_hex = hex::encode(input_hash(input)?);
+
+    let mut stats = ProofStats::default();
+    let (receipt, public_output) =
+        run_guest(|| executor_env(input), METHOD_ELF, METHOD_ID, &mut stats)?;
+    package(receipt, &public_output, METHOD_ID, input_hash_hex, opts, stats)
+}
+
+/// Compresses a verified receipt of `image_id` and builds its artifact.
+fn package(
+    receipt: Receipt,
+    public_output: &PublicOutput,
+    image_id: [u32; 8],
+    input_hash_hex: String,
+    opts: &ProveOptions,
+    mut stats: ProofStats,
+) -> Result<ProofArtifact, ProverError> {
+    let started = Instant::now();
+    let receipt = compress_receipt(receipt, opts.receipt_kind)?;
+    if opts.receipt_kind != ReceiptKind::Composite {
+        stats.compress_ms = elapsed_ms(started);
+    }
+    stats.peak_memory_bytes = peak_memory_bytes();
+
+    let seal = receipt.inner.groth16().ok().map(|groth16| groth16.seal.clone());
+
+    let journal = &receipt.journal.bytes;
+    let output = ProofOutputFile {
+        journal_hex: hex::encode(journal),
+        seal_hex: seal.as_deref().map(hex::encode),
+        public_output: PublicOutputJson::from(public_output),
+        image_id_hex: hex::encode(Digest::from(image_id).as_bytes()),
+        journal_digest_hex: hex::encode(journal_digest(journal)),
+        receipt_kind: String::from(receipt_kind(&receipt)),
+        risc0_version: String::from(RISC0_VERSION),
+        host_version: String::from(env!("CARGO_PKG_VERSION")),
+        input_hash_hex,
+    };
+
+    let mut artifact = ProofArtifact::new(receipt, output);
+    artifact.stats = Some(stats);
+    artifact.image_id = image_id;
+    Ok(artifact)
+}
+
+/// Re-verifies the receipt against the artifact's image id and checks that the
+/// `proof-output.json` fields agree with the journal it commits to.
+pub fn verify_artifact(artifact: &ProofArtifact) -> Result<PublicOutput, ProverError> {
+    artifact
+        .receipt
+        .verify(artifact.image_id)
+        .map_err(|e| ProverError::Verify(format!("receipt verification failed: {e}")))?;
+
+    let journal = &artifact.receipt.journal.bytes;
+    let output = &artifact.output;
+
+    if output.journal_hex != hex::encode(journal) {
+        return Err(ProverError::Verify(String::from(
+            "journal_hex does not match the receipt journal",
+        )));
+    }
+    if output.journal_digest_hex != hex::encode(journal_digest(journal)) {
+        return Err(ProverError::Verify(String::from(
+            "journal_digest_hex does not match sha256(journal)",
+        )));
+    }
+    if output.image_id_hex != hex::encode(Digest::from(artifact.image_id).as_bytes()) {
+        return Err(ProverError::Verify(String::from(
+            "image_id_hex does not match the image the receipt was proven for",
+        )));
+    }
+
+    let public_output: PublicOutput = artifact
+        .receipt
+        .journal
+        .decode()
+        .map_err(|e| ProverError::Verify(format!("journal decode failed: {e}")))?;
+    if PublicOutputJson::from(&public_output) != output.public_output {
+        return Err(ProverError::Verify(String::from(
+            "public_output does not match the receipt journal",
+        )));
+    }
+
+    Ok(public_output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_opts() -> ProveOptions {
+        ProveOptions {
+            receipt_kind: ReceiptKind::Composite,
+        }
+    }
+
+    #[test]
+    fn valid_input_proves_successfully() {
+        let input = default_game_input(777);
+        let result = prove(&input, &fast_opts());
+        assert!(result.is_ok(), "expected valid input to prove, got: {:?}", result.err());
+
+        let artifact = result.unwrap();
+        let out = &artifact.output.public_output;
+        assert_eq!(out.session_id, 777);
+        assert_eq!(out.total_moves, 7);
+
+        verify_artifact(&artifact).expect("fresh artifact should verify");
+
+        let stats = artifact.stats.as_ref().expect("fresh proof should carry stats");
+        assert!(stats.segments > 0);
+        assert!(stats.total_cycles >= stats.user_cycles);
+    }
+
+    #[test]
+    fn duplicate_shot_is_rejected_by_guest() {
+        let mut input = default_game_input(778);
+        // Duplicate shot for player 1 at (0,0)
+        input.moves = vec![
+            Move::new(1, 0, 0),
+            Move::new(2, 1, 0),
+            Move::new(1, 0, 0),
+            Move::new(2, 2, 0),
+        ];
+
+        let err = prove(&input, &fast_opts()).expect_err("expected duplicate shot to fail");
+        assert!(
+            err.to_string().contains("duplicate shot by player 1"),
+            "unexpected error message: {err}"
+        );
+    }
+
+
+    #[test]
+    fn invalid_board_layout_is_rejected() {
+        let mut input = default_game_input(7781);
+        // 2x2 contiguous block is one ship of size 4, which is invalid for the new rule.
+        input.board_p1 = [
+            1, 1, 0, 0,
+            1, 1, 0, 0,
+            0, 0, 0, 0,
+            0, 0, 0, 0,
+        ];
+
+        let err = prove(&input, &fast_opts()).expect_err("expected invalid board layout to fail");
+        assert!(
+            err.to_string().contains("invalid board layout P1"),
+            "unexpected error message: {err}"
+        );
+    }
+
+
+    #[test]
+    fn moves_after_game_over_are_rejected() {
+        let mut input = default_game_input(779);
+        // P1 wins in 7 moves in default sample; add an extra move after game over.
+        input.moves.push(Move::new(2, 2, 2));
+
+        let err =
+            prove(&input, &fast_opts()).expect_err("expected moves-after-game-over to fail");
+        assert!(
+            err.to_string().contains("moves after game over are not allowed"),
+            "unexpected error message: {err}"
+        );
+    }
+
+    #[test]
+    fn invalid_turn_order_is_rejected() {
+        let mut input = default_game_input(780);
+        input.moves = vec![
+            Move::new(1, 0, 0),
+            Move::new(1, 1, 0), // invalid: player 1 twice
+        ];
+
+        let err = prove(&input, &fast_opts()).expect_err("expected invalid turn order to fail");
+        assert!(
+            err.to_string().contains("invalid turn order"),
+            "unexpected error message: {err}"
+        );
+    }
+
+    #[test]
+    fn full_board_game_is_the_longest_legal_match() {
+        let input = full_board_game_input(42);
+        assert_eq!(input.moves.len(), 31);
+
+        let mut shots = [[false; 16]; 2];
+        let mut hits = [0u8; 2];
+        for (i, mv) in input.moves.iter().enumerate() {
+            assert_eq!(mv.player, if i % 2 == 0 { 1 } else { 2 });
+            let idx = usize::from(mv.y) * 4 + usize::from(mv.x);
+            let shooter = usize::from(mv.player - 1);
+            assert!(!shots[shooter][idx], "duplicate shot at move {i}");
+            shots[shooter][idx] = true;
+
+            let target = if mv.player == 1 { &input.board_p2 } else { &input.board_p1 };
+            hits[shooter] += target[idx];
+            let game_over = hits.contains(&4);
+            assert_eq!(game_over, i == 30, "game ended early at move {i}");
+        }
+        assert_eq!(hits, [4, 3]);
+    }
+
+    #[test]
+    fn classic_sample_file_matches_built_in_input() {
+        let file: GameInput =
+            serde_json::from_str(include_str!("../../game-input.classic.example.json")).unwrap();
+        let built_in = classic_game_input(file.session_id);
+        assert_eq!(
+            serde_json::to_value(&file).unwrap(),
+            serde_json::to_value(&built_in).unwrap()
+        );
+
+        let output = battleship_rules::play(&built_in).expect("classic sample should be valid");
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.total_moves, 33);
+        assert_eq!(output.mode, GameMode::Classic);
+        assert_eq!(output.stats_p1.ships_sunk, 5);
+        assert_eq!(output.stats_p1.hits + output.stats_p1.misses, 17);
+    }
+
+    #[test]
+    fn hit_keeps_turn_sample_file_matches_built_in_input() {
+        let file: GameInput = serde_json::from_str(include_str!(
+            "../../game-input.hit-keeps-turn.example.json"
+        ))
+        .unwrap();
+        let built_in = hit_keeps_turn_game_input(file.session_id);
+        assert_eq!(
+            serde_json::to_value(&file).unwrap(),
+            serde_json::to_value(&built_in).unwrap()
+        );
+
+        let output =
+            battleship_rules::play(&built_in).expect("hit-keeps-turn sample should be valid");
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.total_moves, 8);
+        assert!(output.hit_keeps_turn);
+
+        // The alternating sample breaks the rule: P1 hits with its first shot.
+        let strict = GameInput {
+            hit_keeps_turn: true,
+            ..default_game_input(42)
+        };
+        assert!(battleship_rules::play(&strict).is_err());
+    }
+
+    #[test]
+    fn weapons_sample_file_matches_built_in_input() {
+        let file: GameInput =
+            serde_json::from_str(include_str!("../../game-input.weapons.example.json")).unwrap();
+        let built_in = weapons_game_input(file.session_id);
+        assert_eq!(
+            serde_json::to_value(&file).unwrap(),
+            serde_json::to_value(&built_in).unwrap()
+        );
+
+        let output = battleship_rules::play(&built_in).expect("weapons sample should be valid");
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.total_moves, 27);
+        assert_eq!(output.stats_p1.ships_sunk, 5);
+        assert_eq!(output.stats_p2.ships_sunk, 2);
+        let pings: Vec<(u8, u8)> =
+            output.sonar.iter().map(|ping| (ping.player, ping.ship_cells)).collect();
+        assert_eq!(pings, [(1, 3), (2, 3)]);
+    }
+
+    #[test]
+    fn mines_sample_file_matches_built_in_input() {
+        let file: GameInput =
+            serde_json::from_str(include_str!("../../game-input.mines.example.json")).unwrap();
+        let built_in = mines_game_input(file.session_id);
+        assert_eq!(
+            serde_json::to_value(&file).unwrap(),
+            serde_json::to_value(&built_in).unwrap()
+        );
+
+        let output = battleship_rules::play(&built_in).expect("mines sample should be valid");
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.total_moves, 6);
+        let hits: Vec<(u32, u8)> =
+            output.mine_hits.iter().map(|hit| (hit.move_index, hit.player)).collect();
+        assert_eq!(hits, [(1, 2)]);
+
+        let json = serde_json::to_value(PublicOutputJson::from(&output)).unwrap();
+        assert_eq!(json["mine_penalty"], "skip_turn");
+    }
+
+    #[test]
+    fn bundle_failures_are_not_prove_failures() {
+        use risc0_zkvm::{FakeReceipt, ReceiptClaim};
+
+        let output = battleship_rules::play(&signed_game_input(42)).unwrap();
+        let journal = risc0_zkvm::serde::to_vec(&output).unwrap();
+        let journal: Vec<u8> = journal.iter().flat_map(|word| word.to_le_bytes()).collect();
+        let claim = ReceiptClaim::ok(METHOD_ID, journal.clone());
+        let receipt = Receipt::new(InnerReceipt::Fake(FakeReceipt::new(claim)), journal);
+        let artifact = package(
+            receipt,
+            &output,
+            METHOD_ID,
+            String::new(),
+            &fast_opts(),
+            ProofStats::default(),
+        )
+        .unwrap();
+
+        assert!(artifact.submission_bundle(None).is_ok());
+        let err = artifact
+            .submission_bundle(Some("not-an-address"))
+            .expect_err("submitter should be rejected");
+        assert!(matches!(err, ProverError::Bundle(_)), "unexpected error: {err:?}");
+    }
+
+    #[test]
+    fn aggregate_needs_one_turn_receipt_per_move() {
+        let input = default_game_input(42);
+        let err = aggregate(&input, &[], &fast_opts()).expect_err("no turn receipts");
+        assert!(matches!(err, ProverError::Input(_)));
+        assert_eq!(err.to_string(), "0 turn receipts for 7 moves");
+    }
+
+    #[test]
+    fn signed_sample_file_matches_built_in_input() {
+        let file: GameInput =
+            serde_json::from_str(include_str!("../../game-input.signed.example.json")).unwrap();
+        let built_in = signed_game_input(file.session_id);
+        assert_eq!(
+            serde_json::to_value(&file).unwrap(),
+            serde_json::to_value(&built_in).unwrap()
+        );
+
+        let output = battleship_rules::play(&built_in).expect("signed sample should be valid");
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.total_moves, 7);
+
+        let json = PublicOutputJson::from(&output);
+        let [p1, _] = json.player_keys.expect("signed sample should commit its keys");
+        assert_eq!(p1, hex::encode(transcript::public_key(&SAMPLE_SECRET_KEYS[0])));
+
+        // The signatures are bound to the session.
+        let replayed = GameInput {
+            session_id: file.session_id + 1,
+            ..file
+        };
+        assert!(battleship_rules::play(&replayed).is_err());
+    }
+
+    #[test]
+    fn free_for_all_sample_file_matches_built_in_input() {
+        let file: FreeForAllInput =
+            serde_json::from_str(include_str!("../../game-input.free-for-all.example.json"))
+                .unwrap();
+        let built_in = free_for_all_game_input(file.session_id);
+        assert_eq!(
+            serde_json::to_value(&file).unwrap(),
+            serde_json::to_value(&built_in).unwrap()
+        );
+
+        let output = battleship_rules::free_for_all::play_free_for_all(&built_in)
+            .expect("free-for-all sample should be valid");
+        assert_eq!(output.ranking, [1, 2, 3]);
+        assert_eq!(output.total_moves, 12);
+
+        let json = FreeForAllOutputJson::from(&output);
+        let keys = json.player_keys.expect("free-for-all sample should commit its keys");
+        assert_eq!(keys[2], hex::encode(transcript::public_key(&FREE_FOR_ALL_SECRET_KEYS[2])));
+    }
+
+    #[test]
+    fn team_sample_file_matches_built_in_input() {
+        let file: TeamInput =
+            serde_json::from_str(include_str!("../../game-input.teams.example.json")).unwrap();
+        let built_in = team_game_input(file.session_id);
+        assert_eq!(
+            serde_json::to_value(&file).unwrap(),
+            serde_json::to_value(&built_in).unwrap()
+        );
+
+        let output = battleship_rules::teams::play_teams(&built_in)
+            .expect("team sample should be valid");
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.total_moves, 7);
+
+        let json = TeamOutputJson::from(&output);
+        let keys = json.player_keys.expect("team sample should commit its keys");
+        assert_eq!(keys[3], hex::encode(transcript::public_key(&TEAM_SECRET_KEYS[3])));
+    }
+
+    #[test]
+    fn resignations_may_omit_coordinates() {
+        let mut input = default_game_input(42);
+        input.moves = serde_json::from_str(
+            r#"[{ "player": 1, "x": 0, "y": 0 }, { "player": 2, "action": "resign" }]"#,
+        )
+        .unwrap();
+        let output = battleship_rules::play(&input).expect("resignation should be accepted");
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.outcome, Outcome::Resigned);
+
+        let json = serde_json::to_value(PublicOutputJson::from(&output)).unwrap();
+        assert_eq!(json["outcome"], "resigned");
+    }
+
+    #[test]
+    fn receipt_kind_accepts_only_known_values() {
+        assert_eq!("composite".parse(), Ok(ReceiptKind::Composite));
+        assert_eq!("succinct".parse(), Ok(ReceiptKind::Succinct));
+        assert_eq!("groth16".parse(), Ok(ReceiptKind::Groth16));
+
+        let err = "stark"
+            .parse::<ReceiptKind>()
+            .expect_err("expected unknown kind to fail");
+        assert!(err.contains("receipt kind"), "unexpected error message: {err}");
+    }
+
+    #[test]
+    fn zero_session_id_is_rejected_before_proving() {
+        let input = default_game_input(0);
+        let err = prove(&input, &fast_opts()).expect_err("expected session 0 to fail");
+        assert!(matches!(err, ProverError::Input(_)), "unexpected error: {err:?}");
+    }
+}
@@ -0,0 +1,150 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+fn parent_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    parent_dir(path).join(format!(".{file_name}.tmp-{}", std::process::id()))
+}
+
+/// The file `path` names, with its parent directory canonicalized, so `./a.json` and
+/// `a.json` compare equal. Falls back to the path as given when the directory is missing.
+pub fn output_target(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    let parent = parent_dir(path);
+    match (fs::canonicalize(parent), path.file_name()) {
+        (Ok(parent), Some(name)) => parent.join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Checks that `path` can be written before any proving work starts.
+///
+/// The parent directory must exist and accept new files, and `path` itself must not be a
+/// directory. A probe file is created and removed to catch read-only mounts and permissions;
+/// a probe left behind by a crashed run is overwritten.
+pub fn validate_output_path(flag: &str, path: &str) -> Result<(), String> {
+    let path = Path::new(path);
+    let parent = parent_dir(path);
+
+    if !parent.is_dir() {
+        return Err(format!(
+            "{flag} directory '{}' does not exist",
+            parent.display()
+        ));
+    }
+    if path.is_dir() {
+        return Err(format!("{flag} path '{}' is a directory", path.display()));
+    }
+
+    let probe = temp_path(path);
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&probe)
+        .map_err(|e| format!("{flag} path '{}' is not writable: {e}", path.display()))?;
+    let _ = fs::remove_file(&probe);
+
+    Ok(())
+}
+
+/// Writes `bytes` to a temp file next to `path`, syncs it, then renames it into place.
+///
+/// Readers see either the previous file or the complete new one, never a partial write.
+pub fn write_atomic(path: &str, bytes: &[u8]) -> Result<(), String> {
+    let target = Path::new(path);
+    let temp = temp_path(target);
+
+    let result = File::create(&temp)
+        .and_then(|mut file| {
+            file.write_all(bytes)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp, target));
+
+    result.map_err(|e| {
+        let _ = fs::remove_file(&temp);
+        format!("failed to write '{}': {e}", target.display())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("host-output-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn missing_directory_is_rejected_before_proving() {
+        let dir = scratch_dir("missing");
+        let path = dir.join("no-such-dir").join("proof-output.json");
+
+        let err = validate_output_path("--proof", path.to_str().unwrap())
+            .expect_err("expected missing directory to fail");
+        assert!(err.contains("does not exist"), "unexpected error message: {err}");
+    }
+
+    #[test]
+    fn directory_target_is_rejected() {
+        let dir = scratch_dir("is-dir");
+
+        let err = validate_output_path("--receipt", dir.to_str().unwrap())
+            .expect_err("expected directory target to fail");
+        assert!(err.contains("is a directory"), "unexpected error message: {err}");
+    }
+
+    #[test]
+    fn stale_probe_file_does_not_block_validation() {
+        let dir = scratch_dir("stale-probe");
+        let path = dir.join("proof-output.json");
+        fs::write(temp_path(&path), b"left by a crashed run").unwrap();
+
+        validate_output_path("--proof", path.to_str().unwrap()).expect("path should be writable");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn output_targets_ignore_how_the_directory_is_spelled() {
+        let dir = scratch_dir("targets");
+        let plain = dir.join("proof-output.json");
+        let dotted = dir.join(".").join("proof-output.json");
+        assert_eq!(
+            output_target(plain.to_str().unwrap()),
+            output_target(dotted.to_str().unwrap())
+        );
+        assert_ne!(
+            output_target(plain.to_str().unwrap()),
+            output_target(dir.join("receipt.bin").to_str().unwrap())
+        );
+    }
+
+    #[test]
+    fn atomic_write_replaces_file_and_leaves_no_temp() {
+        let dir = scratch_dir("atomic");
+        let path = dir.join("proof-output.json");
+        let path_str = path.to_str().unwrap();
+
+        validate_output_path("--proof", path_str).expect("path should be writable");
+        write_atomic(path_str, b"first").unwrap();
+        write_atomic(path_str, b"second").unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), b"second");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    }
+}
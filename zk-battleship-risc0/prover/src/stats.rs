@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+
+/// Cost of producing one proof, written as `proof-stats.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProofStats {
+    /// Cycles proven, including paging and padding overhead.
+    pub total_cycles: u64,
+    /// Cycles spent in guest code.
+    pub user_cycles: u64,
+    pub segments: usize,
+    pub execute_ms: u64,
+    pub prove_ms: u64,
+    /// Zero for composite receipts.
+    pub compress_ms: u64,
+    /// Peak resident memory of the whole process, when the platform reports it.
+    pub peak_memory_bytes: Option<u64>,
+}
+
+impl fmt::Display for ProofStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "cycles: {} total, {} user",
+            self.total_cycles, self.user_cycles
+        )?;
+        writeln!(f, "segments: {}", self.segments)?;
+        writeln!(
+            f,
+            "time: execute {} ms, prove {} ms, compress {} ms",
+            self.execute_ms, self.prove_ms, self.compress_ms
+        )?;
+        match self.peak_memory_bytes {
+            Some(bytes) => write!(
+                f,
+                "peak memory: {:.1} MiB",
+                bytes as f64 / (1024.0 * 1024.0)
+            ),
+            None => write!(f, "peak memory: unavailable"),
+        }
+    }
+}
+
+/// Peak resident set size of this process, read from `VmHWM` in `/proc/self/status`.
+///
+/// Returns `None` on platforms without procfs.
+pub fn peak_memory_bytes() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    parse_vm_hwm(&status)
+}
+
+fn parse_vm_hwm(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vm_hwm_is_parsed_in_bytes() {
+        let status = "Name:\thost\nVmPeak:\t  200 kB\nVmHWM:\t    1536 kB\nVmRSS:\t 1024 kB\n";
+        assert_eq!(parse_vm_hwm(status), Some(1536 * 1024));
+        assert_eq!(parse_vm_hwm("Name:\thost\n"), None);
+    }
+
+    #[test]
+    fn human_form_lists_every_phase() {
+        let stats = ProofStats {
+            total_cycles: 131072,
+            user_cycles: 90000,
+            segments: 1,
+            execute_ms: 12,
+            prove_ms: 3400,
+            compress_ms: 0,
+            peak_memory_bytes: None,
+        };
+        let text = stats.to_string();
+        assert!(text.contains("131072 total, 90000 user"));
+        assert!(text.contains("execute 12 ms, prove 3400 ms, compress 0 ms"));
+        assert!(text.ends_with("peak memory: unavailable"));
+    }
+}
@@ -0,0 +1,567 @@
+use serde::Serialize;
+use std::str::FromStr;
+use stellar_xdr::curr::{Limits, ScAddress, ScVal, ScVec, WriteXdr};
+
+use crate::{FreeForAllOutputJson, PublicOutputJson, TeamOutputJson};
+
+const SUBMIT_METHOD: &str = "submit_result";
+const SUBMIT_FREE_FOR_ALL_METHOD: &str = "submit_free_for_all_result";
+const SUBMIT_TEAM_METHOD: &str = "submit_team_result";
+const SUBMITTER_PLACEHOLDER: &str = "<SUBMITTER_ADDRESS>";
+
+/// One `submit_result` argument, as passed to the stellar CLI and as an XDR ScVal.
+#[derive(Debug, Serialize)]
+pub struct SubmissionArg {
+    pub name: &'static str,
+    pub cli_value: String,
+    /// `None` only for `submitter` when no `--submitter` address was given.
+    pub scval_xdr_base64: Option<String>,
+}
+
+/// Everything needed to call `submit_result(...)` for a proven match.
+#[derive(Debug, Serialize)]
+pub struct SubmissionBundle {
+    pub contract_method: &'static str,
+    pub session_id: u32,
+    pub winner: u8,
+    /// `Outcome::code` of the proven game: 1 = all ships sunk, 2 = resignation.
+    pub outcome: u32,
+    pub total_moves: u32,
+    pub board_hash_p1: String,
+    pub board_hash_p2: String,
+    /// Hex ed25519 keys of P1 and P2; null for an unsigned game, which `submit_result`
+    /// rejects.
+    pub player_keys: Option<[String; 2]>,
+    pub journal_hex: String,
+    /// Verifier-ready seal (`selector || groth16 seal`), null without a Groth16 receipt.
+    pub seal_hex: Option<String>,
+    pub image_id_hex: String,
+    pub selector_hex: Option<String>,
+    pub submitter: Option<String>,
+    pub args: Vec<SubmissionArg>,
+    pub stellar_cli_args: Vec<String>,
+    pub stellar_invoke_command: String,
+}
+
+/// Everything needed to call `submit_free_for_all_result(...)` for a proven free-for-all.
+#[derive(Debug, Serialize)]
+pub struct FreeForAllSubmission {
+    pub contract_method: &'static str,
+    pub session_id: u32,
+    /// Seats from first to last place.
+    pub ranking: Vec<u8>,
+    pub total_moves: u32,
+    pub board_hashes: Vec<String>,
+    pub player_keys: Option<Vec<String>>,
+    pub journal_hex: String,
+    pub seal_hex: Option<String>,
+    pub image_id_hex: String,
+    pub selector_hex: Option<String>,
+    pub submitter: Option<String>,
+    pub args: Vec<SubmissionArg>,
+    pub stellar_cli_args: Vec<String>,
+    pub stellar_invoke_command: String,
+}
+
+/// Everything needed to call `submit_team_result(...)` for a proven team match.
+#[derive(Debug, Serialize)]
+pub struct TeamSubmission {
+    pub contract_method: &'static str,
+    pub session_id: u32,
+    /// The winning team, 1 or 2.
+    pub winner: u8,
+    pub total_moves: u32,
+    pub board_hashes: [String; 2],
+    pub player_keys: Option<[String; 4]>,
+    pub journal_hex: String,
+    pub seal_hex: Option<String>,
+    pub image_id_hex: String,
+    pub selector_hex: Option<String>,
+    pub submitter: Option<String>,
+    pub args: Vec<SubmissionArg>,
+    pub stellar_cli_args: Vec<String>,
+    pub stellar_invoke_command: String,
+}
+
+pub fn parse_submitter(value: &str) -> Result<String, String> {
+    ScAddress::from_str(value)
+        .map(|_| value.to_string())
+        .map_err(|_| format!("--submitter must be a valid Stellar address, got '{value}'"))
+}
+
+fn scval_base64(value: ScVal) -> Result<String, String> {
+    value
+        .to_xdr_base64(Limits::none())
+        .map_err(|e| format!("failed to encode ScVal: {e}"))
+}
+
+fn bytes_arg(name: &'static str, bytes: &[u8]) -> Result<SubmissionArg, String> {
+    let value = ScVal::try_from(bytes)
+        .map_err(|_| format!("argument '{name}' is too large for ScVal::Bytes"))?;
+    Ok(SubmissionArg {
+        name,
+        cli_value: hex::encode(bytes),
+        scval_xdr_base64: Some(scval_base64(value)?),
+    })
+}
+
+fn u32_arg(name: &'static str, value: u32) -> Result<SubmissionArg, String> {
+    Ok(SubmissionArg {
+        name,
+        cli_value: value.to_string(),
+        scval_xdr_base64: Some(scval_base64(ScVal::U32(value))?),
+    })
+}
+
+/// A `Vec<u32>` argument, written as a JSON array for the stellar CLI.
+fn u32_vec_arg(name: &'static str, values: &[u32]) -> Result<SubmissionArg, String> {
+    let items: Vec<ScVal> = values.iter().map(|&value| ScVal::U32(value)).collect();
+    let vec = ScVec::try_from(items).map_err(|_| format!("argument '{name}' is too long"))?;
+    Ok(SubmissionArg {
+        name,
+        cli_value: format!("{values:?}").replace(' ', ""),
+        scval_xdr_base64: Some(scval_base64(ScVal::Vec(Some(vec)))?),
+    })
+}
+
+/// A `Vec<BytesN<32>>` argument, written as a JSON array of hex strings for the stellar CLI.
+fn hashes_arg(name: &'static str, hashes: &[Vec<u8>]) -> Result<SubmissionArg, String> {
+    let items = hashes
+        .iter()
+        .map(|hash| {
+            ScVal::try_from(hash.as_slice())
+                .map_err(|_| format!("argument '{name}' is too large for ScVal::Bytes"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let vec = ScVec::try_from(items).map_err(|_| format!("argument '{name}' is too long"))?;
+    let hex: Vec<String> = hashes.iter().map(hex::encode).collect();
+    Ok(SubmissionArg {
+        name,
+        cli_value: serde_json::to_string(&hex)
+            .map_err(|e| format!("failed to encode '{name}': {e}"))?,
+        scval_xdr_base64: Some(scval_base64(ScVal::Vec(Some(vec)))?),
+    })
+}
+
+fn submitter_arg(submitter: Option<&str>) -> Result<SubmissionArg, String> {
+    match submitter {
+        Some(address) => {
+            let parsed = ScAddress::from_str(address)
+                .map_err(|_| format!("invalid submitter address '{address}'"))?;
+            Ok(SubmissionArg {
+                name: "submitter",
+                cli_value: address.to_string(),
+                scval_xdr_base64: Some(scval_base64(ScVal::Address(parsed))?),
+            })
+        }
+        None => Ok(SubmissionArg {
+            name: "submitter",
+            cli_value: String::from(SUBMITTER_PLACEHOLDER),
+            scval_xdr_base64: None,
+        }),
+    }
+}
+
+fn decode_hash(name: &str, value: &str) -> Result<Vec<u8>, String> {
+    let bytes = hex::decode(value).map_err(|e| format!("{name} is not valid hex: {e}"))?;
+    if bytes.len() != 32 {
+        return Err(format!("{name} must be 32 bytes, got {}", bytes.len()));
+    }
+    Ok(bytes)
+}
+
+fn verifier_seal(groth16_seal: Option<&[u8]>, selector: Option<[u8; 4]>) -> Option<Vec<u8>> {
+    match (groth16_seal, selector) {
+        (Some(seal), Some(selector)) => {
+            let mut bytes = selector.to_vec();
+            bytes.extend_from_slice(seal);
+            Some(bytes)
+        }
+        _ => None,
+    }
+}
+
+/// The stellar CLI arguments and full `invoke` command for calling `method` with `args`.
+fn invoke_command(method: &str, args: &[SubmissionArg]) -> (Vec<String>, String) {
+    let mut stellar_cli_args = vec![String::from(method)];
+    for arg in args {
+        stellar_cli_args.push(format!("--{}", arg.name));
+        stellar_cli_args.push(arg.cli_value.clone());
+    }
+
+    let stellar_invoke_command = format!(
+        "stellar contract invoke --id <CONTRACT_ID> --source-account <SOURCE_ACCOUNT> --network testnet -- {}",
+        stellar_cli_args.join(" ")
+    );
+    (stellar_cli_args, stellar_invoke_command)
+}
+
+pub fn build_submission_bundle(
+    output: &PublicOutputJson,
+    journal: &[u8],
+    groth16_seal: Option<&[u8]>,
+    selector: Option<[u8; 4]>,
+    image_id: &[u8],
+    submitter: Option<&str>,
+) -> Result<SubmissionBundle, String> {
+    let board_hash_p1 = decode_hash("board_hash_p1", &output.board_hash_p1)?;
+    let board_hash_p2 = decode_hash("board_hash_p2", &output.board_hash_p2)?;
+    let Some([p1, p2]) = &output.player_keys else {
+        return Err(String::from(
+            "the game is unsigned: submit_result only settles games whose moves are signed \
+             with both player keys (see game-input.signed.example.json)",
+        ));
+    };
+    let player_key_p1 = decode_hash("player_keys[0]", p1)?;
+    let player_key_p2 = decode_hash("player_keys[1]", p2)?;
+
+    let seal = verifier_seal(groth16_seal, selector);
+
+    let args = vec![
+        u32_arg("session_id", output.session_id)?,
+        submitter_arg(submitter)?,
+        u32_arg("winner", u32::from(output.winner))?,
+        u32_arg("outcome", output.outcome.code())?,
+        u32_arg("total_moves", output.total_moves)?,
+        bytes_arg("board_hash_p1", &board_hash_p1)?,
+        bytes_arg("board_hash_p2", &board_hash_p2)?,
+        bytes_arg("player_key_p1", &player_key_p1)?,
+        bytes_arg("player_key_p2", &player_key_p2)?,
+        bytes_arg("journal", journal)?,
+        bytes_arg("seal", seal.as_deref().unwrap_or_default())?,
+    ];
+
+    let (stellar_cli_args, stellar_invoke_command) = invoke_command(SUBMIT_METHOD, &args);
+
+    Ok(SubmissionBundle {
+        contract_method: SUBMIT_METHOD,
+        session_id: output.session_id,
+        winner: output.winner,
+        outcome: output.outcome.code(),
+        total_moves: output.total_moves,
+        board_hash_p1: output.board_hash_p1.clone(),
+        board_hash_p2: output.board_hash_p2.clone(),
+        player_keys: output.player_keys.clone(),
+        journal_hex: hex::encode(journal),
+        seal_hex: seal.as_deref().map(hex::encode),
+        image_id_hex: hex::encode(image_id),
+        selector_hex: selector.map(hex::encode),
+        submitter: submitter.map(String::from),
+        args,
+        stellar_cli_args,
+        stellar_invoke_command,
+    })
+}
+
+pub fn build_free_for_all_submission(
+    output: &FreeForAllOutputJson,
+    journal: &[u8],
+    groth16_seal: Option<&[u8]>,
+    selector: Option<[u8; 4]>,
+    image_id: &[u8],
+    submitter: Option<&str>,
+) -> Result<FreeForAllSubmission, String> {
+    if output.ranking.is_empty() {
+        return Err(String::from("the free-for-all has no winner yet"));
+    }
+    let board_hashes = output
+        .board_hashes
+        .iter()
+        .enumerate()
+        .map(|(index, hash)| decode_hash(&format!("board_hashes[{index}]"), hash))
+        .collect::<Result<Vec<_>, _>>()?;
+    let player_keys = match &output.player_keys {
+        Some(keys) => keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| decode_hash(&format!("player_keys[{index}]"), key))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => {
+            return Err(String::from(
+                "the free-for-all is unsigned: submit_free_for_all_result only settles games \
+                 whose moves are signed with every player's key",
+            ))
+        }
+    };
+    let ranking: Vec<u32> = output.ranking.iter().map(|&seat| u32::from(seat)).collect();
+    let seal = verifier_seal(groth16_seal, selector);
+
+    let args = vec![
+        u32_arg("session_id", output.session_id)?,
+        submitter_arg(submitter)?,
+        u32_vec_arg("ranking", &ranking)?,
+        u32_arg("total_moves", output.total_moves)?,
+        hashes_arg("board_hashes", &board_hashes)?,
+        hashes_arg("player_keys", &player_keys)?,
+        bytes_arg("journal", journal)?,
+        bytes_arg("seal", seal.as_deref().unwrap_or_default())?,
+    ];
+    let (stellar_cli_args, stellar_invoke_command) =
+        invoke_command(SUBMIT_FREE_FOR_ALL_METHOD, &args);
+
+    Ok(FreeForAllSubmission {
+        contract_method: SUBMIT_FREE_FOR_ALL_METHOD,
+        session_id: output.session_id,
+        ranking: output.ranking.clone(),
+        total_moves: output.total_moves,
+        board_hashes: output.board_hashes.clone(),
+        player_keys: output.player_keys.clone(),
+        journal_hex: hex::encode(journal),
+        seal_hex: seal.as_deref().map(hex::encode),
+        image_id_hex: hex::encode(image_id),
+        selector_hex: selector.map(hex::encode),
+        submitter: submitter.map(String::from),
+        args,
+        stellar_cli_args,
+        stellar_invoke_command,
+    })
+}
+
+pub fn build_team_submission(
+    output: &TeamOutputJson,
+    journal: &[u8],
+    groth16_seal: Option<&[u8]>,
+    selector: Option<[u8; 4]>,
+    image_id: &[u8],
+    submitter: Option<&str>,
+) -> Result<TeamSubmission, String> {
+    if output.winner == 0 {
+        return Err(String::from("the team match has no winner yet"));
+    }
+    let board_hashes = output
+        .board_hashes
+        .iter()
+        .enumerate()
+        .map(|(index, hash)| decode_hash(&format!("board_hashes[{index}]"), hash))
+        .collect::<Result<Vec<_>, _>>()?;
+    let player_keys = match &output.player_keys {
+        Some(keys) => keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| decode_hash(&format!("player_keys[{index}]"), key))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => {
+            return Err(String::from(
+                "the team match is unsigned: submit_team_result only settles games \
+                 whose moves are signed with every player's key",
+            ))
+        }
+    };
+    let seal = verifier_seal(groth16_seal, selector);
+
+    let args = vec![
+        u32_arg("session_id", output.session_id)?,
+        submitter_arg(submitter)?,
+        u32_arg("winner", u32::from(output.winner))?,
+        u32_arg("total_moves", output.total_moves)?,
+        hashes_arg("board_hashes", &board_hashes)?,
+        hashes_arg("player_keys", &player_keys)?,
+        bytes_arg("journal", journal)?,
+        bytes_arg("seal", seal.as_deref().unwrap_or_default())?,
+    ];
+    let (stellar_cli_args, stellar_invoke_command) = invoke_command(SUBMIT_TEAM_METHOD, &args);
+
+    Ok(TeamSubmission {
+        contract_method: SUBMIT_TEAM_METHOD,
+        session_id: output.session_id,
+        winner: output.winner,
+        total_moves: output.total_moves,
+        board_hashes: output.board_hashes.clone(),
+        player_keys: output.player_keys.clone(),
+        journal_hex: hex::encode(journal),
+        seal_hex: seal.as_deref().map(hex::encode),
+        image_id_hex: hex::encode(image_id),
+        selector_hex: selector.map(hex::encode),
+        submitter: submitter.map(String::from),
+        args,
+        stellar_cli_args,
+        stellar_invoke_command,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_output() -> PublicOutputJson {
+        PublicOutputJson {
+            session_id: 42,
+            winner: 1,
+            outcome: crate::Outcome::AllSunk,
+            board_hash_p1: hex::encode([1u8; 32]),
+            board_hash_p2: hex::encode([2u8; 32]),
+            transcript_hash: hex::encode([5u8; 32]),
+            total_moves: 7,
+            mode: crate::GameMode::Demo,
+            no_touching: false,
+            salvo: false,
+            hit_keeps_turn: false,
+            weapons: crate::Weapons::default(),
+            stats_p1: crate::PlayerStats::default(),
+            stats_p2: crate::PlayerStats::default(),
+            sonar: Vec::new(),
+            mines: 0,
+            mine_penalty: crate::MinePenalty::default(),
+            mine_hits: Vec::new(),
+            player_keys: Some([hex::encode([3u8; 32]), hex::encode([4u8; 32])]),
+        }
+    }
+
+    #[test]
+    fn bundle_encodes_submit_result_arguments() {
+        let submitter = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+        let bundle = build_submission_bundle(
+            &sample_output(),
+            &[0xaa, 0xbb],
+            Some(&[0x11; 256]),
+            Some([0x73, 0xc4, 0x57, 0xba]),
+            &[9u8; 32],
+            Some(submitter),
+        )
+        .expect("bundle should build");
+
+        let names: Vec<_> = bundle.args.iter().map(|arg| arg.name).collect();
+        assert_eq!(
+            names,
+            [
+                "session_id",
+                "submitter",
+                "winner",
+                "outcome",
+                "total_moves",
+                "board_hash_p1",
+                "board_hash_p2",
+                "player_key_p1",
+                "player_key_p2",
+                "journal",
+                "seal"
+            ]
+        );
+
+        // ScVal::U32(42) => discriminant 3, value 42.
+        assert_eq!(
+            bundle.args[0].scval_xdr_base64.as_deref(),
+            Some("AAAAAwAAACo=")
+        );
+        // ScVal::Bytes([0xaa, 0xbb]) => discriminant 13, length 2, padded data.
+        assert_eq!(
+            bundle.args[9].scval_xdr_base64.as_deref(),
+            Some("AAAADQAAAAKquwAA")
+        );
+        assert_eq!(bundle.args[3].cli_value, "1");
+        assert_eq!(bundle.args[7].cli_value, hex::encode([3u8; 32]));
+
+        let seal_hex = bundle.seal_hex.expect("seal should be present");
+        assert_eq!(seal_hex.len(), 260 * 2);
+        assert!(seal_hex.starts_with("73c457ba"));
+        assert!(bundle
+            .stellar_invoke_command
+            .contains(&format!("--submitter {submitter}")));
+    }
+
+    #[test]
+    fn bundle_without_submitter_or_seal_uses_placeholders() {
+        let bundle = build_submission_bundle(&sample_output(), &[1], None, None, &[9u8; 32], None)
+            .expect("bundle should build");
+
+        assert_eq!(bundle.args[1].cli_value, SUBMITTER_PLACEHOLDER);
+        assert!(bundle.args[1].scval_xdr_base64.is_none());
+        assert!(bundle.seal_hex.is_none());
+        assert!(bundle.selector_hex.is_none());
+    }
+
+    #[test]
+    fn unsigned_games_have_no_bundle() {
+        let output = PublicOutputJson {
+            player_keys: None,
+            ..sample_output()
+        };
+        let err = build_submission_bundle(&output, &[1], None, None, &[9u8; 32], None)
+            .expect_err("unsigned games cannot settle");
+
+        assert!(err.contains("unsigned"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn free_for_all_bundle_encodes_vectors() {
+        let input = crate::free_for_all_game_input(42);
+        let output = battleship_rules::free_for_all::play_free_for_all(&input).unwrap();
+        let json = FreeForAllOutputJson::from(&output);
+        let bundle = build_free_for_all_submission(&json, &[1], None, None, &[9u8; 32], None)
+            .expect("bundle should build");
+
+        let names: Vec<_> = bundle.args.iter().map(|arg| arg.name).collect();
+        assert_eq!(
+            names,
+            [
+                "session_id",
+                "submitter",
+                "ranking",
+                "total_moves",
+                "board_hashes",
+                "player_keys",
+                "journal",
+                "seal"
+            ]
+        );
+        assert_eq!(bundle.args[2].cli_value, "[1,2,3]");
+        // ScVal::Vec([U32(1), U32(2), U32(3)]) => discriminant 16, present, length 3.
+        assert_eq!(
+            bundle.args[2].scval_xdr_base64.as_deref(),
+            Some("AAAAEAAAAAEAAAADAAAAAwAAAAEAAAADAAAAAgAAAAMAAAAD")
+        );
+        assert!(bundle.args[4]
+            .cli_value
+            .starts_with(&format!("[\"{}\"", json.board_hashes[0])));
+        assert!(bundle
+            .stellar_invoke_command
+            .contains("submit_free_for_all_result --session_id 42"));
+
+        let mut unfinished = json.clone();
+        unfinished.ranking.clear();
+        assert!(
+            build_free_for_all_submission(&unfinished, &[1], None, None, &[9u8; 32], None).is_err()
+        );
+
+        let mut unsigned = json.clone();
+        unsigned.player_keys = None;
+        let err = build_free_for_all_submission(&unsigned, &[1], None, None, &[9u8; 32], None)
+            .expect_err("unsigned free-for-alls cannot settle");
+        assert!(err.contains("unsigned"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn team_bundle_names_the_winning_team() {
+        let input = crate::team_game_input(42);
+        let output = battleship_rules::teams::play_teams(&input).unwrap();
+        let json = TeamOutputJson::from(&output);
+        let bundle = build_team_submission(&json, &[1], None, None, &[9u8; 32], None)
+            .expect("bundle should build");
+
+        assert_eq!(bundle.args[2].name, "winner");
+        assert_eq!(bundle.args[2].cli_value, "1");
+        let keys = json.player_keys.as_ref().unwrap();
+        assert_eq!(
+            bundle.args[5].cli_value,
+            format!("[\"{}\"]", keys.join("\",\""))
+        );
+        assert!(bundle
+            .stellar_invoke_command
+            .contains("submit_team_result --session_id 42"));
+
+        let mut unfinished = json.clone();
+        unfinished.winner = 0;
+        assert!(build_team_submission(&unfinished, &[1], None, None, &[9u8; 32], None).is_err());
+
+        let mut unsigned = json.clone();
+        unsigned.player_keys = None;
+        let err = build_team_submission(&unsigned, &[1], None, None, &[9u8; 32], None)
+            .expect_err("unsigned team matches cannot settle");
+        assert!(err.contains("unsigned"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn invalid_submitter_is_rejected() {
+        assert!(parse_submitter("not-an-address").is_err());
+    }
+}
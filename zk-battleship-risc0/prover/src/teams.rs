@@ -0,0 +1,224 @@
+//! Team proving: the teams guest replays a 2v2 match over two shared boards (see
+//! [`battleship_rules::teams`]) and commits the winning team, which `submit_team_result`
+//! settles as one Game Hub session per pair of opposing players.
+
+use crate::submission::{self, TeamSubmission};
+use crate::{
+    compress_receipt, elapsed_ms, groth16_seal, journal_digest, peak_memory_bytes, receipt_kind,
+    run_guest, transcript, write_atomic, write_json, GameMode, Move, Outcome, PlayerStats,
+    ProofStats, ProveOptions, ProverError, ReceiptKind, RISC0_VERSION, TEAMS_ELF, TEAMS_ID,
+};
+use battleship_rules::teams::{Member, Team, TeamInput, TeamOutput};
+use risc0_zkvm::{sha::Digest, ExecutorEnv, Receipt};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+/// Secret keys the team sample is signed with, players 1 to 4: the three of
+/// [`FREE_FOR_ALL_SECRET_KEYS`](crate::FREE_FOR_ALL_SECRET_KEYS) and a fourth. Public test
+/// values.
+pub const TEAM_SECRET_KEYS: [[u8; 32]; 4] = [[1; 32], [2; 32], [3; 32], [4; 32]];
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TeamOutputJson {
+    pub session_id: u32,
+    /// The winning team, or 0 while the match is unfinished.
+    pub winner: u8,
+    pub outcome: Outcome,
+    pub board_hashes: [String; 2],
+    pub transcript_hash: String,
+    pub total_moves: u32,
+    pub mode: GameMode,
+    pub no_touching: bool,
+    pub stats: [PlayerStats; 2],
+    /// Hex ed25519 keys of players 1 to 4 for a signed match.
+    pub player_keys: Option<[String; 4]>,
+}
+
+impl From<&TeamOutput> for TeamOutputJson {
+    fn from(output: &TeamOutput) -> Self {
+        Self {
+            session_id: output.session_id,
+            winner: output.winner,
+            outcome: output.outcome,
+            board_hashes: output.board_hashes.map(hex::encode),
+            transcript_hash: hex::encode(output.transcript_hash),
+            total_moves: output.total_moves,
+            mode: output.mode,
+            no_touching: output.no_touching,
+            stats: output.stats,
+            player_keys: output.player_keys.map(|keys| keys.map(hex::encode)),
+        }
+    }
+}
+
+/// Contents of the team `proof-output.json`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TeamProofFile {
+    pub journal_hex: String,
+    pub seal_hex: Option<String>,
+    pub public_output: TeamOutputJson,
+    pub image_id_hex: String,
+    /// sha256(journal), the digest `submit_team_result` passes to the verifier.
+    pub journal_digest_hex: String,
+    pub receipt_kind: String,
+    pub risc0_version: String,
+    pub host_version: String,
+    /// sha256 of the compact JSON encoding of the proven `TeamInput`.
+    pub input_hash_hex: String,
+}
+
+/// A proven team match: the receipt plus everything derived from it.
+#[derive(Debug)]
+pub struct TeamArtifact {
+    pub receipt: Receipt,
+    pub output: TeamProofFile,
+    /// Raw Groth16 seal, present only for real Groth16 receipts.
+    pub groth16_seal: Option<Vec<u8>>,
+    pub selector: Option<[u8; 4]>,
+    pub stats: ProofStats,
+}
+
+impl TeamArtifact {
+    /// Builds the `submit_team_result` arguments for this proof.
+    pub fn submission(&self, submitter: Option<&str>) -> Result<TeamSubmission, ProverError> {
+        submission::build_team_submission(
+            &self.output.public_output,
+            &self.receipt.journal.bytes,
+            self.groth16_seal.as_deref(),
+            self.selector,
+            Digest::from(TEAMS_ID).as_bytes(),
+            submitter,
+        )
+        .map_err(ProverError::Bundle)
+    }
+
+    pub fn write_receipt(&self, path: &str) -> Result<(), ProverError> {
+        let bytes = bincode::serialize(&self.receipt)
+            .map_err(|e| ProverError::Io(format!("failed to serialize receipt: {e}")))?;
+        write_atomic(path, &bytes).map_err(ProverError::Io)
+    }
+
+    pub fn write_output(&self, path: &str) -> Result<(), ProverError> {
+        write_json(&self.output, path)
+    }
+}
+
+/// Hex of the teams guest's image id, as stored by `set_team_image_id`.
+pub fn team_image_id_hex() -> String {
+    hex::encode(Digest::from(TEAMS_ID).as_bytes())
+}
+
+/// Built-in 2v2 match (`game-input.teams.example.json`), signed with [`TEAM_SECRET_KEYS`]:
+/// on both teams the first member places the top ship of the sample board and the second
+/// member the lower one, and team 1 sinks team 2 in 7 moves.
+pub fn team_game_input(session_id: u32) -> TeamInput {
+    let team = Team {
+        members: [
+            Member {
+                board: [
+                    1, 1, 0, 0, // y=0 (ship size 2)
+                    0, 0, 0, 0, // y=1
+                    0, 0, 0, 0, // y=2
+                    0, 0, 0, 0, // y=3
+                ],
+                fleet: Vec::new(),
+            },
+            Member {
+                board: [
+                    0, 0, 0, 0, // y=0
+                    0, 0, 0, 0, // y=1
+                    1, 1, 0, 0, // y=2 (ship size 2)
+                    0, 0, 0, 0, // y=3
+                ],
+                fleet: Vec::new(),
+            },
+        ],
+        board_salt: [0; 32],
+    };
+    let mut moves = vec![
+        Move::new(1, 0, 0),
+        Move::new(2, 3, 3),
+        Move::new(3, 1, 0),
+        Move::new(4, 3, 2),
+        Move::new(1, 0, 2),
+        Move::new(2, 2, 3),
+        Move::new(3, 1, 2),
+    ];
+    transcript::sign_moves(session_id, &mut moves, &TEAM_SECRET_KEYS);
+    TeamInput {
+        session_id,
+        mode: GameMode::Demo,
+        no_touching: false,
+        teams: [team.clone(), team],
+        player_keys: Some(TEAM_SECRET_KEYS.map(|key| transcript::public_key(&key))),
+        moves,
+    }
+}
+
+pub fn load_team_input(path: impl AsRef<Path>) -> Result<TeamInput, ProverError> {
+    let path = path.as_ref();
+    let raw = fs::read_to_string(path).map_err(|e| {
+        ProverError::Input(format!(
+            "Failed to read input file '{}': {e}",
+            path.display()
+        ))
+    })?;
+    serde_json::from_str::<TeamInput>(&raw).map_err(|e| {
+        ProverError::Input(format!("Failed to parse JSON in '{}': {e}", path.display()))
+    })
+}
+
+/// Proves a team match and packages the result.
+pub fn prove_teams(input: &TeamInput, opts: &ProveOptions) -> Result<TeamArtifact, ProverError> {
+    if input.session_id == 0 {
+        return Err(ProverError::Input(String::from(
+            "game input session_id must be greater than 0",
+        )));
+    }
+    let canonical = serde_json::to_vec(input)
+        .map_err(|e| ProverError::Input(format!("failed to encode game input: {e}")))?;
+    let input_hash_hex = hex::encode(Sha256::digest(&canonical));
+
+    let env = || {
+        ExecutorEnv::builder()
+            .write(input)
+            .map_err(|e| ProverError::Env(format!("failed to write executor input: {e}")))?
+            .build()
+            .map_err(|e| ProverError::Env(format!("failed to build executor env: {e}")))
+    };
+    let mut stats = ProofStats::default();
+    let (receipt, public_output): (Receipt, TeamOutput) =
+        run_guest(env, TEAMS_ELF, TEAMS_ID, &mut stats)?;
+
+    let started = Instant::now();
+    let receipt = compress_receipt(receipt, opts.receipt_kind)?;
+    if opts.receipt_kind != ReceiptKind::Composite {
+        stats.compress_ms = elapsed_ms(started);
+    }
+    stats.peak_memory_bytes = peak_memory_bytes();
+
+    let (groth16_seal, selector) = groth16_seal(&receipt);
+    let journal = &receipt.journal.bytes;
+    let output = TeamProofFile {
+        journal_hex: hex::encode(journal),
+        seal_hex: groth16_seal.as_deref().map(hex::encode),
+        public_output: TeamOutputJson::from(&public_output),
+        image_id_hex: team_image_id_hex(),
+        journal_digest_hex: hex::encode(journal_digest(journal)),
+        receipt_kind: String::from(receipt_kind(&receipt)),
+        risc0_version: String::from(RISC0_VERSION),
+        host_version: String::from(env!("CARGO_PKG_VERSION")),
+        input_hash_hex,
+    };
+
+    Ok(TeamArtifact {
+        receipt,
+        output,
+        groth16_seal,
+        selector,
+        stats,
+    })
+}
@@ -0,0 +1,135 @@
+//! Turn-by-turn proving: the turn guest proves each move as it is played, and the
+//! aggregation guest folds the turn receipts into one match receipt (see
+//! [`battleship_rules::turn`]).
+
+use crate::{
+    input_hash, package, run_guest, write_atomic, GameInput, Move, ProofArtifact, ProofStats,
+    ProveOptions, ProverError, AGGREGATE_ELF, AGGREGATE_ID, TURN_ELF, TURN_ID,
+};
+use battleship_rules::turn::{turn, MatchState, TurnJournal};
+use risc0_zkvm::{default_prover, ExecutorEnv, ProverOpts, Receipt};
+use std::fs;
+use std::path::Path;
+
+/// Proves that `mv` takes the match from `state` to the next state.
+///
+/// Turn receipts are succinct, which the aggregation guest's assumptions resolve cheaply.
+pub fn prove_turn(state: &MatchState, mv: &Move) -> Result<(Receipt, TurnJournal), ProverError> {
+    let index = state.moves_applied();
+    // Replaying natively first reports an illegal move without proving anything.
+    let expected = turn(&mut state.clone(), mv)
+        .map_err(|e| ProverError::Input(format!("move {index}: {e}")))?;
+
+    let env = ExecutorEnv::builder()
+        .write(state)
+        .and_then(|builder| builder.write(mv))
+        .map_err(|e| ProverError::Env(format!("failed to write executor input: {e}")))?
+        .build()
+        .map_err(|e| ProverError::Env(format!("failed to build executor env: {e}")))?;
+    let receipt = default_prover()
+        .prove_with_opts(env, TURN_ELF, &ProverOpts::succinct())
+        .map_err(|e| ProverError::Prove(format!("turn {index} prove failed: {e}")))?
+        .receipt;
+
+    check_turn_receipt(&receipt, &expected)?;
+    Ok((receipt, expected))
+}
+
+/// Checks that `receipt` is a turn receipt committing to `expected`.
+pub fn check_turn_receipt(receipt: &Receipt, expected: &TurnJournal) -> Result<(), ProverError> {
+    let index = expected.index;
+    receipt.verify(TURN_ID).map_err(|e| {
+        ProverError::Verify(format!("turn {index} receipt verification failed: {e}"))
+    })?;
+    let journal: TurnJournal = receipt
+        .journal
+        .decode()
+        .map_err(|e| ProverError::Verify(format!("turn {index} journal decode failed: {e}")))?;
+    if journal != *expected {
+        return Err(ProverError::Verify(format!(
+            "turn {index} receipt proves another state or move"
+        )));
+    }
+    Ok(())
+}
+
+pub fn read_turn_receipt(path: &Path) -> Result<Receipt, ProverError> {
+    let bytes = fs::read(path).map_err(|e| {
+        ProverError::Input(format!(
+            "Failed to read turn receipt '{}': {e}",
+            path.display()
+        ))
+    })?;
+    bincode::deserialize(&bytes).map_err(|e| {
+        ProverError::Input(format!(
+            "Failed to decode turn receipt '{}': {e}",
+            path.display()
+        ))
+    })
+}
+
+pub fn write_turn_receipt(receipt: &Receipt, path: &str) -> Result<(), ProverError> {
+    let bytes = bincode::serialize(receipt)
+        .map_err(|e| ProverError::Io(format!("failed to serialize turn receipt: {e}")))?;
+    write_atomic(path, &bytes).map_err(ProverError::Io)
+}
+
+/// Folds one turn receipt per move of `input`, in order, into a match proof.
+///
+/// The journal is the one [`prove`](crate::prove) commits for the whole game, so the
+/// artifact settles through `submit_result` once the contract's image id is `AGGREGATE_ID`.
+pub fn aggregate(
+    input: &GameInput,
+    turns: &[Receipt],
+    opts: &ProveOptions,
+) -> Result<ProofArtifact, ProverError> {
+    if input.session_id == 0 {
+        return Err(ProverError::Input(String::from(
+            "game input session_id must be greater than 0",
+        )));
+    }
+    if turns.len() != input.moves.len() {
+        return Err(ProverError::Input(format!(
+            "{} turn receipts for {} moves",
+            turns.len(),
+            input.moves.len()
+        )));
+    }
+    let input_hash_hex = hex::encode(input_hash(input)?);
+
+    let mut last = MatchState::new(input).map_err(|e| ProverError::Input(e.to_string()))?;
+    let mut journals = Vec::with_capacity(turns.len());
+    for (index, (mv, receipt)) in input.moves.iter().zip(turns).enumerate() {
+        let expected =
+            turn(&mut last, mv).map_err(|e| ProverError::Input(format!("move {index}: {e}")))?;
+        check_turn_receipt(receipt, &expected)?;
+        journals.push(expected);
+    }
+
+    let mut setup = input.clone();
+    setup.moves.clear();
+    let env = || {
+        let mut builder = ExecutorEnv::builder();
+        for receipt in turns {
+            builder.add_assumption(receipt.clone());
+        }
+        builder
+            .write(&setup)
+            .and_then(|builder| builder.write(&journals))
+            .and_then(|builder| builder.write(&last))
+            .map_err(|e| ProverError::Env(format!("failed to write executor input: {e}")))?
+            .build()
+            .map_err(|e| ProverError::Env(format!("failed to build executor env: {e}")))
+    };
+
+    let mut stats = ProofStats::default();
+    let (receipt, public_output) = run_guest(env, AGGREGATE_ELF, AGGREGATE_ID, &mut stats)?;
+    package(
+        receipt,
+        &public_output,
+        AGGREGATE_ID,
+        input_hash_hex,
+        opts,
+        stats,
+    )
+}
@@ -0,0 +1,273 @@
+//! Bitboards: one bit per cell, row-major, bit `y * width + x`.
+//!
+//! `u16` covers the 4x4 board; wider rulesets use `u128`.
+
+use std::ops::{BitAnd, BitOr, Not, Shl, Shr};
+
+pub trait Bits:
+    Copy
+    + Eq
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + Not<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const BITS: u32;
+
+    fn count(self) -> u32;
+
+    fn trailing_zeros(self) -> u32;
+
+    /// The lowest set bit alone, or zero.
+    fn lowest(self) -> Self;
+}
+
+macro_rules! impl_bits {
+    ($($ty:ty),*) => {$(
+        impl Bits for $ty {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const BITS: u32 = <$ty>::BITS;
+
+            fn count(self) -> u32 {
+                self.count_ones()
+            }
+
+            fn trailing_zeros(self) -> u32 {
+                <$ty>::trailing_zeros(self)
+            }
+
+            fn lowest(self) -> Self {
+                self & self.wrapping_neg()
+            }
+        }
+    )*};
+}
+
+impl_bits!(u16, u32, u64, u128);
+
+/// Geometry of a `width` x `height` board stored in `B`.
+#[derive(Debug, Clone, Copy)]
+pub struct Grid<B> {
+    width: u32,
+    height: u32,
+    all: B,
+    first_row: B,
+    first_column: B,
+    last_column: B,
+}
+
+impl<B: Bits> Grid<B> {
+    pub fn new(width: u32, height: u32) -> Self {
+        assert!(width * height <= B::BITS, "board does not fit the bitboard");
+
+        let mut all = B::ZERO;
+        let mut first_row = B::ZERO;
+        let mut first_column = B::ZERO;
+        let mut last_column = B::ZERO;
+        for y in 0..height {
+            first_column = first_column | (B::ONE << (y * width));
+            last_column = last_column | (B::ONE << (y * width + width - 1));
+            for x in 0..width {
+                all = all | (B::ONE << (y * width + x));
+            }
+        }
+        for x in 0..width {
+            first_row = first_row | (B::ONE << x);
+        }
+
+        Self {
+            width,
+            height,
+            all,
+            first_row,
+            first_column,
+            last_column,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Every cell on the board.
+    pub fn all(&self) -> B {
+        self.all
+    }
+
+    /// The bit for `(x, y)`, or `None` when it is off the board.
+    pub fn cell(&self, x: u32, y: u32) -> Option<B> {
+        (x < self.width && y < self.height).then(|| B::ONE << (y * self.width + x))
+    }
+
+    /// Every cell of row `y`, or `None` when it is off the board.
+    pub fn row(&self, y: u32) -> Option<B> {
+        (y < self.height).then(|| self.first_row << (y * self.width))
+    }
+
+    /// Every cell of column `x`, or `None` when it is off the board.
+    pub fn column(&self, x: u32) -> Option<B> {
+        (x < self.width).then(|| self.first_column << x)
+    }
+
+    /// Whether every cell of `set` lies in one row or one column.
+    pub fn is_line(&self, set: B) -> bool {
+        if set == B::ZERO {
+            return false;
+        }
+        let idx = set.trailing_zeros();
+        let row = self.first_row << (idx - idx % self.width);
+        let column = self.first_column << (idx % self.width);
+        set & row == set || set & column == set
+    }
+
+    /// Cells orthogonally adjacent to any cell of `set`, excluding wrap-around across rows.
+    pub fn neighbors(&self, set: B) -> B {
+        let east = (set & !self.last_column) << 1;
+        let west = (set & !self.first_column) >> 1;
+        let south = set << self.width;
+        let north = set >> self.width;
+        (east | west | south | north) & self.all
+    }
+
+    /// Cells touching `set` orthogonally or diagonally, excluding `set` itself.
+    pub fn surrounding(&self, set: B) -> B {
+        let row = set | ((set & !self.last_column) << 1) | ((set & !self.first_column) >> 1);
+        let block = row | (row << self.width) | (row >> self.width);
+        block & self.all & !set
+    }
+
+    /// The orthogonally connected part of `board` containing `seed`.
+    pub fn component(&self, board: B, seed: B) -> B {
+        let mut component = seed & board;
+        loop {
+            let grown = (component | self.neighbors(component)) & board;
+            if grown == component {
+                return component;
+            }
+            component = grown;
+        }
+    }
+
+    /// Splits `board` into its orthogonally connected components.
+    pub fn components(&self, board: B) -> Components<'_, B> {
+        Components {
+            grid: self,
+            remaining: board,
+        }
+    }
+}
+
+pub struct Components<'a, B> {
+    grid: &'a Grid<B>,
+    remaining: B,
+}
+
+impl<B: Bits> Iterator for Components<'_, B> {
+    type Item = B;
+
+    fn next(&mut self) -> Option<B> {
+        if self.remaining == B::ZERO {
+            return None;
+        }
+        let component = self.grid.component(self.remaining, self.remaining.lowest());
+        self.remaining = self.remaining & !component;
+        Some(component)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(grid: &Grid<u16>, cells: &[(u32, u32)]) -> u16 {
+        cells
+            .iter()
+            .fold(0, |acc, &(x, y)| acc | grid.cell(x, y).unwrap())
+    }
+
+    #[test]
+    fn neighbors_do_not_wrap_across_rows() {
+        let grid = Grid::<u16>::new(4, 4);
+        assert_eq!(
+            grid.neighbors(bits(&grid, &[(3, 1)])),
+            bits(&grid, &[(2, 1), (3, 0), (3, 2)])
+        );
+        assert_eq!(
+            grid.neighbors(bits(&grid, &[(0, 0)])),
+            bits(&grid, &[(1, 0), (0, 1)])
+        );
+        assert_eq!(grid.cell(4, 0), None);
+    }
+
+    #[test]
+    fn surrounding_includes_diagonals_without_wrapping() {
+        let grid = Grid::<u16>::new(4, 4);
+        assert_eq!(
+            grid.surrounding(bits(&grid, &[(3, 0)])),
+            bits(&grid, &[(2, 0), (2, 1), (3, 1)])
+        );
+        assert_eq!(grid.surrounding(bits(&grid, &[(1, 1)])).count(), 8);
+    }
+
+    #[test]
+    fn components_split_diagonal_and_separate_ships() {
+        let grid = Grid::<u16>::new(4, 4);
+        let board = bits(&grid, &[(0, 0), (1, 0), (2, 1), (3, 3)]);
+        let components: Vec<u16> = grid.components(board).collect();
+        assert_eq!(
+            components,
+            [
+                bits(&grid, &[(0, 0), (1, 0)]),
+                bits(&grid, &[(2, 1)]),
+                bits(&grid, &[(3, 3)])
+            ]
+        );
+    }
+
+    #[test]
+    fn lines_are_single_row_or_column() {
+        let grid = Grid::<u128>::new(10, 10);
+        let cells = |cells: &[(u32, u32)]| {
+            cells
+                .iter()
+                .fold(0u128, |acc, &(x, y)| acc | grid.cell(x, y).unwrap())
+        };
+        assert!(grid.is_line(cells(&[(7, 3), (8, 3), (9, 3)])));
+        assert!(grid.is_line(cells(&[(9, 7), (9, 8), (9, 9)])));
+        assert!(!grid.is_line(cells(&[(0, 0), (1, 0), (1, 1)])));
+        assert!(!grid.is_line(cells(&[(9, 0), (0, 1)])));
+        assert!(!grid.is_line(0));
+    }
+
+    #[test]
+    fn rows_and_columns_stay_on_the_board() {
+        let grid = Grid::<u16>::new(4, 4);
+        assert_eq!(
+            grid.row(2),
+            Some(bits(&grid, &[(0, 2), (1, 2), (2, 2), (3, 2)]))
+        );
+        assert_eq!(
+            grid.column(3),
+            Some(bits(&grid, &[(3, 0), (3, 1), (3, 2), (3, 3)]))
+        );
+        assert_eq!(grid.row(4), None);
+        assert_eq!(grid.column(4), None);
+    }
+
+    #[test]
+    fn wide_boards_fit_u128() {
+        let grid = Grid::<u128>::new(10, 10);
+        assert_eq!(grid.all().count(), 100);
+        let corner = grid.cell(9, 9).unwrap();
+        assert_eq!(grid.neighbors(corner).count(), 2);
+        assert_eq!(grid.component(grid.all(), corner), grid.all());
+    }
+}
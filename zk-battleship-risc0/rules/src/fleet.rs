@@ -0,0 +1,146 @@
+//! Ship lists and their rasterization onto a bitboard.
+
+use crate::bitboard::{Bits, Grid};
+use crate::RuleError;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Orientation {
+    /// Extends towards increasing `x`.
+    Horizontal,
+    /// Extends towards increasing `y`.
+    Vertical,
+}
+
+/// A straight ship starting at `(x, y)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Ship {
+    pub x: u8,
+    pub y: u8,
+    pub orientation: Orientation,
+    pub length: u8,
+}
+
+impl Ship {
+    /// The cells this ship covers, or `None` if any of them is off the board.
+    pub fn cells<B: Bits>(&self, grid: &Grid<B>) -> Option<B> {
+        if self.length == 0 {
+            return None;
+        }
+        let mut cells = B::ZERO;
+        for step in 0..u32::from(self.length) {
+            let (x, y) = match self.orientation {
+                Orientation::Horizontal => (u32::from(self.x) + step, u32::from(self.y)),
+                Orientation::Vertical => (u32::from(self.x), u32::from(self.y) + step),
+            };
+            cells = cells | grid.cell(x, y)?;
+        }
+        Some(cells)
+    }
+}
+
+/// Places `fleet` on an empty board and returns the cells of each ship, in `fleet` order.
+///
+/// `lengths` is the required fleet, in any order. Ships must stay on the board and must not
+/// overlap; with `no_touching` they may not share an edge or a corner either.
+pub fn rasterize<B: Bits>(
+    grid: &Grid<B>,
+    fleet: &[Ship],
+    lengths: &[u8],
+    no_touching: bool,
+    player: u8,
+) -> Result<Vec<B>, RuleError> {
+    let mut expected = lengths.to_vec();
+    let mut actual: Vec<u8> = fleet.iter().map(|ship| ship.length).collect();
+    expected.sort_unstable();
+    actual.sort_unstable();
+    if expected != actual {
+        return Err(RuleError::FleetComposition { player });
+    }
+
+    let mut board = B::ZERO;
+    let mut ships = Vec::with_capacity(fleet.len());
+    for ship in fleet {
+        let cells = ship
+            .cells(grid)
+            .ok_or(RuleError::ShipOutOfBounds { player })?;
+        if board & cells != B::ZERO {
+            return Err(RuleError::ShipOverlap { player });
+        }
+        if no_touching && grid.surrounding(cells) & board != B::ZERO {
+            return Err(RuleError::ShipsTouching { player });
+        }
+        board = board | cells;
+        ships.push(cells);
+    }
+    Ok(ships)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLEET: [u8; 3] = [3, 2, 2];
+
+    fn ship(x: u8, y: u8, orientation: Orientation, length: u8) -> Ship {
+        Ship {
+            x,
+            y,
+            orientation,
+            length,
+        }
+    }
+
+    #[test]
+    fn ships_are_rasterized_along_their_orientation() {
+        let grid = Grid::<u128>::new(10, 10);
+        let fleet = [
+            ship(0, 0, Orientation::Horizontal, 3),
+            ship(9, 8, Orientation::Vertical, 2),
+            ship(5, 5, Orientation::Vertical, 2),
+        ];
+        let ships = rasterize(&grid, &fleet, &FLEET, true, 1).unwrap();
+        assert_eq!(
+            ships.iter().map(|ship| ship.count()).collect::<Vec<_>>(),
+            [3, 2, 2]
+        );
+        let board = ships.iter().fold(0, |acc, &ship| acc | ship);
+        assert_eq!(board.count(), 7);
+        for (x, y) in [(0, 0), (1, 0), (2, 0), (9, 8), (9, 9), (5, 5), (5, 6)] {
+            assert_ne!(board & grid.cell(x, y).unwrap(), 0, "({x}, {y}) not set");
+        }
+    }
+
+    #[test]
+    fn invalid_fleets_are_rejected() {
+        let grid = Grid::<u128>::new(10, 10);
+        let h = Orientation::Horizontal;
+        let v = Orientation::Vertical;
+
+        let wrong_lengths = [ship(0, 0, h, 3), ship(0, 2, h, 3), ship(0, 4, h, 2)];
+        assert_eq!(
+            rasterize(&grid, &wrong_lengths, &FLEET, false, 2),
+            Err(RuleError::FleetComposition { player: 2 })
+        );
+
+        let off_board = [ship(8, 0, h, 3), ship(0, 2, h, 2), ship(0, 4, h, 2)];
+        assert_eq!(
+            rasterize(&grid, &off_board, &FLEET, false, 1),
+            Err(RuleError::ShipOutOfBounds { player: 1 })
+        );
+
+        let overlapping = [ship(0, 0, h, 3), ship(1, 0, v, 2), ship(0, 4, h, 2)];
+        assert_eq!(
+            rasterize(&grid, &overlapping, &FLEET, false, 1),
+            Err(RuleError::ShipOverlap { player: 1 })
+        );
+
+        let diagonal = [ship(0, 0, h, 3), ship(3, 1, v, 2), ship(0, 4, h, 2)];
+        assert!(rasterize(&grid, &diagonal, &FLEET, false, 1).is_ok());
+        assert_eq!(
+            rasterize(&grid, &diagonal, &FLEET, true, 1),
+            Err(RuleError::ShipsTouching { player: 1 })
+        );
+    }
+}
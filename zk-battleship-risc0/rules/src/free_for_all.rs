@@ -0,0 +1,454 @@
+//! Free-for-all: three or four players on the boards of either mode, each shot fired at an
+//! opponent of the shooter's choice.
+//!
+//! Players move in seat order, skipping anyone who is out. A move is a single shot at the
+//! board of its `target`, or a resignation (at any time, out of turn too). A player is out
+//! once their last ship is sunk or they resign, and the last player standing wins. Hits are
+//! public: once anyone has shot a cell of a board, nobody may shoot it again, and a ship is
+//! sunk by whoever hits its last cell. Salvo, hit-keeps-turn and special weapons are
+//! two-player rules only.
+//!
+//! The journal ranks every player: the winner first, then the others in reverse order of
+//! going out.
+
+use crate::bitboard::{Bits, Grid};
+use crate::transcript::{self, PublicKey};
+use crate::{
+    cell_map, classic_grid, demo_board, fleet, grid, merkle, occupied, Action, GameMode, Move,
+    Outcome, PlayerStats, RuleError, Ship, CLASSIC_FLEET,
+};
+use serde::{Deserialize, Serialize};
+
+pub const MIN_PLAYERS: usize = 3;
+pub const MAX_PLAYERS: usize = 4;
+
+/// One player's board, given like `board_p1`/`fleet_p1` of a two-player game.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Seat {
+    #[serde(default)]
+    pub board: [u8; 16],
+    #[serde(default)]
+    pub fleet: Vec<Ship>,
+    /// Secret salt of the board commitment (see [`merkle`]).
+    #[serde(default)]
+    pub board_salt: [u8; 32],
+}
+
+/// A free-for-all match, as read by the free-for-all guest.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FreeForAllInput {
+    pub session_id: u32,
+    #[serde(default)]
+    pub mode: GameMode,
+    #[serde(default)]
+    pub no_touching: bool,
+    /// Player `n` sits at `seats[n - 1]`.
+    pub seats: Vec<Seat>,
+    /// Ed25519 public keys in seat order. When set, every move must be signed by its player.
+    #[serde(default)]
+    pub player_keys: Option<Vec<PublicKey>>,
+    /// Shots carry the `target` they are fired at.
+    pub moves: Vec<Move>,
+}
+
+/// Journal committed by the free-for-all guest.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FreeForAllOutput {
+    pub session_id: u32,
+    pub players: u8,
+    /// The last player standing, or 0 while two or more are left.
+    pub winner: u8,
+    /// How the last opponent went out, or `Unfinished`.
+    pub outcome: Outcome,
+    /// Every player, winner first, then in reverse order of going out; empty until the
+    /// match is won.
+    pub ranking: Vec<u8>,
+    /// [`merkle::board_root`] of each seat's board and salt, in seat order.
+    pub board_hashes: Vec<[u8; 32]>,
+    /// [`transcript::transcript_hash`] of `moves`.
+    pub transcript_hash: [u8; 32],
+    pub total_moves: u32,
+    pub mode: GameMode,
+    pub no_touching: bool,
+    /// Each player's shots, at whichever opponents they fired at.
+    pub stats: Vec<PlayerStats>,
+    pub player_keys: Option<Vec<PublicKey>>,
+}
+
+/// Progress of a free-for-all between two moves.
+struct Match<B> {
+    /// Cells shot on each player's board, by anyone.
+    shots: Vec<B>,
+    out: Vec<bool>,
+    /// Players in the order they went out.
+    eliminated: Vec<u8>,
+    expected_player: u8,
+    winner: u8,
+    outcome: Outcome,
+    total_moves: u32,
+    stats: Vec<PlayerStats>,
+}
+
+impl<B: Bits> Match<B> {
+    fn new(players: usize) -> Self {
+        Self {
+            shots: vec![B::ZERO; players],
+            out: vec![false; players],
+            eliminated: Vec::new(),
+            expected_player: 1,
+            winner: 0,
+            outcome: Outcome::Unfinished,
+            total_moves: 0,
+            stats: vec![PlayerStats::default(); players],
+        }
+    }
+
+    /// Whether `player` is seated and still in the match.
+    fn standing(&self, player: u8) -> bool {
+        player != 0 && self.out.get(usize::from(player - 1)) == Some(&false)
+    }
+
+    /// The first standing player after `player` in seat order.
+    fn next_after(&self, player: u8) -> u8 {
+        let players = self.out.len() as u8;
+        (1..=players)
+            .map(|step| (player - 1 + step) % players + 1)
+            .find(|&next| self.standing(next))
+            .unwrap_or(player)
+    }
+
+    fn knock_out(&mut self, player: u8, outcome: Outcome) {
+        self.out[usize::from(player - 1)] = true;
+        self.eliminated.push(player);
+        let mut standing = (1..=self.out.len() as u8).filter(|&p| self.standing(p));
+        if let (Some(last), None) = (standing.next(), standing.next()) {
+            self.winner = last;
+            self.outcome = outcome;
+        }
+    }
+
+    fn step(&mut self, grid: &Grid<B>, ships: &[Vec<B>], mv: &Move) -> Result<(), RuleError> {
+        if self.winner != 0 {
+            return Err(RuleError::MovesAfterGameOver);
+        }
+        if !self.standing(mv.player) {
+            return Err(RuleError::TurnOrder);
+        }
+        if !mv.extra_shots.is_empty() {
+            return Err(RuleError::SalvoSize { player: mv.player });
+        }
+        match mv.action {
+            Action::Shot => {}
+            Action::Resign => {
+                if mv.target != 0 {
+                    return Err(RuleError::Target { player: mv.player });
+                }
+                self.total_moves += 1;
+                self.knock_out(mv.player, Outcome::Resigned);
+                if self.expected_player == mv.player {
+                    self.expected_player = self.next_after(mv.player);
+                }
+                return Ok(());
+            }
+            _ => return Err(RuleError::WeaponLimit { player: mv.player }),
+        }
+        if mv.player != self.expected_player {
+            return Err(RuleError::TurnOrder);
+        }
+        if mv.target == mv.player || !self.standing(mv.target) {
+            return Err(RuleError::Target { player: mv.player });
+        }
+        let cell = grid
+            .cell(u32::from(mv.x), u32::from(mv.y))
+            .ok_or(RuleError::MovePosition)?;
+
+        let target = usize::from(mv.target - 1);
+        if self.shots[target] & cell != B::ZERO {
+            return Err(RuleError::DuplicateShot { player: mv.player });
+        }
+        let shots = self.shots[target] | cell;
+        self.shots[target] = shots;
+
+        let fleet = occupied(&ships[target]);
+        let stats = &mut self.stats[usize::from(mv.player - 1)];
+        if fleet & cell != B::ZERO {
+            stats.hits += 1;
+        } else {
+            stats.misses += 1;
+        }
+        stats.ships_sunk += ships[target]
+            .iter()
+            .filter(|&&ship| ship & cell != B::ZERO && shots & ship == ship)
+            .count() as u8;
+        self.total_moves += 1;
+
+        if fleet & !shots == B::ZERO {
+            self.knock_out(mv.target, Outcome::AllSunk);
+        }
+        self.expected_player = self.next_after(mv.player);
+        Ok(())
+    }
+
+    fn output(
+        &self,
+        input: &FreeForAllInput,
+        board_hashes: Vec<[u8; 32]>,
+        transcript_hash: [u8; 32],
+    ) -> FreeForAllOutput {
+        let ranking = if self.winner == 0 {
+            Vec::new()
+        } else {
+            std::iter::once(self.winner)
+                .chain(self.eliminated.iter().rev().copied())
+                .collect()
+        };
+        let stats = self
+            .stats
+            .iter()
+            .map(|stats| PlayerStats {
+                accuracy_bps: PlayerStats::accuracy_bps(stats.hits, stats.misses),
+                ..*stats
+            })
+            .collect();
+        FreeForAllOutput {
+            session_id: input.session_id,
+            players: input.seats.len() as u8,
+            winner: self.winner,
+            outcome: self.outcome,
+            ranking,
+            board_hashes,
+            transcript_hash,
+            total_moves: self.total_moves,
+            mode: input.mode,
+            no_touching: input.no_touching,
+            stats,
+            player_keys: input.player_keys.clone(),
+        }
+    }
+}
+
+/// Validates every seat's board into its ships.
+fn seat_boards<B: Bits>(
+    input: &FreeForAllInput,
+    board: impl Fn(&Seat, u8) -> Result<Vec<B>, RuleError>,
+) -> Result<Vec<Vec<B>>, RuleError> {
+    input
+        .seats
+        .iter()
+        .zip(1..)
+        .map(|(seat, player)| board(seat, player))
+        .collect()
+}
+
+fn run<B: Bits>(
+    grid: &Grid<B>,
+    ships: &[Vec<B>],
+    input: &FreeForAllInput,
+    transcript_hash: [u8; 32],
+) -> Result<FreeForAllOutput, RuleError> {
+    let mut replay = Match::new(ships.len());
+    for mv in &input.moves {
+        replay.step(grid, ships, mv)?;
+    }
+    let board_hashes = ships
+        .iter()
+        .zip(&input.seats)
+        .map(|(ships, seat)| merkle::board_root(&cell_map(grid, occupied(ships)), &seat.board_salt))
+        .collect();
+    Ok(replay.output(input, board_hashes, transcript_hash))
+}
+
+/// Validates every board and replays `input.moves`, returning the journal to commit.
+pub fn play_free_for_all(input: &FreeForAllInput) -> Result<FreeForAllOutput, RuleError> {
+    let players = input.seats.len();
+    if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&players) {
+        return Err(RuleError::PlayerCount);
+    }
+    let transcript_hash = match &input.player_keys {
+        Some(keys) if keys.len() != players => return Err(RuleError::PlayerCount),
+        Some(keys) => transcript::verify_moves(input.session_id, &input.moves, keys)?,
+        None => transcript::transcript_hash(input.session_id, &input.moves),
+    };
+    match input.mode {
+        GameMode::Demo => {
+            let grid = grid();
+            let ships = seat_boards(input, |seat, player| {
+                demo_board(&seat.board, &seat.fleet, input.no_touching, player)
+            })?;
+            run(&grid, &ships, input, transcript_hash)
+        }
+        GameMode::Classic => {
+            let grid = classic_grid();
+            let ships = seat_boards(input, |seat, player| {
+                if seat.board != [0; 16] {
+                    return Err(RuleError::BoardFormat { player });
+                }
+                fleet::rasterize(
+                    &grid,
+                    &seat.fleet,
+                    &CLASSIC_FLEET,
+                    input.no_touching,
+                    player,
+                )
+            })?;
+            run(&grid, &ships, input, transcript_hash)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: [u8; 16] = [
+        1, 1, 0, 0, //
+        0, 0, 0, 0, //
+        1, 1, 0, 0, //
+        0, 0, 0, 0, //
+    ];
+
+    fn seats(players: usize) -> Vec<Seat> {
+        vec![
+            Seat {
+                board: BOARD,
+                ..Seat::default()
+            };
+            players
+        ]
+    }
+
+    /// P1 and P2 sink P3 together, then P1 sinks P2.
+    fn three_way() -> FreeForAllInput {
+        FreeForAllInput {
+            session_id: 7,
+            mode: GameMode::Demo,
+            no_touching: false,
+            seats: seats(3),
+            player_keys: None,
+            moves: vec![
+                Move::at(1, 3, 0, 0),
+                Move::at(2, 3, 1, 0), // sinks P3's first ship
+                Move::at(3, 1, 3, 3),
+                Move::at(1, 3, 0, 2),
+                Move::at(2, 3, 1, 2), // sinks P3's last ship: P3 is out
+                Move::at(1, 2, 0, 0),
+                Move::at(2, 1, 3, 2),
+                Move::at(1, 2, 1, 0),
+                Move::at(2, 1, 2, 2),
+                Move::at(1, 2, 0, 2),
+                Move::at(2, 1, 2, 3),
+                Move::at(1, 2, 1, 2),
+            ],
+        }
+    }
+
+    #[test]
+    fn last_player_standing_wins_and_ranks_first() {
+        let output = play_free_for_all(&three_way()).unwrap();
+        assert_eq!(output.players, 3);
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.outcome, Outcome::AllSunk);
+        assert_eq!(output.ranking, [1, 2, 3]);
+        assert_eq!(output.total_moves, 12);
+        assert_eq!(output.board_hashes.len(), 3);
+
+        let sunk: Vec<u8> = output.stats.iter().map(|stats| stats.ships_sunk).collect();
+        assert_eq!(sunk, [2, 2, 0]);
+        assert_eq!((output.stats[1].hits, output.stats[1].misses), (2, 3));
+        assert_eq!(output.stats[0].accuracy_bps, 10_000);
+    }
+
+    #[test]
+    fn shots_must_target_a_standing_opponent() {
+        let mut input = three_way();
+        input.moves.truncate(5);
+
+        let mut at_self = input.clone();
+        at_self.moves.push(Move::at(1, 1, 3, 3));
+        assert_eq!(
+            play_free_for_all(&at_self),
+            Err(RuleError::Target { player: 1 })
+        );
+
+        let mut at_sunk = input.clone();
+        at_sunk.moves.push(Move::at(1, 3, 3, 3));
+        assert_eq!(
+            play_free_for_all(&at_sunk),
+            Err(RuleError::Target { player: 1 })
+        );
+
+        // P3 is out, so P1 moves after P2.
+        let mut out_of_turn = input.clone();
+        out_of_turn.moves.push(Move::at(3, 1, 3, 3));
+        assert_eq!(play_free_for_all(&out_of_turn), Err(RuleError::TurnOrder));
+
+        // Hits are shared: P2 may not shoot the cell P1 hit.
+        let mut repeated = three_way();
+        repeated.moves.truncate(1);
+        repeated.moves.push(Move::at(2, 3, 0, 0));
+        assert_eq!(
+            play_free_for_all(&repeated),
+            Err(RuleError::DuplicateShot { player: 2 })
+        );
+
+        let mut untargeted = three_way();
+        untargeted.moves = vec![Move::new(1, 0, 0)];
+        assert_eq!(
+            play_free_for_all(&untargeted),
+            Err(RuleError::Target { player: 1 })
+        );
+    }
+
+    #[test]
+    fn resigning_players_go_out_at_once() {
+        let mut input = three_way();
+        input.seats = seats(4);
+        input.moves = vec![
+            Move::at(1, 4, 3, 3),
+            Move::resign(3), // out of turn: P2 is still next
+            Move::at(2, 4, 2, 3),
+            Move::resign(4), // P4 was next, so P1 moves
+            Move::at(1, 2, 3, 3),
+            Move::resign(2),
+        ];
+        let output = play_free_for_all(&input).unwrap();
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.outcome, Outcome::Resigned);
+        assert_eq!(output.ranking, [1, 2, 4, 3]);
+
+        let mut unfinished = input.clone();
+        unfinished.moves.pop();
+        let output = play_free_for_all(&unfinished).unwrap();
+        assert_eq!(output.winner, 0);
+        assert!(output.ranking.is_empty());
+    }
+
+    #[test]
+    fn seats_and_keys_must_match_the_player_count() {
+        let mut input = three_way();
+        input.seats = seats(2);
+        assert_eq!(play_free_for_all(&input), Err(RuleError::PlayerCount));
+        input.seats = seats(5);
+        assert_eq!(play_free_for_all(&input), Err(RuleError::PlayerCount));
+
+        let mut input = three_way();
+        input.player_keys = Some(vec![[0; 32]; 2]);
+        assert_eq!(play_free_for_all(&input), Err(RuleError::PlayerCount));
+    }
+
+    #[test]
+    fn signatures_cover_the_target() {
+        let secret_keys = [[1; 32], [2; 32], [3; 32]];
+        let mut input = three_way();
+        input.player_keys = Some(secret_keys.iter().map(transcript::public_key).collect());
+        transcript::sign_moves(input.session_id, &mut input.moves, &secret_keys);
+        assert_eq!(play_free_for_all(&input).unwrap().winner, 1);
+
+        // The same shot redirected at another opponent no longer verifies.
+        input.moves[0].target = 2;
+        assert_eq!(
+            play_free_for_all(&input),
+            Err(RuleError::Signature { player: 1 })
+        );
+    }
+}
@@ -0,0 +1,1925 @@
+//! Battleship rules shared by the RISC0 guest and the host-side prover.
+//!
+//! The guest runs [`play`] inside the zkVM and commits the returned [`PublicOutput`]; the
+//! prover uses the same types to build inputs and decode journals. State is kept in
+//! bitboards (see [`bitboard`]) so hit, duplicate-shot and ship checks are bit operations.
+//! Games with `player_keys` also check every move's signature (see [`transcript`]), and
+//! [`turn`] replays a game one move at a time for turn-by-turn proving.
+//! [`free_for_all`] plays the same boards with three or four players, and [`teams`] two
+//! teams of two on shared boards.
+
+pub mod bitboard;
+pub mod fleet;
+pub mod free_for_all;
+pub mod merkle;
+pub mod teams;
+pub mod transcript;
+pub mod turn;
+
+use bitboard::{Bits, Grid};
+pub use fleet::{Orientation, Ship};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use transcript::PublicKey;
+
+/// One turn: a shot at `(x, y)`, followed by `extra_shots` under the salvo rule, or a
+/// special weapon aimed at `(x, y)`, or a resignation.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Move {
+    pub player: u8,
+    /// Ignored by `Resign`, so resignations may omit it.
+    #[serde(default)]
+    pub x: u8,
+    #[serde(default)]
+    pub y: u8,
+    #[serde(default)]
+    pub extra_shots: Vec<Shot>,
+    #[serde(default)]
+    pub action: Action,
+    /// The player whose board a free-for-all shot is fired at; 0 in two-player games.
+    #[serde(default)]
+    pub target: u8,
+    /// The player's ed25519 signature over [`transcript::move_message`]; required when the
+    /// game has `player_keys`.
+    #[serde(default)]
+    pub signature: Vec<u8>,
+}
+
+/// What a move does at `(x, y)`. Everything but `Shot` and `Resign` draws on the match's
+/// [`Weapons`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    #[default]
+    Shot,
+    /// Fires at the 3x3 area centred on `(x, y)`, clipped to the board.
+    Bomb,
+    /// Fires at every cell of row `y`.
+    RowStrike,
+    /// Fires at every cell of column `x`.
+    ColumnStrike,
+    /// Fires nothing; the journal records how many opposing ship cells lie in the 3x3 area
+    /// centred on `(x, y)`.
+    Sonar,
+    /// Ends the game: the other player wins. Allowed for either player at any point, out of
+    /// turn too.
+    Resign,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Shot {
+    pub x: u8,
+    pub y: u8,
+}
+
+impl Move {
+    /// A turn with a single shot.
+    pub fn new(player: u8, x: u8, y: u8) -> Self {
+        Self {
+            player,
+            x,
+            y,
+            extra_shots: Vec::new(),
+            action: Action::Shot,
+            target: 0,
+            signature: Vec::new(),
+        }
+    }
+
+    /// A free-for-all shot at `target`'s board.
+    pub fn at(player: u8, target: u8, x: u8, y: u8) -> Self {
+        Self {
+            target,
+            ..Self::new(player, x, y)
+        }
+    }
+
+    /// `player` gives up the game.
+    pub fn resign(player: u8) -> Self {
+        Self {
+            action: Action::Resign,
+            ..Self::new(player, 0, 0)
+        }
+    }
+
+    /// A turn that uses a special weapon instead of shooting.
+    pub fn weapon(player: u8, action: Action, x: u8, y: u8) -> Self {
+        Self {
+            action,
+            ..Self::new(player, x, y)
+        }
+    }
+
+    /// Every shot fired this turn, `(x, y)` first.
+    pub fn shots(&self) -> impl Iterator<Item = Shot> + '_ {
+        std::iter::once(Shot {
+            x: self.x,
+            y: self.y,
+        })
+        .chain(self.extra_shots.iter().copied())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    /// 4x4 board with two size-2 ships, given as `board_p1`/`board_p2` cell bitmaps or as
+    /// `fleet_p1`/`fleet_p2` ship lists.
+    #[default]
+    Demo,
+    /// 10x10 board with the 5-4-3-3-2 fleet, given as `fleet_p1`/`fleet_p2` ship lists.
+    Classic,
+}
+
+impl GameMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Demo => "demo",
+            Self::Classic => "classic",
+        }
+    }
+
+    /// Side length of the board.
+    pub fn board_size(self) -> u32 {
+        match self {
+            Self::Demo => BOARD_SIZE,
+            Self::Classic => CLASSIC_BOARD_SIZE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GameInput {
+    pub session_id: u32,
+    #[serde(default)]
+    pub mode: GameMode,
+    /// Ships may not touch, not even diagonally.
+    #[serde(default)]
+    pub no_touching: bool,
+    /// Salvo rule: each turn fires one shot per surviving ship of the shooter.
+    #[serde(default)]
+    pub salvo: bool,
+    /// A move that hits lets the same player move again; otherwise players alternate.
+    #[serde(default)]
+    pub hit_keeps_turn: bool,
+    /// Special weapon uses per player; none by default.
+    #[serde(default)]
+    pub weapons: Weapons,
+    /// Mines each player places on water cells of their board; none by default.
+    #[serde(default)]
+    pub mines: u8,
+    #[serde(default)]
+    pub mine_penalty: MinePenalty,
+    /// Bitmap boards: 1 = ship, and [`MINE`] marks a mine when the game has `mines`.
+    #[serde(default)]
+    pub board_p1: [u8; 16],
+    #[serde(default)]
+    pub board_p2: [u8; 16],
+    #[serde(default)]
+    pub fleet_p1: Vec<Ship>,
+    #[serde(default)]
+    pub fleet_p2: Vec<Ship>,
+    /// Mine cells, for boards that do not mark them in `board_p1`/`board_p2` (every
+    /// classic board).
+    #[serde(default)]
+    pub mines_p1: Vec<Shot>,
+    #[serde(default)]
+    pub mines_p2: Vec<Shot>,
+    /// Secret salts for the board commitments (see [`merkle`]). Use fresh random salts for
+    /// every game: an unsalted small board can be guessed from its commitment.
+    #[serde(default)]
+    pub board_salt_p1: [u8; 32],
+    #[serde(default)]
+    pub board_salt_p2: [u8; 32],
+    /// Ed25519 public keys of P1 and P2. When set, every move must be signed by its player.
+    #[serde(default)]
+    pub player_keys: Option<[PublicKey; 2]>,
+    pub moves: Vec<Move>,
+}
+
+/// Journal committed by the guest.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PublicOutput {
+    pub session_id: u32,
+    pub winner: u8,
+    /// How the game ended, or `Unfinished` when `winner` is 0.
+    pub outcome: Outcome,
+    /// [`merkle::board_root`] of the row-major cell map (one byte per cell, 1 = ship) and
+    /// the board salt: the commitment `submit_result` matches against `board_commit_p1`.
+    pub board_hash_p1: [u8; 32],
+    pub board_hash_p2: [u8; 32],
+    /// [`transcript::transcript_hash`] of `moves`: recompute it from the game input to
+    /// prove which moves were played.
+    pub transcript_hash: [u8; 32],
+    /// Entries of `moves` replayed. A salvo is one move, and with `hit_keeps_turn` every
+    /// extra move earned by a hit counts as its own move.
+    pub total_moves: u32,
+    pub mode: GameMode,
+    pub no_touching: bool,
+    pub salvo: bool,
+    pub hit_keeps_turn: bool,
+    pub weapons: Weapons,
+    /// Shots fired by P1 at P2's board.
+    pub stats_p1: PlayerStats,
+    /// Shots fired by P2 at P1's board.
+    pub stats_p2: PlayerStats,
+    /// Every sonar result, in move order.
+    pub sonar: Vec<SonarPing>,
+    pub mines: u8,
+    pub mine_penalty: MinePenalty,
+    /// Every mine set off, in move order.
+    pub mine_hits: Vec<MineHit>,
+    /// The keys every move's signature was checked against; `None` for an unsigned game.
+    pub player_keys: Option<[PublicKey; 2]>,
+}
+
+/// Why the game ended.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    #[default]
+    Unfinished,
+    /// The winner sank every opposing ship.
+    AllSunk,
+    /// The loser resigned.
+    Resigned,
+}
+
+impl Outcome {
+    /// The reason code `submit_result` takes: 1 for all sunk, 2 for a resignation, 0 otherwise.
+    pub fn code(self) -> u32 {
+        match self {
+            Self::Unfinished => 0,
+            Self::AllSunk => 1,
+            Self::Resigned => 2,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Unfinished => "unfinished",
+            Self::AllSunk => "all_sunk",
+            Self::Resigned => "resigned",
+        }
+    }
+}
+
+/// How many times each player may use each special weapon in one match.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Weapons {
+    pub bombs: u8,
+    /// Shared by row and column strikes.
+    pub line_strikes: u8,
+    pub sonar_pings: u8,
+}
+
+impl Weapons {
+    /// The allowance `action` draws on, or `None` if it needs none.
+    fn allowance(&mut self, action: Action) -> Option<&mut u8> {
+        match action {
+            Action::Shot | Action::Resign => None,
+            Action::Bomb => Some(&mut self.bombs),
+            Action::RowStrike | Action::ColumnStrike => Some(&mut self.line_strikes),
+            Action::Sonar => Some(&mut self.sonar_pings),
+        }
+    }
+}
+
+/// The result of a sonar move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SonarPing {
+    /// Index of the sonar move in `moves`.
+    pub move_index: u32,
+    pub player: u8,
+    pub x: u8,
+    pub y: u8,
+    /// Opposing ship cells in the 3x3 area centred on `(x, y)`, hit or not.
+    pub ship_cells: u8,
+}
+
+/// What setting off a mine costs the shooter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MinePenalty {
+    /// The shooter's next turn goes to the opponent.
+    #[default]
+    SkipTurn,
+    /// The journal gives away one of the shooter's ship cells that the opponent has not
+    /// shot yet: the first in row-major order not given away before.
+    RevealCell,
+}
+
+impl MinePenalty {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SkipTurn => "skip_turn",
+            Self::RevealCell => "reveal_cell",
+        }
+    }
+}
+
+/// A mine set off by a shot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MineHit {
+    /// Index of the move in `moves`.
+    pub move_index: u32,
+    /// The shooter.
+    pub player: u8,
+    pub x: u8,
+    pub y: u8,
+    /// The shooter's ship cell given away under [`MinePenalty::RevealCell`]; `None` under
+    /// `SkipTurn`, or once every ship cell left is known.
+    pub revealed: Option<Shot>,
+}
+
+/// What one player's shots achieved over the game.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PlayerStats {
+    /// Opponent ships with every cell hit.
+    pub ships_sunk: u8,
+    pub hits: u32,
+    pub misses: u32,
+    /// `hits / (hits + misses)` in basis points (10000 = every shot hit); 0 without shots.
+    pub accuracy_bps: u16,
+}
+
+impl PlayerStats {
+    fn accuracy_bps(hits: u32, misses: u32) -> u16 {
+        let shots = u64::from(hits) + u64::from(misses);
+        if shots == 0 {
+            return 0;
+        }
+        (u64::from(hits) * 10_000 / shots) as u16
+    }
+}
+
+/// Why a game was rejected. The guest panics with the `Display` text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleError {
+    InvalidCell,
+    /// The board does not have exactly four ship cells.
+    ShipCount {
+        player: u8,
+    },
+    /// The ship cells are not two separate size-2 ships.
+    Layout {
+        player: u8,
+    },
+    MovesAfterGameOver,
+    TurnOrder,
+    MovePosition,
+    DuplicateShot {
+        player: u8,
+    },
+    /// The board was given in a format the game mode does not accept.
+    BoardFormat {
+        player: u8,
+    },
+    /// The ship lengths are not the fleet the mode requires.
+    FleetComposition {
+        player: u8,
+    },
+    ShipOutOfBounds {
+        player: u8,
+    },
+    ShipOverlap {
+        player: u8,
+    },
+    ShipsTouching {
+        player: u8,
+    },
+    /// The move fired a different number of shots than the ruleset allows this turn.
+    SalvoSize {
+        player: u8,
+    },
+    /// The player has no uses of the move's special weapon left.
+    WeaponLimit {
+        player: u8,
+    },
+    /// The player's entry in `player_keys` is not a valid ed25519 public key.
+    PlayerKey {
+        player: u8,
+    },
+    /// A move is missing its player's signature, or the signature does not verify.
+    Signature {
+        player: u8,
+    },
+    /// A turn proof does not start from the state the previous turn ended in.
+    TurnChain {
+        index: u32,
+    },
+    /// The final state is not the one the last turn proof ended in.
+    FinalState,
+    /// A free-for-all needs three or four seats, and one key per seat when signed.
+    PlayerCount,
+    /// The move's `target` is not a standing opponent (free-for-all), or is set outside a
+    /// free-for-all.
+    Target {
+        player: u8,
+    },
+    /// A team member placed no ships, a ship on a cell their teammate already used, or their
+    /// share as board cells where the team gave ship lists (or the other way round).
+    MemberBoard {
+        player: u8,
+    },
+    /// The board does not hold exactly `mines` mines, each on its own water cell.
+    Mines {
+        player: u8,
+    },
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCell => f.write_str("invalid board cell value"),
+            Self::ShipCount { player } => write!(f, "invalid board P{player}"),
+            Self::Layout { player } => write!(f, "invalid board layout P{player}"),
+            Self::MovesAfterGameOver => f.write_str("moves after game over are not allowed"),
+            Self::TurnOrder => f.write_str("invalid turn order"),
+            Self::MovePosition => f.write_str("invalid move position"),
+            Self::DuplicateShot { player } => write!(f, "duplicate shot by player {player}"),
+            Self::BoardFormat { player } => {
+                write!(
+                    f,
+                    "board P{player} is not in the format this game mode uses"
+                )
+            }
+            Self::FleetComposition { player } => write!(f, "invalid fleet P{player}"),
+            Self::ShipOutOfBounds { player } => write!(f, "ship out of bounds P{player}"),
+            Self::ShipOverlap { player } => write!(f, "overlapping ships P{player}"),
+            Self::ShipsTouching { player } => write!(f, "ships touching P{player}"),
+            Self::SalvoSize { player } => write!(f, "wrong number of shots by player {player}"),
+            Self::WeaponLimit { player } => write!(f, "weapon limit reached by player {player}"),
+            Self::PlayerKey { player } => write!(f, "invalid public key P{player}"),
+            Self::Signature { player } => write!(f, "invalid move signature by player {player}"),
+            Self::TurnChain { index } => write!(f, "turn {index} does not continue the match"),
+            Self::FinalState => f.write_str("final state does not match the last turn"),
+            Self::PlayerCount => f.write_str("a free-for-all needs 3 or 4 players"),
+            Self::Target { player } => write!(f, "invalid target by player {player}"),
+            Self::MemberBoard { player } => write!(f, "invalid team board share P{player}"),
+            Self::Mines { player } => write!(f, "invalid mines P{player}"),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+/// Side length of the demo board.
+pub const BOARD_SIZE: u32 = 4;
+/// Two destroyers.
+pub const DEMO_FLEET: [u8; 2] = [2, 2];
+/// Side length of the classic board.
+pub const CLASSIC_BOARD_SIZE: u32 = 10;
+/// Carrier, battleship, cruiser, submarine, destroyer.
+pub const CLASSIC_FLEET: [u8; 5] = [5, 4, 3, 3, 2];
+
+/// Cell value of a mine, in bitmap boards and cell maps.
+pub const MINE: u8 = 2;
+
+pub type Board = u16;
+pub type ClassicBoard = u128;
+
+pub fn grid() -> Grid<Board> {
+    Grid::new(BOARD_SIZE, BOARD_SIZE)
+}
+
+pub fn classic_grid() -> Grid<ClassicBoard> {
+    Grid::new(CLASSIC_BOARD_SIZE, CLASSIC_BOARD_SIZE)
+}
+
+/// Unpacks a bitboard into one byte per cell, row-major, as hashed into the journal.
+pub fn cell_map<B: Bits>(grid: &Grid<B>, board: B) -> Vec<u8> {
+    (0..grid.height())
+        .flat_map(|y| (0..grid.width()).map(move |x| (x, y)))
+        .map(|(x, y)| match grid.cell(x, y) {
+            Some(cell) if board & cell != B::ZERO => 1,
+            _ => 0,
+        })
+        .collect()
+}
+
+/// Packs a `[u8; 16]` cell map (0 = water, 1 = ship) into a bitboard.
+pub fn board_bits(cells: &[u8; 16]) -> Result<Board, RuleError> {
+    let mut bits: Board = 0;
+    for (idx, &cell) in cells.iter().enumerate() {
+        match cell {
+            0 => {}
+            1 => bits |= 1 << idx,
+            _ => return Err(RuleError::InvalidCell),
+        }
+    }
+    Ok(bits)
+}
+
+/// Whether a bitmap board splits into straight ships with exactly the `lengths` fleet.
+///
+/// Orthogonally adjacent ship cells count as one ship, so an L shape or two ships placed
+/// end to end are rejected.
+fn bitmap_matches_fleet<B: Bits>(grid: &Grid<B>, board: B, lengths: &[u8]) -> bool {
+    let mut expected = lengths.to_vec();
+    expected.sort_unstable();
+    let mut actual = Vec::with_capacity(expected.len());
+    for component in grid.components(board) {
+        if !grid.is_line(component) {
+            return false;
+        }
+        actual.push(component.count() as u8);
+    }
+    actual.sort_unstable();
+    actual == expected
+}
+
+/// Every cell covered by `ships`.
+fn occupied<B: Bits>(ships: &[B]) -> B {
+    ships.iter().fold(B::ZERO, |board, &ship| board | ship)
+}
+
+/// Whether any two orthogonally separate ships of `board` touch diagonally.
+fn has_touching_ships<B: Bits>(grid: &Grid<B>, board: B) -> bool {
+    grid.components(board)
+        .any(|ship| grid.surrounding(ship) & board != B::ZERO)
+}
+
+/// Resolves one demo board from either its cell bitmap or its ship list into its ships.
+fn demo_board(
+    cells: &[u8; 16],
+    fleet: &[Ship],
+    no_touching: bool,
+    player: u8,
+) -> Result<Vec<Board>, RuleError> {
+    let grid = grid();
+    if !fleet.is_empty() {
+        if *cells != [0; 16] {
+            return Err(RuleError::BoardFormat { player });
+        }
+        return fleet::rasterize(&grid, fleet, &DEMO_FLEET, no_touching, player);
+    }
+
+    let board = board_bits(cells)?;
+    let ship_cells: u32 = DEMO_FLEET.iter().map(|&length| u32::from(length)).sum();
+    if board.count() != ship_cells {
+        return Err(RuleError::ShipCount { player });
+    }
+    if !bitmap_matches_fleet(&grid, board, &DEMO_FLEET) {
+        return Err(RuleError::Layout { player });
+    }
+    if no_touching && has_touching_ships(&grid, board) {
+        return Err(RuleError::ShipsTouching { player });
+    }
+    Ok(grid.components(board).collect())
+}
+
+fn demo_boards(input: &GameInput) -> Result<[Vec<Board>; 2], RuleError> {
+    // Mines are validated by `mine_boards`; only a game with mines may mark them.
+    let [board_p1, board_p2] = [input.board_p1, input.board_p2].map(|cells| match input.mines {
+        0 => cells,
+        _ => cells.map(|cell| if cell == MINE { 0 } else { cell }),
+    });
+    Ok([
+        demo_board(&board_p1, &input.fleet_p1, input.no_touching, 1)?,
+        demo_board(&board_p2, &input.fleet_p2, input.no_touching, 2)?,
+    ])
+}
+
+fn classic_boards(input: &GameInput) -> Result<[Vec<ClassicBoard>; 2], RuleError> {
+    if input.board_p1 != [0; 16] {
+        return Err(RuleError::BoardFormat { player: 1 });
+    }
+    if input.board_p2 != [0; 16] {
+        return Err(RuleError::BoardFormat { player: 2 });
+    }
+
+    let grid = classic_grid();
+    let ships_p1 = fleet::rasterize(&grid, &input.fleet_p1, &CLASSIC_FLEET, input.no_touching, 1)?;
+    let ships_p2 = fleet::rasterize(&grid, &input.fleet_p2, &CLASSIC_FLEET, input.no_touching, 2)?;
+    Ok([ships_p1, ships_p2])
+}
+
+/// Resolves one player's mines from either the [`MINE`] cells of their bitmap or their
+/// mine list, checking there are exactly `count` of them, all on water.
+fn mine_board<B: Bits>(
+    grid: &Grid<B>,
+    cells: &[u8; 16],
+    listed: &[Shot],
+    ships: B,
+    count: u8,
+    player: u8,
+) -> Result<B, RuleError> {
+    let marked: Vec<B> = (0..16)
+        .filter(|&idx| cells[idx as usize] == MINE)
+        .filter_map(|idx| grid.cell(idx % grid.width(), idx / grid.width()))
+        .collect();
+    if !marked.is_empty() && !listed.is_empty() {
+        return Err(RuleError::BoardFormat { player });
+    }
+
+    let mut mines = B::ZERO;
+    for &cell in &marked {
+        mines = mines | cell;
+    }
+    for shot in listed {
+        let cell = grid
+            .cell(u32::from(shot.x), u32::from(shot.y))
+            .ok_or(RuleError::Mines { player })?;
+        if mines & cell != B::ZERO {
+            return Err(RuleError::Mines { player });
+        }
+        mines = mines | cell;
+    }
+    if mines.count() != u32::from(count) || mines & ships != B::ZERO {
+        return Err(RuleError::Mines { player });
+    }
+    Ok(mines)
+}
+
+/// The mines of both players, over boards already validated into `ships`.
+fn mine_boards<B: Bits>(
+    grid: &Grid<B>,
+    input: &GameInput,
+    ships: &[Vec<B>; 2],
+) -> Result<[B; 2], RuleError> {
+    Ok([
+        mine_board(
+            grid,
+            &input.board_p1,
+            &input.mines_p1,
+            occupied(&ships[0]),
+            input.mines,
+            1,
+        )?,
+        mine_board(
+            grid,
+            &input.board_p2,
+            &input.mines_p2,
+            occupied(&ships[1]),
+            input.mines,
+            2,
+        )?,
+    ])
+}
+
+/// The cells of `set` as coordinates, row-major.
+fn cell_coords<B: Bits>(grid: &Grid<B>, mut set: B) -> Vec<Shot> {
+    let mut coords = Vec::new();
+    while set != B::ZERO {
+        let index = set.trailing_zeros();
+        coords.push(Shot {
+            x: (index % grid.width()) as u8,
+            y: (index / grid.width()) as u8,
+        });
+        set = set & !set.lowest();
+    }
+    coords
+}
+
+/// Progress of a game between two moves, over boards given as the cells of their ships.
+///
+/// A ship is sunk by the shot that hits its last cell; a player wins once every opposing
+/// ship is sunk. With `salvo`, each move must fire one shot per surviving ship of the
+/// shooter (or at every cell left, if fewer); otherwise exactly one. Players alternate,
+/// except that with `hit_keeps_turn` a move with at least one hit is followed by another
+/// move of the same player.
+///
+/// A resignation ends the game at once, whoever's turn it is. A special weapon is the whole
+/// move, salvo or not. Bombs and line strikes fire at the
+/// cells of their area that were not shot before, and need at least one such cell.
+///
+/// Every mine a move sets off costs its shooter under `mine_penalty`: a skipped turn, which
+/// hands the opponent an extra move (and overrides `hit_keeps_turn`), or one of their ship
+/// cells revealed in the journal. Skipped turns both players owe cancel out.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Replay<B> {
+    salvo: bool,
+    hit_keeps_turn: bool,
+    // shots[0] is what P1 fired at P2's board, shots[1] the reverse.
+    shots: [B; 2],
+    expected_player: u8,
+    /// 1 or 2, or 0 while both fleets are afloat.
+    winner: u8,
+    outcome: Outcome,
+    total_moves: u32,
+    /// Special weapon uses each player has left.
+    remaining: [Weapons; 2],
+    stats: [PlayerStats; 2],
+    sonar: Vec<SonarPing>,
+    /// mines[0] holds P1's mines, which P2 may set off.
+    mines: [B; 2],
+    mine_penalty: MinePenalty,
+    /// Turns each player still has to skip.
+    skips: [u8; 2],
+    /// Own ship cells each player has had revealed.
+    revealed: [B; 2],
+    mine_hits: Vec<MineHit>,
+}
+
+impl<B: Bits> Replay<B> {
+    fn new(input: &GameInput, mines: [B; 2]) -> Self {
+        Self {
+            salvo: input.salvo,
+            hit_keeps_turn: input.hit_keeps_turn,
+            shots: [B::ZERO; 2],
+            expected_player: 1,
+            winner: 0,
+            outcome: Outcome::Unfinished,
+            total_moves: 0,
+            remaining: [input.weapons; 2],
+            stats: [PlayerStats::default(); 2],
+            sonar: Vec::new(),
+            mines,
+            mine_penalty: input.mine_penalty,
+            skips: [0; 2],
+            revealed: [B::ZERO; 2],
+            mine_hits: Vec::new(),
+        }
+    }
+
+    /// Who moves after a move that would leave the turn to `next`, once skipped turns are
+    /// served.
+    fn take_turn(&mut self, next: u8) -> u8 {
+        let owed = self.skips[0].min(self.skips[1]);
+        self.skips = self.skips.map(|skips| skips - owed);
+        let skips = &mut self.skips[usize::from(next - 1)];
+        if *skips == 0 {
+            return next;
+        }
+        *skips -= 1;
+        3 - next
+    }
+
+    /// Charges the shooter for every mine among `fresh`, row-major.
+    fn trip_mines(&mut self, grid: &Grid<B>, ships: &[Vec<B>; 2], shooter: usize, fresh: B) {
+        let tripped = fresh & self.mines[1 - shooter];
+        for mine in cell_coords(grid, tripped) {
+            let revealed = match self.mine_penalty {
+                MinePenalty::SkipTurn => {
+                    self.skips[shooter] += 1;
+                    None
+                }
+                MinePenalty::RevealCell => {
+                    let hidden = occupied(&ships[shooter])
+                        & !self.shots[1 - shooter]
+                        & !self.revealed[shooter];
+                    let cell = hidden.lowest();
+                    self.revealed[shooter] = self.revealed[shooter] | cell;
+                    cell_coords(grid, cell).first().copied()
+                }
+            };
+            self.mine_hits.push(MineHit {
+                move_index: self.total_moves,
+                player: shooter as u8 + 1,
+                x: mine.x,
+                y: mine.y,
+                revealed,
+            });
+        }
+    }
+
+    /// Applies the next move, which is `moves[self.total_moves]`.
+    fn step(&mut self, grid: &Grid<B>, ships: &[Vec<B>; 2], mv: &Move) -> Result<(), RuleError> {
+        let targets = [&ships[1], &ships[0]];
+        // fleets[0] is every ship cell P1 is shooting at.
+        let fleets = [occupied(targets[0]), occupied(targets[1])];
+        let index = self.total_moves;
+
+        if self.winner != 0 {
+            return Err(RuleError::MovesAfterGameOver);
+        }
+        if mv.action == Action::Resign {
+            if !matches!(mv.player, 1 | 2) {
+                return Err(RuleError::TurnOrder);
+            }
+            if !mv.extra_shots.is_empty() {
+                return Err(RuleError::SalvoSize { player: mv.player });
+            }
+            if mv.target != 0 {
+                return Err(RuleError::Target { player: mv.player });
+            }
+            self.winner = 3 - mv.player;
+            self.outcome = Outcome::Resigned;
+            self.total_moves += 1;
+            return Ok(());
+        }
+        if mv.player != self.expected_player {
+            return Err(RuleError::TurnOrder);
+        }
+        if mv.target != 0 {
+            return Err(RuleError::Target { player: mv.player });
+        }
+        let (x, y) = (u32::from(mv.x), u32::from(mv.y));
+        let aim = grid.cell(x, y).ok_or(RuleError::MovePosition)?;
+
+        let shooter = usize::from(mv.player - 1);
+        let salvo_size = match self.remaining[shooter].allowance(mv.action) {
+            Some(0) => return Err(RuleError::WeaponLimit { player: mv.player }),
+            Some(left) => {
+                *left -= 1;
+                1
+            }
+            None if self.salvo => {
+                let afloat = ships[shooter].len() - usize::from(self.stats[1 - shooter].ships_sunk);
+                afloat.min((grid.all() & !self.shots[shooter]).count() as usize)
+            }
+            None => 1,
+        };
+        if 1 + mv.extra_shots.len() != salvo_size {
+            return Err(RuleError::SalvoSize { player: mv.player });
+        }
+
+        let area = aim | grid.surrounding(aim);
+        let volleys = match mv.action {
+            Action::Shot => mv
+                .shots()
+                .map(|shot| {
+                    grid.cell(u32::from(shot.x), u32::from(shot.y))
+                        .ok_or(RuleError::MovePosition)
+                })
+                .collect::<Result<Vec<B>, RuleError>>()?,
+            Action::Bomb => vec![area],
+            Action::RowStrike => vec![grid.row(y).ok_or(RuleError::MovePosition)?],
+            Action::ColumnStrike => vec![grid.column(x).ok_or(RuleError::MovePosition)?],
+            Action::Resign => Vec::new(),
+            Action::Sonar => {
+                self.sonar.push(SonarPing {
+                    move_index: index,
+                    player: mv.player,
+                    x: mv.x,
+                    y: mv.y,
+                    ship_cells: (area & fleets[shooter]).count() as u8,
+                });
+                Vec::new()
+            }
+        };
+
+        let shots = &mut self.shots[shooter];
+        let stats = &mut self.stats[shooter];
+        let hits_before = stats.hits;
+        let shots_before = *shots;
+        for cells in volleys {
+            let fresh = cells & !*shots;
+            if fresh == B::ZERO {
+                return Err(RuleError::DuplicateShot { player: mv.player });
+            }
+            *shots = *shots | fresh;
+
+            stats.hits += (fresh & fleets[shooter]).count();
+            stats.misses += (fresh & !fleets[shooter]).count();
+            let sunk = targets[shooter]
+                .iter()
+                .filter(|&&ship| ship & fresh != B::ZERO && *shots & ship == ship)
+                .count();
+            stats.ships_sunk += sunk as u8;
+        }
+        let won = usize::from(stats.ships_sunk) == targets[shooter].len();
+        let keeps_turn = self.hit_keeps_turn && stats.hits > hits_before;
+        let fresh = *shots & !shots_before;
+        self.trip_mines(grid, ships, shooter, fresh);
+        self.total_moves += 1;
+
+        if won {
+            self.winner = mv.player;
+            self.outcome = Outcome::AllSunk;
+        }
+        let next = if keeps_turn { mv.player } else { 3 - mv.player };
+        self.expected_player = self.take_turn(next);
+        Ok(())
+    }
+
+    /// The journal for the moves replayed so far.
+    fn output(
+        &self,
+        input: &GameInput,
+        board_hashes: [[u8; 32]; 2],
+        transcript_hash: [u8; 32],
+    ) -> PublicOutput {
+        let mut stats = self.stats;
+        for stats in &mut stats {
+            stats.accuracy_bps = PlayerStats::accuracy_bps(stats.hits, stats.misses);
+        }
+        PublicOutput {
+            session_id: input.session_id,
+            winner: self.winner,
+            outcome: self.outcome,
+            board_hash_p1: board_hashes[0],
+            board_hash_p2: board_hashes[1],
+            transcript_hash,
+            total_moves: self.total_moves,
+            mode: input.mode,
+            no_touching: input.no_touching,
+            salvo: input.salvo,
+            hit_keeps_turn: input.hit_keeps_turn,
+            weapons: input.weapons,
+            stats_p1: stats[0],
+            stats_p2: stats[1],
+            sonar: self.sonar.clone(),
+            mines: input.mines,
+            mine_penalty: input.mine_penalty,
+            mine_hits: self.mine_hits.clone(),
+            player_keys: input.player_keys,
+        }
+    }
+}
+
+/// Replays every move of `input` over its validated boards.
+fn replay<B: Bits>(
+    grid: &Grid<B>,
+    ships: &[Vec<B>; 2],
+    mines: [B; 2],
+    input: &GameInput,
+) -> Result<Replay<B>, RuleError> {
+    let mut replay = Replay::new(input, mines);
+    for mv in &input.moves {
+        replay.step(grid, ships, mv)?;
+    }
+    Ok(replay)
+}
+
+/// The cell map of a board with its mines, as committed to: 1 for ship cells and [`MINE`]
+/// for mines.
+fn board_map<B: Bits>(grid: &Grid<B>, ships: &[B], mines: B) -> Vec<u8> {
+    let mut cells = cell_map(grid, occupied(ships));
+    for (cell, mine) in cells.iter_mut().zip(cell_map(grid, mines)) {
+        if mine != 0 {
+            *cell = MINE;
+        }
+    }
+    cells
+}
+
+/// The board commitments of both players.
+fn fleet_hashes<B: Bits>(
+    grid: &Grid<B>,
+    ships: &[Vec<B>; 2],
+    mines: &[B; 2],
+    input: &GameInput,
+) -> [[u8; 32]; 2] {
+    let salts = [&input.board_salt_p1, &input.board_salt_p2];
+    [0, 1].map(|player| {
+        merkle::board_root(
+            &board_map(grid, &ships[player], mines[player]),
+            salts[player],
+        )
+    })
+}
+
+/// Validates both boards and returns their cell maps, as committed to by the journal.
+pub fn cell_maps(input: &GameInput) -> Result<[Vec<u8>; 2], RuleError> {
+    fn maps<B: Bits>(
+        grid: &Grid<B>,
+        input: &GameInput,
+        ships: [Vec<B>; 2],
+    ) -> Result<[Vec<u8>; 2], RuleError> {
+        let mines = mine_boards(grid, input, &ships)?;
+        Ok([0, 1].map(|player| board_map(grid, &ships[player], mines[player])))
+    }
+    match input.mode {
+        GameMode::Demo => maps(&grid(), input, demo_boards(input)?),
+        GameMode::Classic => maps(&classic_grid(), input, classic_boards(input)?),
+    }
+}
+
+/// Validates both boards and replays `input.moves`, returning the journal to commit.
+pub fn play(input: &GameInput) -> Result<PublicOutput, RuleError> {
+    let transcript_hash = match &input.player_keys {
+        Some(keys) => transcript::verify_moves(input.session_id, &input.moves, keys)?,
+        None => transcript::transcript_hash(input.session_id, &input.moves),
+    };
+    match input.mode {
+        GameMode::Demo => {
+            let grid = grid();
+            let ships = demo_boards(input)?;
+            let mines = mine_boards(&grid, input, &ships)?;
+            let replay = replay(&grid, &ships, mines, input)?;
+            let board_hashes = fleet_hashes(&grid, &ships, &mines, input);
+            Ok(replay.output(input, board_hashes, transcript_hash))
+        }
+        GameMode::Classic => {
+            let grid = classic_grid();
+            let ships = classic_boards(input)?;
+            let mines = mine_boards(&grid, input, &ships)?;
+            let replay = replay(&grid, &ships, mines, input)?;
+            let board_hashes = fleet_hashes(&grid, &ships, &mines, input);
+            Ok(replay.output(input, board_hashes, transcript_hash))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_BOARD: [u8; 16] = [
+        1, 1, 0, 0, //
+        0, 0, 0, 0, //
+        1, 1, 0, 0, //
+        0, 0, 0, 0, //
+    ];
+
+    fn mv(player: u8, x: u8, y: u8) -> Move {
+        Move::new(player, x, y)
+    }
+
+    fn volley(player: u8, shots: &[(u8, u8)]) -> Move {
+        let (x, y) = shots[0];
+        Move {
+            extra_shots: shots[1..].iter().map(|&(x, y)| Shot { x, y }).collect(),
+            ..Move::new(player, x, y)
+        }
+    }
+
+    fn sample() -> GameInput {
+        GameInput {
+            session_id: 42,
+            mode: GameMode::Demo,
+            no_touching: false,
+            salvo: false,
+            hit_keeps_turn: false,
+            weapons: Weapons::default(),
+            mines: 0,
+            mine_penalty: MinePenalty::default(),
+            board_p1: SAMPLE_BOARD,
+            board_p2: SAMPLE_BOARD,
+            fleet_p1: Vec::new(),
+            fleet_p2: Vec::new(),
+            mines_p1: Vec::new(),
+            mines_p2: Vec::new(),
+            board_salt_p1: [0; 32],
+            board_salt_p2: [0; 32],
+            player_keys: None,
+            moves: vec![
+                mv(1, 0, 0),
+                mv(2, 3, 3),
+                mv(1, 1, 0),
+                mv(2, 3, 2),
+                mv(1, 0, 2),
+                mv(2, 2, 2),
+                mv(1, 1, 2),
+            ],
+        }
+    }
+
+    #[test]
+    fn sample_game_is_won_by_player_one() {
+        let output = play(&sample()).unwrap();
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.outcome, Outcome::AllSunk);
+        assert_eq!(output.total_moves, 7);
+        assert_eq!(
+            output.board_hash_p1,
+            merkle::board_root(&SAMPLE_BOARD, &[0; 32])
+        );
+
+        // The commitment hides the board behind its salt.
+        let salted = GameInput {
+            board_salt_p1: [9; 32],
+            ..sample()
+        };
+        let output = play(&salted).unwrap();
+        assert_eq!(
+            output.board_hash_p1,
+            merkle::board_root(&SAMPLE_BOARD, &[9; 32])
+        );
+        assert_ne!(output.board_hash_p1, output.board_hash_p2);
+        assert_eq!(cell_maps(&salted).unwrap()[0], SAMPLE_BOARD.to_vec());
+    }
+
+    #[test]
+    fn stats_track_hits_misses_and_sunk_ships() {
+        let output = play(&sample()).unwrap();
+        let perfect = PlayerStats {
+            ships_sunk: 2,
+            hits: 4,
+            misses: 0,
+            accuracy_bps: 10_000,
+        };
+        assert_eq!(output.stats_p1, perfect);
+        assert_eq!(output.stats_p2.hits, 0);
+        assert_eq!(output.stats_p2.misses, 3);
+        assert_eq!(output.stats_p2.accuracy_bps, 0);
+
+        // A ship only counts as sunk once its last cell is hit.
+        let mut input = sample();
+        input.moves = vec![
+            mv(1, 0, 0),
+            mv(2, 0, 0),
+            mv(1, 3, 3),
+            mv(2, 1, 0),
+            mv(1, 1, 0),
+        ];
+        let output = play(&input).unwrap();
+        assert_eq!(output.winner, 0);
+        assert_eq!(
+            output.stats_p1,
+            PlayerStats {
+                ships_sunk: 1,
+                hits: 2,
+                misses: 1,
+                accuracy_bps: 6_666,
+            }
+        );
+        assert_eq!(output.stats_p2.ships_sunk, 1);
+        assert_eq!(output.stats_p2.accuracy_bps, 10_000);
+
+        assert_eq!(PlayerStats::accuracy_bps(0, 0), 0);
+    }
+
+    #[test]
+    fn salvo_fires_one_shot_per_surviving_ship() {
+        let mut input = sample();
+        input.salvo = true;
+        input.moves = vec![
+            volley(1, &[(0, 0), (1, 0)]),
+            // P1 sank one of P2's two ships, so P2 fires a single shot.
+            mv(2, 3, 3),
+            volley(1, &[(0, 2), (1, 2)]),
+        ];
+        let output = play(&input).unwrap();
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.total_moves, 3);
+        assert!(output.salvo);
+        assert_eq!(output.stats_p1.ships_sunk, 2);
+        assert_eq!(output.stats_p2.misses, 1);
+
+        // Once P2 sinks a P1 ship, P1 is down to one shot per turn.
+        input.moves = vec![
+            volley(1, &[(3, 3), (3, 2)]),
+            volley(2, &[(0, 0), (1, 0)]),
+            volley(1, &[(0, 0), (1, 0)]),
+        ];
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::SalvoSize { player: 1 }
+        );
+        input.moves[2] = mv(1, 0, 0);
+        assert_eq!(play(&input).unwrap().total_moves, 3);
+
+        input.moves = vec![mv(1, 0, 0)];
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::SalvoSize { player: 1 }
+        );
+
+        input.moves = vec![volley(1, &[(2, 2), (2, 2)])];
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::DuplicateShot { player: 1 }
+        );
+    }
+
+    #[test]
+    fn extra_shots_need_the_salvo_rule() {
+        let mut input = sample();
+        input.moves[0] = volley(1, &[(0, 0), (1, 0)]);
+        assert_eq!(
+            play(&input).unwrap_err().to_string(),
+            "wrong number of shots by player 1"
+        );
+    }
+
+    #[test]
+    fn hit_keeps_the_turn_when_enabled() {
+        let mut input = sample();
+        input.hit_keeps_turn = true;
+        input.moves = vec![
+            mv(1, 3, 3),
+            mv(2, 0, 0),
+            mv(2, 1, 0),
+            mv(2, 3, 3),
+            mv(1, 0, 0),
+            mv(1, 1, 0),
+            mv(1, 0, 2),
+            mv(1, 1, 2),
+        ];
+        let output = play(&input).unwrap();
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.total_moves, 8);
+        assert!(output.hit_keeps_turn);
+        assert_eq!(output.stats_p1.accuracy_bps, 8_000);
+        assert_eq!(output.stats_p2.ships_sunk, 1);
+
+        // A miss still passes the turn.
+        let mut missed = input.clone();
+        missed.moves.insert(1, mv(1, 2, 2));
+        assert_eq!(play(&missed).unwrap_err(), RuleError::TurnOrder);
+
+        // Strict alternation after a hit is now out of order, and vice versa.
+        let mut strict = sample();
+        strict.hit_keeps_turn = true;
+        assert_eq!(play(&strict).unwrap_err(), RuleError::TurnOrder);
+        input.hit_keeps_turn = false;
+        assert_eq!(play(&input).unwrap_err(), RuleError::TurnOrder);
+    }
+
+    fn armed(weapons: Weapons, moves: Vec<Move>) -> GameInput {
+        GameInput {
+            weapons,
+            moves,
+            ..sample()
+        }
+    }
+
+    #[test]
+    fn bomb_fires_at_a_three_by_three_area() {
+        let weapons = Weapons {
+            bombs: 1,
+            ..Weapons::default()
+        };
+        let output = play(&armed(weapons, vec![Move::weapon(1, Action::Bomb, 0, 1)])).unwrap();
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.total_moves, 1);
+        assert_eq!(output.stats_p1.ships_sunk, 2);
+        assert_eq!(output.stats_p1.hits, 4);
+        assert_eq!(output.stats_p1.misses, 2);
+        assert_eq!(output.weapons, weapons);
+
+        let twice = vec![
+            Move::weapon(1, Action::Bomb, 3, 3),
+            mv(2, 0, 0),
+            Move::weapon(1, Action::Bomb, 0, 1),
+        ];
+        assert_eq!(
+            play(&armed(weapons, twice.clone())).unwrap_err(),
+            RuleError::WeaponLimit { player: 1 }
+        );
+        assert_eq!(
+            play(&armed(Weapons::default(), twice))
+                .unwrap_err()
+                .to_string(),
+            "weapon limit reached by player 1"
+        );
+    }
+
+    #[test]
+    fn line_strikes_share_one_allowance() {
+        let weapons = Weapons {
+            line_strikes: 2,
+            ..Weapons::default()
+        };
+        let mut moves = vec![
+            Move::weapon(1, Action::RowStrike, 3, 0),
+            Move::weapon(2, Action::ColumnStrike, 3, 1),
+            // (1, 0) was already hit by the row strike; only the other three cells are fired at.
+            Move::weapon(1, Action::ColumnStrike, 1, 3),
+        ];
+        let output = play(&armed(weapons, moves.clone())).unwrap();
+        assert_eq!(output.winner, 0);
+        assert_eq!(
+            output.stats_p1,
+            PlayerStats {
+                ships_sunk: 1,
+                hits: 3,
+                misses: 4,
+                accuracy_bps: 4_285,
+            }
+        );
+        assert_eq!(output.stats_p2.misses, 4);
+
+        moves.push(mv(2, 0, 0));
+        moves.push(Move::weapon(1, Action::RowStrike, 0, 2));
+        assert_eq!(
+            play(&armed(weapons, moves)).unwrap_err(),
+            RuleError::WeaponLimit { player: 1 }
+        );
+
+        let repeated = vec![
+            Move::weapon(1, Action::RowStrike, 0, 1),
+            mv(2, 0, 0),
+            Move::weapon(1, Action::RowStrike, 2, 1),
+        ];
+        assert_eq!(
+            play(&armed(weapons, repeated)).unwrap_err(),
+            RuleError::DuplicateShot { player: 1 }
+        );
+    }
+
+    #[test]
+    fn sonar_counts_ship_cells_without_firing() {
+        let weapons = Weapons {
+            sonar_pings: 1,
+            ..Weapons::default()
+        };
+        let mut input = armed(
+            weapons,
+            vec![
+                mv(1, 0, 0),
+                Move::weapon(2, Action::Sonar, 0, 1),
+                mv(1, 1, 0),
+            ],
+        );
+        let output = play(&input).unwrap();
+        assert_eq!(
+            output.sonar,
+            [SonarPing {
+                move_index: 1,
+                player: 2,
+                x: 0,
+                y: 1,
+                ship_cells: 4,
+            }]
+        );
+        assert_eq!(output.stats_p2.hits + output.stats_p2.misses, 0);
+        assert_eq!(output.total_moves, 3);
+
+        // A sonar ping is not a hit, so it passes the turn even under hit_keeps_turn.
+        input.hit_keeps_turn = true;
+        input.moves = vec![Move::weapon(1, Action::Sonar, 3, 3), mv(2, 3, 3)];
+        assert_eq!(play(&input).unwrap().sonar[0].ship_cells, 0);
+    }
+
+    #[test]
+    fn weapons_replace_the_whole_salvo() {
+        let mut input = armed(
+            Weapons {
+                bombs: 1,
+                ..Weapons::default()
+            },
+            vec![
+                Move::weapon(1, Action::Bomb, 3, 3),
+                volley(2, &[(3, 3), (2, 2)]),
+            ],
+        );
+        input.salvo = true;
+        assert_eq!(play(&input).unwrap().total_moves, 2);
+
+        input.moves[0].extra_shots = vec![Shot { x: 0, y: 0 }];
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::SalvoSize { player: 1 }
+        );
+    }
+
+    /// The sample boards with one mine each: P1 marks (3, 3) on its bitmap, P2 lists (3, 0).
+    fn mined(moves: Vec<Move>) -> GameInput {
+        let mut board_p1 = SAMPLE_BOARD;
+        board_p1[15] = MINE;
+        GameInput {
+            mines: 1,
+            board_p1,
+            mines_p2: vec![Shot { x: 3, y: 0 }],
+            moves,
+            ..sample()
+        }
+    }
+
+    #[test]
+    fn mines_sit_on_water_in_the_agreed_number() {
+        let input = mined(Vec::new());
+        let output = play(&input).unwrap();
+        assert_eq!(
+            (output.mines, output.mine_penalty),
+            (1, MinePenalty::SkipTurn)
+        );
+        let maps = cell_maps(&input).unwrap();
+        assert_eq!(maps[0], input.board_p1.to_vec());
+        assert_eq!(maps[1][3], MINE);
+        assert_eq!(output.board_hash_p1, merkle::board_root(&maps[0], &[0; 32]));
+        assert_ne!(output.board_hash_p1, play(&sample()).unwrap().board_hash_p1);
+
+        let mut extra = mined(Vec::new());
+        extra.mines_p2.push(Shot { x: 3, y: 1 });
+        let mut doubled = mined(Vec::new());
+        doubled.mines_p2.push(Shot { x: 3, y: 0 });
+        let mut on_ship = mined(Vec::new());
+        on_ship.mines_p2 = vec![Shot { x: 1, y: 2 }];
+        let mut off_board = mined(Vec::new());
+        off_board.mines_p2 = vec![Shot { x: 4, y: 0 }];
+        for input in [extra, doubled, on_ship, off_board] {
+            assert_eq!(play(&input).unwrap_err(), RuleError::Mines { player: 2 });
+        }
+
+        let mut both = mined(Vec::new());
+        both.mines_p1 = vec![Shot { x: 3, y: 1 }];
+        assert_eq!(
+            play(&both).unwrap_err(),
+            RuleError::BoardFormat { player: 1 }
+        );
+
+        // Without mines, a mine cell is no valid cell and a mine list is one mine too many.
+        let mut unmined = mined(Vec::new());
+        unmined.mines = 0;
+        assert_eq!(play(&unmined).unwrap_err(), RuleError::InvalidCell);
+        unmined.board_p1 = SAMPLE_BOARD;
+        assert_eq!(play(&unmined).unwrap_err(), RuleError::Mines { player: 2 });
+
+        let mut classic_mines = classic();
+        classic_mines.mines = 1;
+        classic_mines.mines_p1 = vec![Shot { x: 9, y: 9 }];
+        classic_mines.mines_p2 = vec![Shot { x: 9, y: 9 }];
+        let output = play(&classic_mines).unwrap();
+        assert_eq!((output.winner, output.total_moves), (1, 33));
+        assert_ne!(
+            output.board_hash_p1,
+            play(&classic()).unwrap().board_hash_p1
+        );
+    }
+
+    #[test]
+    fn mine_costs_the_shooter_a_turn() {
+        let input = mined(vec![
+            mv(1, 0, 0),
+            mv(2, 3, 3),
+            mv(1, 1, 0),
+            mv(1, 0, 2),
+            mv(2, 3, 2),
+            mv(1, 1, 2),
+        ]);
+        let output = play(&input).unwrap();
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.total_moves, 6);
+        assert_eq!(output.stats_p2.misses, 2);
+        assert_eq!(
+            output.mine_hits,
+            vec![MineHit {
+                move_index: 1,
+                player: 2,
+                x: 3,
+                y: 3,
+                revealed: None,
+            }]
+        );
+
+        // P2 may not take the skipped turn.
+        let mut alternating = input.clone();
+        alternating.moves.swap(3, 4);
+        assert_eq!(play(&alternating).unwrap_err(), RuleError::TurnOrder);
+
+        // Turns owed by both players cancel out.
+        let mut even = mined(vec![mv(1, 3, 0), mv(2, 3, 3), mv(1, 0, 0), mv(2, 3, 2)]);
+        assert_eq!(play(&even).unwrap().mine_hits.len(), 2);
+        even.moves.insert(3, mv(1, 1, 0));
+        assert_eq!(play(&even).unwrap_err(), RuleError::TurnOrder);
+
+        // The skip also costs a turn kept by a hit.
+        let mut kept = mined(vec![
+            volley(1, &[(0, 0), (3, 0)]),
+            volley(2, &[(2, 1), (3, 1)]),
+        ]);
+        kept.salvo = true;
+        kept.hit_keeps_turn = true;
+        assert_eq!(play(&kept).unwrap().mine_hits.len(), 1);
+        kept.moves[1] = volley(1, &[(1, 0), (0, 2)]);
+        assert_eq!(play(&kept).unwrap_err(), RuleError::TurnOrder);
+    }
+
+    #[test]
+    fn mine_can_reveal_a_cell_instead() {
+        let mut input = mined(sample().moves);
+        input.mine_penalty = MinePenalty::RevealCell;
+        input.moves.splice(2..2, [mv(1, 3, 0), mv(2, 2, 3)]);
+        let output = play(&input).unwrap();
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.total_moves, 9);
+        // Each shooter gives away their first own ship cell the opponent has not shot.
+        let revealed: Vec<_> = output
+            .mine_hits
+            .iter()
+            .map(|hit| (hit.player, hit.x, hit.y, hit.revealed))
+            .collect();
+        assert_eq!(
+            revealed,
+            vec![
+                (2, 3, 3, Some(Shot { x: 1, y: 0 })),
+                (1, 3, 0, Some(Shot { x: 0, y: 0 })),
+            ]
+        );
+    }
+
+    #[test]
+    fn resigning_hands_the_game_to_the_opponent() {
+        let mut input = sample();
+        input.moves = vec![mv(1, 0, 0), mv(2, 3, 3), Move::resign(2)];
+        let output = play(&input).unwrap();
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.outcome, Outcome::Resigned);
+        assert_eq!(output.outcome.code(), 2);
+        assert_eq!(output.total_moves, 3);
+
+        // Out of turn is fine; nothing may follow a resignation.
+        input.moves = vec![mv(1, 0, 0), Move::resign(1)];
+        assert_eq!(play(&input).unwrap().winner, 2);
+        input.moves.push(mv(2, 0, 0));
+        assert_eq!(play(&input).unwrap_err(), RuleError::MovesAfterGameOver);
+
+        input.moves = vec![Move::resign(3)];
+        assert_eq!(play(&input).unwrap_err(), RuleError::TurnOrder);
+    }
+
+    #[test]
+    fn unfinished_game_has_no_winner() {
+        let mut input = sample();
+        input.moves.truncate(6);
+        let output = play(&input).unwrap();
+        assert_eq!(output.winner, 0);
+        assert_eq!(output.outcome, Outcome::Unfinished);
+        assert_eq!(output.total_moves, 6);
+    }
+
+    #[test]
+    fn board_errors_are_reported_per_player() {
+        let mut input = sample();
+        input.board_p2[15] = 2;
+        assert_eq!(play(&input).unwrap_err(), RuleError::InvalidCell);
+
+        let mut input = sample();
+        input.board_p2[15] = 1;
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::ShipCount { player: 2 }
+        );
+
+        // A 2x2 block is one ship of size 4.
+        let mut input = sample();
+        input.board_p1 = [1, 1, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(play(&input).unwrap_err(), RuleError::Layout { player: 1 });
+
+        // Ships that touch end-to-end form one component.
+        let mut input = sample();
+        input.board_p2 = [1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(play(&input).unwrap_err(), RuleError::Layout { player: 2 });
+    }
+
+    #[test]
+    fn move_errors_match_guest_messages() {
+        let mut input = sample();
+        input.moves.push(mv(2, 2, 2));
+        let err = play(&input).unwrap_err();
+        assert_eq!(err.to_string(), "moves after game over are not allowed");
+
+        let mut input = sample();
+        input.moves = vec![mv(2, 0, 0)];
+        assert_eq!(play(&input).unwrap_err().to_string(), "invalid turn order");
+
+        let mut input = sample();
+        input.moves = vec![mv(1, 4, 0)];
+        assert_eq!(
+            play(&input).unwrap_err().to_string(),
+            "invalid move position"
+        );
+
+        let mut input = sample();
+        input.moves = vec![mv(1, 0, 0), mv(2, 1, 0), mv(1, 0, 0)];
+        assert_eq!(
+            play(&input).unwrap_err().to_string(),
+            "duplicate shot by player 1"
+        );
+    }
+
+    fn classic_fleet() -> Vec<Ship> {
+        let h = Orientation::Horizontal;
+        let v = Orientation::Vertical;
+        [
+            (0, 0, h, 5),
+            (0, 2, h, 4),
+            (0, 4, h, 3),
+            (9, 0, v, 3),
+            (5, 9, h, 2),
+        ]
+        .into_iter()
+        .map(|(x, y, orientation, length)| Ship {
+            x,
+            y,
+            orientation,
+            length,
+        })
+        .collect()
+    }
+
+    fn classic() -> GameInput {
+        let fleet = classic_fleet();
+        let grid = classic_grid();
+        let targets: Vec<(u8, u8)> = (0..10u8)
+            .flat_map(|y| (0..10u8).map(move |x| (x, y)))
+            .filter(|&(x, y)| {
+                fleet.iter().any(|ship| {
+                    ship.cells(&grid).unwrap() & grid.cell(x.into(), y.into()).unwrap() != 0
+                })
+            })
+            .collect();
+        assert_eq!(targets.len(), 17);
+
+        let mut moves = Vec::new();
+        for (i, &(x, y)) in targets.iter().enumerate() {
+            moves.push(mv(1, x, y));
+            if i + 1 < targets.len() {
+                // P2 walks the bottom-left water cells and never finishes the fleet.
+                moves.push(mv(2, (i % 5) as u8, 6 + (i / 5) as u8));
+            }
+        }
+
+        GameInput {
+            session_id: 7,
+            mode: GameMode::Classic,
+            no_touching: true,
+            salvo: false,
+            hit_keeps_turn: false,
+            weapons: Weapons::default(),
+            mines: 0,
+            mine_penalty: MinePenalty::default(),
+            board_p1: [0; 16],
+            board_p2: [0; 16],
+            fleet_p1: fleet.clone(),
+            fleet_p2: fleet,
+            mines_p1: Vec::new(),
+            mines_p2: Vec::new(),
+            board_salt_p1: [0; 32],
+            board_salt_p2: [0; 32],
+            player_keys: None,
+            moves,
+        }
+    }
+
+    #[test]
+    fn classic_game_is_played_on_ten_by_ten() {
+        let input = classic();
+        let output = play(&input).unwrap();
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.total_moves, 33);
+        assert_eq!(output.mode, GameMode::Classic);
+        assert!(output.no_touching);
+        assert_eq!(output.stats_p1.ships_sunk, 5);
+        assert_eq!(output.stats_p1.hits, 17);
+        assert_eq!(output.stats_p2.misses, 16);
+
+        let ships =
+            fleet::rasterize(&classic_grid(), &input.fleet_p1, &CLASSIC_FLEET, false, 1).unwrap();
+        let cells = cell_map(&classic_grid(), occupied(&ships));
+        assert_eq!(cells.len(), 100);
+        assert_eq!(output.board_hash_p1, merkle::board_root(&cells, &[0; 32]));
+
+        let mut input = classic();
+        input.moves = vec![mv(1, 9, 9), mv(2, 10, 0)];
+        assert_eq!(play(&input).unwrap_err(), RuleError::MovePosition);
+    }
+
+    #[test]
+    fn classic_mode_rejects_bitmaps_and_bad_fleets() {
+        let mut input = classic();
+        input.board_p2 = SAMPLE_BOARD;
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::BoardFormat { player: 2 }
+        );
+
+        let mut input = classic();
+        input.fleet_p1.pop();
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::FleetComposition { player: 1 }
+        );
+
+        // The cruiser at (0, 4) touches a destroyer moved to (3, 5) only at a corner.
+        let mut input = classic();
+        input.fleet_p2[4] = Ship {
+            x: 3,
+            y: 5,
+            orientation: Orientation::Horizontal,
+            length: 2,
+        };
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::ShipsTouching { player: 2 }
+        );
+        input.no_touching = false;
+        assert!(play(&input).is_ok());
+
+        let mut input = sample();
+        input.fleet_p1 = classic_fleet();
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::BoardFormat { player: 1 }
+        );
+        input.board_p1 = [0; 16];
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::FleetComposition { player: 1 }
+        );
+    }
+
+    #[test]
+    fn demo_no_touching_rejects_diagonal_ships() {
+        let mut input = sample();
+        input.no_touching = true;
+        assert!(play(&input).is_ok());
+
+        input.board_p1 = [1, 1, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::ShipsTouching { player: 1 }
+        );
+    }
+
+    #[test]
+    fn demo_ship_lists_hash_like_the_equivalent_bitmap() {
+        let bitmap = play(&sample()).unwrap();
+
+        let mut input = sample();
+        input.board_p1 = [0; 16];
+        input.fleet_p1 = [(0, 0), (0, 2)]
+            .into_iter()
+            .map(|(x, y)| Ship {
+                x,
+                y,
+                orientation: Orientation::Horizontal,
+                length: 2,
+            })
+            .collect();
+        let listed = play(&input).unwrap();
+        assert_eq!(listed.board_hash_p1, bitmap.board_hash_p1);
+        assert_eq!(listed.winner, bitmap.winner);
+
+        input.fleet_p1[1].y = 3;
+        input.fleet_p1[1].orientation = Orientation::Vertical;
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::ShipOutOfBounds { player: 1 }
+        );
+    }
+
+    #[test]
+    fn bent_bitmap_ships_are_rejected() {
+        let grid = classic_grid();
+        let l_shape = [(0, 0), (1, 0), (1, 1)]
+            .iter()
+            .fold(0u128, |acc, &(x, y)| acc | grid.cell(x, y).unwrap());
+        assert!(!bitmap_matches_fleet(&grid, l_shape, &[3]));
+
+        let straight = l_shape & !grid.cell(1, 1).unwrap() | grid.cell(2, 0).unwrap();
+        assert!(bitmap_matches_fleet(&grid, straight, &[3]));
+        assert!(!bitmap_matches_fleet(&grid, straight, &[2]));
+    }
+
+    const SECRET_KEYS: [[u8; 32]; 2] = [[1; 32], [2; 32]];
+
+    fn signed() -> GameInput {
+        let mut input = GameInput {
+            player_keys: Some(SECRET_KEYS.map(|key| transcript::public_key(&key))),
+            ..sample()
+        };
+        transcript::sign_moves(input.session_id, &mut input.moves, &SECRET_KEYS);
+        input
+    }
+
+    #[test]
+    fn signed_game_commits_player_keys() {
+        let input = signed();
+        let output = play(&input).unwrap();
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.player_keys, input.player_keys);
+        assert_eq!(play(&sample()).unwrap().player_keys, None);
+    }
+
+    #[test]
+    fn transcript_hash_commits_to_every_move_in_order() {
+        let input = sample();
+        let output = play(&input).unwrap();
+        let hashes = transcript::move_hashes(input.session_id, &input.moves);
+        assert_eq!(hashes.len(), 7);
+        assert_eq!(output.transcript_hash, hashes[6]);
+        // Signatures are not part of the chain.
+        assert_eq!(
+            play(&signed()).unwrap().transcript_hash,
+            output.transcript_hash
+        );
+
+        let mut reordered = sample();
+        reordered.moves.swap(0, 2);
+        assert_ne!(
+            play(&reordered).unwrap().transcript_hash,
+            output.transcript_hash
+        );
+
+        let other_session = GameInput {
+            session_id: 43,
+            ..sample()
+        };
+        assert_ne!(
+            play(&other_session).unwrap().transcript_hash,
+            output.transcript_hash
+        );
+        assert_eq!(transcript::transcript_hash(42, &[]), [0; 32]);
+    }
+
+    #[test]
+    fn rewritten_or_unsigned_moves_are_rejected() {
+        let mut input = signed();
+        input.moves[3].x = 0;
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::Signature { player: 2 }
+        );
+
+        let mut input = signed();
+        input.moves[6].signature.clear();
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::Signature { player: 1 }
+        );
+
+        // Dropping a move breaks the chain for every later signature.
+        let mut input = signed();
+        input.moves.remove(1);
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::Signature { player: 1 }
+        );
+
+        // P2 cannot sign for P1.
+        let mut input = signed();
+        input.moves[2].signature = input.moves[1].signature.clone();
+        assert_eq!(
+            play(&input).unwrap_err(),
+            RuleError::Signature { player: 1 }
+        );
+    }
+
+    fn turn_by_turn(input: &GameInput) -> (Vec<turn::TurnJournal>, turn::MatchState) {
+        let mut state = turn::MatchState::new(input).unwrap();
+        let turns = input
+            .moves
+            .iter()
+            .map(|mv| turn::turn(&mut state, mv).unwrap())
+            .collect();
+        (turns, state)
+    }
+
+    #[test]
+    fn turn_by_turn_replay_commits_the_played_journal() {
+        let weapons = Weapons {
+            bombs: 1,
+            line_strikes: 1,
+            sonar_pings: 1,
+        };
+        let mut classic_weapons = classic();
+        classic_weapons.weapons = weapons;
+        classic_weapons
+            .moves
+            .insert(0, Move::weapon(1, Action::Sonar, 4, 4));
+        classic_weapons
+            .moves
+            .insert(1, Move::weapon(2, Action::Bomb, 8, 8));
+
+        let mut mined_reveal = mined(sample().moves);
+        mined_reveal.mine_penalty = MinePenalty::RevealCell;
+        let mined_skip = mined(vec![mv(1, 0, 0), mv(2, 3, 3), mv(1, 1, 0), mv(1, 3, 0)]);
+
+        for input in [
+            sample(),
+            signed(),
+            classic(),
+            classic_weapons,
+            mined_skip,
+            mined_reveal,
+        ] {
+            let (turns, state) = turn_by_turn(&input);
+            assert_eq!(state.output(), play(&input).unwrap());
+            assert_eq!(turn::aggregate(&input, &turns, &state), play(&input));
+        }
+    }
+
+    #[test]
+    fn aggregate_rejects_broken_turn_chains() {
+        let input = sample();
+        let (turns, state) = turn_by_turn(&input);
+
+        let mut skipped = turns.clone();
+        skipped.remove(3);
+        assert_eq!(
+            turn::aggregate(&input, &skipped, &state),
+            Err(RuleError::TurnChain { index: 3 })
+        );
+
+        // A turn proven from another game's boards does not continue this one.
+        let mut other = sample();
+        other.board_p2 = [
+            0, 0, 1, 1, //
+            0, 0, 0, 0, //
+            0, 0, 1, 1, //
+            0, 0, 0, 0, //
+        ];
+        let (other_turns, _) = turn_by_turn(&GameInput {
+            moves: vec![mv(1, 2, 0)],
+            ..other
+        });
+        let mut spliced = turns.clone();
+        spliced[0] = other_turns[0];
+        assert_eq!(
+            turn::aggregate(&input, &spliced, &state),
+            Err(RuleError::TurnChain { index: 0 })
+        );
+
+        let (_, early) = turn_by_turn(&GameInput {
+            moves: input.moves[..6].to_vec(),
+            ..sample()
+        });
+        assert_eq!(
+            turn::aggregate(&input, &turns, &early),
+            Err(RuleError::FinalState)
+        );
+
+        // The state a signed move is applied to still needs its signature.
+        let mut state = turn::MatchState::new(&signed()).unwrap();
+        assert_eq!(
+            turn::turn(&mut state, &mv(1, 0, 0)),
+            Err(RuleError::Signature { player: 1 })
+        );
+    }
+}
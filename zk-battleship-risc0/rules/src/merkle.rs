@@ -0,0 +1,201 @@
+//! Board commitments: a Merkle tree over the salted cells of a board, so a single cell can
+//! be opened without revealing the rest of the board.
+//!
+//! Cell `i` (row-major, as in [`cell_map`](crate::cell_map)) gets the salt
+//! `sha256(board_salt || i)` and the leaf `sha256(0x00 || i || cell || salt)`, with `i` as
+//! four big-endian bytes and `cell` 1 for a ship cell, [`MINE`] for a mine, 0 for water.
+//! Leaves are padded with all-zero hashes to a power of two and paired with
+//! `sha256(0x01 || left || right)`; the root is the board commitment. Revealing a cell's
+//! salt reveals nothing about the others as long as `board_salt` stays secret.
+
+use crate::MINE;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// What a player reveals to prove one cell of their committed board.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CellOpening {
+    /// Row-major cell index.
+    pub index: u32,
+    pub ship: bool,
+    /// Whether the cell holds a mine, in a game with mines.
+    #[serde(default)]
+    pub mine: bool,
+    /// The cell's salt, derived from the board salt.
+    pub salt: [u8; 32],
+    /// Sibling hashes from the leaf up to the root.
+    pub siblings: Vec<[u8; 32]>,
+}
+
+pub fn cell_salt(board_salt: &[u8; 32], index: u32) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(board_salt);
+    hasher.update(index.to_be_bytes());
+    hasher.finalize().into()
+}
+
+pub fn leaf_hash(index: u32, cell: u8, salt: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0]);
+    hasher.update(index.to_be_bytes());
+    hasher.update([cell]);
+    hasher.update(salt);
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([1]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Every level of the tree over `cells`, leaves first and the root last.
+fn levels(cells: &[u8], board_salt: &[u8; 32]) -> Vec<Vec<[u8; 32]>> {
+    let mut leaves: Vec<[u8; 32]> = cells
+        .iter()
+        .enumerate()
+        .map(|(index, &cell)| {
+            let index = index as u32;
+            leaf_hash(index, cell, &cell_salt(board_salt, index))
+        })
+        .collect();
+    leaves.resize(cells.len().next_power_of_two(), [0; 32]);
+
+    let mut levels = vec![leaves];
+    while levels[levels.len() - 1].len() > 1 {
+        let level = &levels[levels.len() - 1];
+        let parents = level
+            .chunks(2)
+            .map(|pair| node_hash(&pair[0], &pair[1]))
+            .collect();
+        levels.push(parents);
+    }
+    levels
+}
+
+/// The commitment to a cell map (one byte per cell: 0, 1 or [`MINE`]).
+pub fn board_root(cells: &[u8], board_salt: &[u8; 32]) -> [u8; 32] {
+    levels(cells, board_salt)
+        .last()
+        .and_then(|root| root.first())
+        .copied()
+        .unwrap_or([0; 32])
+}
+
+/// Opens cell `index` of a cell map; `None` if the board has no such cell.
+pub fn open_cell(cells: &[u8], board_salt: &[u8; 32], index: u32) -> Option<CellOpening> {
+    let cell = *cells.get(index as usize)?;
+    let levels = levels(cells, board_salt);
+    let mut position = index as usize;
+    let siblings = levels[..levels.len() - 1]
+        .iter()
+        .map(|level| {
+            let sibling = level[position ^ 1];
+            position /= 2;
+            sibling
+        })
+        .collect();
+    Some(CellOpening {
+        index,
+        ship: cell == 1,
+        mine: cell == MINE,
+        salt: cell_salt(board_salt, index),
+        siblings,
+    })
+}
+
+/// Whether `opening` proves a cell of the board committed to by `root`.
+pub fn verify_opening(root: &[u8; 32], opening: &CellOpening) -> bool {
+    let cell = if opening.mine {
+        MINE
+    } else {
+        u8::from(opening.ship)
+    };
+    let mut hash = leaf_hash(opening.index, cell, &opening.salt);
+    let mut position = opening.index;
+    for sibling in &opening.siblings {
+        hash = if position & 1 == 0 {
+            node_hash(&hash, sibling)
+        } else {
+            node_hash(sibling, &hash)
+        };
+        position /= 2;
+    }
+    hash == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: [u8; 16] = [
+        1, 1, 0, 0, //
+        0, 0, 0, 0, //
+        1, 1, 0, 0, //
+        0, 0, 0, 0, //
+    ];
+    const SALT: [u8; 32] = [7; 32];
+
+    #[test]
+    fn every_cell_opens_against_the_root() {
+        let root = board_root(&BOARD, &SALT);
+        for index in 0..16 {
+            let opening = open_cell(&BOARD, &SALT, index).unwrap();
+            assert_eq!(opening.ship, BOARD[index as usize] == 1);
+            assert_eq!(opening.siblings.len(), 4);
+            assert!(verify_opening(&root, &opening));
+        }
+        assert_eq!(open_cell(&BOARD, &SALT, 16), None);
+    }
+
+    #[test]
+    fn openings_cannot_lie_about_a_cell() {
+        let root = board_root(&BOARD, &SALT);
+        let opening = open_cell(&BOARD, &SALT, 0).unwrap();
+
+        let flipped = CellOpening {
+            ship: false,
+            ..opening.clone()
+        };
+        assert!(!verify_opening(&root, &flipped));
+
+        // Cell 1's leaf does not open cell 3, even with cell 1's path.
+        let moved = CellOpening {
+            index: 3,
+            ..open_cell(&BOARD, &SALT, 1).unwrap()
+        };
+        assert!(!verify_opening(&root, &moved));
+
+        assert!(!verify_opening(&board_root(&BOARD, &[8; 32]), &opening));
+    }
+
+    #[test]
+    fn classic_boards_pad_to_a_power_of_two() {
+        let mut cells = [0u8; 100];
+        cells[99] = 1;
+        let root = board_root(&cells, &SALT);
+        let opening = open_cell(&cells, &SALT, 99).unwrap();
+        assert_eq!(opening.siblings.len(), 7);
+        assert!(opening.ship);
+        assert!(verify_opening(&root, &opening));
+    }
+
+    #[test]
+    fn mines_open_as_their_own_cell_type() {
+        let mut cells = BOARD;
+        cells[15] = MINE;
+        let root = board_root(&cells, &SALT);
+        assert_ne!(root, board_root(&BOARD, &SALT));
+
+        let opening = open_cell(&cells, &SALT, 15).unwrap();
+        assert!(opening.mine && !opening.ship);
+        assert!(verify_opening(&root, &opening));
+        let water = CellOpening {
+            mine: false,
+            ..opening
+        };
+        assert!(!verify_opening(&root, &water));
+    }
+}
@@ -0,0 +1,338 @@
+//! Team games: two teams of two players, each team sharing one board.
+//!
+//! Every member places part of their team's fleet; together the two placements must make up
+//! a valid board of the mode (the demo's two size-2 ships, or the classic fleet), with each
+//! member placing at least one ship and no cell claimed by both. The team commits to the
+//! combined board under one salt, and the match is then an ordinary two-player game between
+//! the two combined boards: one shot per move, no special weapons.
+//!
+//! Players 1 and 3 form team 1, players 2 and 4 team 2, and they shoot in seat order 1, 2,
+//! 3, 4, so the teams alternate and so do the members of each team. Any player may resign
+//! for their team at any time.
+
+use crate::transcript::{self, PublicKey};
+use crate::{
+    play, Action, GameInput, GameMode, MinePenalty, Move, Outcome, PlayerStats, RuleError, Ship,
+    Weapons,
+};
+use serde::{Deserialize, Serialize};
+
+pub const TEAM_PLAYERS: u8 = 4;
+
+/// One member's share of a team board, given like `board_p1`/`fleet_p1` of a two-player
+/// game but holding only the ships this member placed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Member {
+    #[serde(default)]
+    pub board: [u8; 16],
+    #[serde(default)]
+    pub fleet: Vec<Ship>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Team {
+    pub members: [Member; 2],
+    /// Secret salt of the team's board commitment (see [`crate::merkle`]), shared by both
+    /// members.
+    #[serde(default)]
+    pub board_salt: [u8; 32],
+}
+
+/// A team game, as read by the teams guest.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TeamInput {
+    pub session_id: u32,
+    #[serde(default)]
+    pub mode: GameMode,
+    #[serde(default)]
+    pub no_touching: bool,
+    /// Player `n` is `teams[(n - 1) % 2].members[(n - 1) / 2]`.
+    pub teams: [Team; 2],
+    /// Ed25519 public keys of players 1 to 4. When set, every move must be signed by its
+    /// player.
+    #[serde(default)]
+    pub player_keys: Option<[PublicKey; 4]>,
+    pub moves: Vec<Move>,
+}
+
+/// Journal committed by the teams guest.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TeamOutput {
+    pub session_id: u32,
+    /// The winning team, 1 or 2, or 0 while the match is unfinished.
+    pub winner: u8,
+    pub outcome: Outcome,
+    /// [`crate::merkle::board_root`] of each team's combined board and salt.
+    pub board_hashes: [[u8; 32]; 2],
+    /// [`transcript::transcript_hash`] of `moves`, as signed by the players.
+    pub transcript_hash: [u8; 32],
+    pub total_moves: u32,
+    pub mode: GameMode,
+    pub no_touching: bool,
+    /// Shots fired by each team, both members together.
+    pub stats: [PlayerStats; 2],
+    pub player_keys: Option<[PublicKey; 4]>,
+}
+
+/// The team `player` plays for, or `None` for a player outside 1 to 4.
+pub fn team_of(player: u8) -> Option<u8> {
+    (1..=TEAM_PLAYERS).contains(&player).then(|| 2 - player % 2)
+}
+
+/// Merges both members' placements into the team's board and fleet.
+///
+/// Only the members' own shares are checked here; the combined fleet is validated by the
+/// two-player rules. Both members must give their share the same way, as board cells or as
+/// a ship list, since the two-player rules read only one of them.
+fn combine(team: &Team, number: u8) -> Result<([u8; 16], Vec<Ship>), RuleError> {
+    let mut board = [0; 16];
+    let mut fleet = Vec::new();
+    let mut lists_ships = None;
+    for (member, player) in team.members.iter().zip([number, number + 2]) {
+        let cells = member.board.iter().filter(|&&cell| cell != 0).count();
+        if cells + member.fleet.len() == 0 {
+            return Err(RuleError::MemberBoard { player });
+        }
+        let listed = !member.fleet.is_empty();
+        if (listed && cells != 0) || *lists_ships.get_or_insert(listed) != listed {
+            return Err(RuleError::MemberBoard { player });
+        }
+        for (merged, &cell) in board.iter_mut().zip(&member.board) {
+            if *merged != 0 && cell != 0 {
+                return Err(RuleError::MemberBoard { player });
+            }
+            *merged |= cell;
+        }
+        fleet.extend_from_slice(&member.fleet);
+    }
+    Ok((board, fleet))
+}
+
+/// Whether the shots of `moves` come in seat order; a resignation ends the match, so it
+/// can only be the last move of a valid game.
+fn check_rotation(moves: &[Move]) -> Result<(), RuleError> {
+    let mut expected = 1;
+    for mv in moves.iter().filter(|mv| mv.action != Action::Resign) {
+        if mv.player != expected {
+            return Err(RuleError::TurnOrder);
+        }
+        expected = expected % TEAM_PLAYERS + 1;
+    }
+    Ok(())
+}
+
+/// Validates both team boards and replays `input.moves`, returning the journal to commit.
+pub fn play_teams(input: &TeamInput) -> Result<TeamOutput, RuleError> {
+    let transcript_hash = match &input.player_keys {
+        Some(keys) => transcript::verify_moves(input.session_id, &input.moves, keys)?,
+        None => transcript::transcript_hash(input.session_id, &input.moves),
+    };
+    let moves = input
+        .moves
+        .iter()
+        .map(|mv| {
+            let team = team_of(mv.player).ok_or(RuleError::TurnOrder)?;
+            Ok(Move {
+                player: team,
+                signature: Vec::new(),
+                ..mv.clone()
+            })
+        })
+        .collect::<Result<Vec<_>, RuleError>>()?;
+
+    let [team1, team2] = &input.teams;
+    let (board_p1, fleet_p1) = combine(team1, 1)?;
+    let (board_p2, fleet_p2) = combine(team2, 2)?;
+    let output = play(&GameInput {
+        session_id: input.session_id,
+        mode: input.mode,
+        no_touching: input.no_touching,
+        salvo: false,
+        hit_keeps_turn: false,
+        weapons: Weapons::default(),
+        mines: 0,
+        mine_penalty: MinePenalty::default(),
+        board_p1,
+        board_p2,
+        fleet_p1,
+        fleet_p2,
+        mines_p1: Vec::new(),
+        mines_p2: Vec::new(),
+        board_salt_p1: team1.board_salt,
+        board_salt_p2: team2.board_salt,
+        player_keys: None,
+        moves,
+    })?;
+    check_rotation(&input.moves)?;
+
+    Ok(TeamOutput {
+        session_id: input.session_id,
+        winner: output.winner,
+        outcome: output.outcome,
+        board_hashes: [output.board_hash_p1, output.board_hash_p2],
+        transcript_hash,
+        total_moves: output.total_moves,
+        mode: input.mode,
+        no_touching: input.no_touching,
+        stats: [output.stats_p1, output.stats_p2],
+        player_keys: input.player_keys,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fleet::Orientation;
+
+    /// One ship of the demo fleet per member: the top ship for the first member and the
+    /// lower one for the second, as on the sample board.
+    fn team() -> Team {
+        let share = |y: usize| Member {
+            board: std::array::from_fn(|idx| u8::from(idx == 4 * y || idx == 4 * y + 1)),
+            fleet: Vec::new(),
+        };
+        Team {
+            members: [share(0), share(2)],
+            board_salt: [0; 32],
+        }
+    }
+
+    /// Team 1 sinks team 2's ships, each member hitting one cell of each.
+    fn match_input() -> TeamInput {
+        TeamInput {
+            session_id: 9,
+            mode: GameMode::Demo,
+            no_touching: false,
+            teams: [team(), team()],
+            player_keys: None,
+            moves: vec![
+                Move::new(1, 0, 0),
+                Move::new(2, 3, 3),
+                Move::new(3, 1, 0),
+                Move::new(4, 3, 2),
+                Move::new(1, 0, 2),
+                Move::new(2, 2, 3),
+                Move::new(3, 1, 2),
+            ],
+        }
+    }
+
+    #[test]
+    fn team_wins_on_the_combined_board() {
+        let output = play_teams(&match_input()).unwrap();
+        assert_eq!(output.winner, 1);
+        assert_eq!(output.outcome, Outcome::AllSunk);
+        assert_eq!(output.total_moves, 7);
+        assert_eq!((output.stats[0].hits, output.stats[0].ships_sunk), (4, 2));
+        assert_eq!(output.stats[1].misses, 3);
+
+        // The team commitment is the board commitment of the combined board.
+        let board = [1, 1, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            output.board_hashes,
+            [crate::merkle::board_root(&board, &[0; 32]); 2]
+        );
+    }
+
+    #[test]
+    fn teammates_alternate_and_either_may_resign() {
+        let mut swapped = match_input();
+        swapped.moves.swap(0, 2);
+        assert_eq!(play_teams(&swapped), Err(RuleError::TurnOrder));
+
+        let mut outsider = match_input();
+        outsider.moves[1].player = 5;
+        assert_eq!(play_teams(&outsider), Err(RuleError::TurnOrder));
+
+        let mut resigned = match_input();
+        resigned.moves.truncate(3);
+        resigned.moves.push(Move::resign(2));
+        let output = play_teams(&resigned).unwrap();
+        assert_eq!((output.winner, output.outcome), (1, Outcome::Resigned));
+
+        let mut unfinished = match_input();
+        unfinished.moves.pop();
+        assert_eq!(play_teams(&unfinished).unwrap().winner, 0);
+    }
+
+    #[test]
+    fn members_share_one_valid_fleet() {
+        let mut idle = match_input();
+        idle.teams[1].members[1] = Member::default();
+        assert_eq!(play_teams(&idle), Err(RuleError::MemberBoard { player: 4 }));
+
+        let mut overlapping = match_input();
+        overlapping.teams[0].members[1] = overlapping.teams[0].members[0].clone();
+        assert_eq!(
+            play_teams(&overlapping),
+            Err(RuleError::MemberBoard { player: 3 })
+        );
+
+        // Both members placing a full fleet leaves the team with too many ships.
+        let mut doubled = match_input();
+        doubled.teams[0].members[1].board[14] = 1;
+        doubled.teams[0].members[1].board[15] = 1;
+        assert_eq!(
+            play_teams(&doubled),
+            Err(RuleError::ShipCount { player: 1 })
+        );
+
+        let ship = |y| Ship {
+            x: 0,
+            y,
+            orientation: Orientation::Horizontal,
+            length: 2,
+        };
+        let mut fleets = match_input();
+        fleets.teams[1].members = [
+            Member {
+                board: [0; 16],
+                fleet: vec![ship(0)],
+            },
+            Member {
+                board: [0; 16],
+                fleet: vec![ship(2)],
+            },
+        ];
+        assert_eq!(
+            play_teams(&fleets).unwrap(),
+            play_teams(&match_input()).unwrap()
+        );
+
+        // A ship list next to board cells, from one member or across the team, would hide
+        // the cells from the two-player rules.
+        let mut mixed = match_input();
+        mixed.teams[1].members[1] = fleets.teams[1].members[1].clone();
+        assert_eq!(
+            play_teams(&mixed),
+            Err(RuleError::MemberBoard { player: 4 })
+        );
+
+        let mut both = match_input();
+        both.teams[0].members[0].fleet = vec![ship(2)];
+        assert_eq!(play_teams(&both), Err(RuleError::MemberBoard { player: 1 }));
+    }
+
+    #[test]
+    fn signed_team_moves_check_each_members_key() {
+        let secret_keys = [[1; 32], [2; 32], [3; 32], [4; 32]];
+        let mut input = match_input();
+        transcript::sign_moves(input.session_id, &mut input.moves, &secret_keys);
+        input.player_keys = Some(secret_keys.map(|key| transcript::public_key(&key)));
+        let output = play_teams(&input).unwrap();
+        assert_eq!(
+            output.transcript_hash,
+            transcript::transcript_hash(input.session_id, &input.moves)
+        );
+
+        // P3's move signed with P1's key.
+        let mut impostor = input.clone();
+        let mut own = impostor.moves[..3].to_vec();
+        transcript::sign_moves(input.session_id, &mut own, &[[1; 32], [2; 32], [1; 32]]);
+        impostor.moves[2] = own[2].clone();
+        assert_eq!(
+            play_teams(&impostor),
+            Err(RuleError::Signature { player: 3 })
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Hash-chained, optionally player-signed move transcripts.
+//!
+//! Move `i` is described by [`move_message`]: the session, `i`, the hash of move `i - 1`'s
+//! message (all zero for the first move) and the move itself. Since every message hashes in
+//! the one before it, the last move's hash commits to the whole game (the journal's
+//! `transcript_hash`), and a signature over a message also pins the moves that led up to
+//! it, so the prover cannot drop, reorder or rewrite moves without the players' keys.
+
+use crate::{Action, Move, RuleError};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+/// An ed25519 public key.
+pub type PublicKey = [u8; 32];
+
+/// The bytes signed for move `index`: `session_id` and `index` big-endian, `previous`, then
+/// the player, `(x, y)`, the action, every extra shot and, for a free-for-all shot, the
+/// target. Two-player moves have no target byte, so their messages are unchanged.
+pub fn move_message(session_id: u32, index: u32, previous: &[u8; 32], mv: &Move) -> Vec<u8> {
+    let mut message = Vec::with_capacity(45 + 2 * mv.extra_shots.len());
+    message.extend_from_slice(&session_id.to_be_bytes());
+    message.extend_from_slice(&index.to_be_bytes());
+    message.extend_from_slice(previous);
+    message.extend_from_slice(&[mv.player, mv.x, mv.y, action_code(mv.action)]);
+    for shot in &mv.extra_shots {
+        message.extend_from_slice(&[shot.x, shot.y]);
+    }
+    if mv.target != 0 {
+        message.push(mv.target);
+    }
+    message
+}
+
+/// sha256 of a move message, chained into the next move's message.
+pub fn move_hash(message: &[u8]) -> [u8; 32] {
+    Sha256::digest(message).into()
+}
+
+/// The chained hash after each move, in order; the last one is the transcript hash.
+pub fn move_hashes(session_id: u32, moves: &[Move]) -> Vec<[u8; 32]> {
+    let mut hashes = Vec::with_capacity(moves.len());
+    let mut previous = [0; 32];
+    for (index, mv) in moves.iter().enumerate() {
+        previous = move_hash(&move_message(session_id, index as u32, &previous, mv));
+        hashes.push(previous);
+    }
+    hashes
+}
+
+/// The hash committing to every move in order; all zero for a game without moves.
+pub fn transcript_hash(session_id: u32, moves: &[Move]) -> [u8; 32] {
+    move_hashes(session_id, moves)
+        .last()
+        .copied()
+        .unwrap_or([0; 32])
+}
+
+/// The public key for an ed25519 secret key.
+pub fn public_key(secret_key: &[u8; 32]) -> PublicKey {
+    SigningKey::from_bytes(secret_key)
+        .verifying_key()
+        .to_bytes()
+}
+
+/// Signs every move with its player's key from `secret_keys` (P1's first).
+///
+/// Panics if a move's player has no key.
+pub fn sign_moves(session_id: u32, moves: &mut [Move], secret_keys: &[[u8; 32]]) {
+    let mut previous = [0; 32];
+    for (index, mv) in moves.iter_mut().enumerate() {
+        let message = move_message(session_id, index as u32, &previous, mv);
+        let key = SigningKey::from_bytes(&secret_keys[usize::from(mv.player) - 1]);
+        mv.signature = key.sign(&message).to_bytes().to_vec();
+        previous = move_hash(&message);
+    }
+}
+
+/// Checks that every move carries a valid signature by its player's key, returning the
+/// transcript hash.
+pub(crate) fn verify_moves(
+    session_id: u32,
+    moves: &[Move],
+    keys: &[PublicKey],
+) -> Result<[u8; 32], RuleError> {
+    let mut previous = [0; 32];
+    for (index, mv) in moves.iter().enumerate() {
+        previous = verify_move(session_id, index as u32, &previous, mv, keys)?;
+    }
+    Ok(previous)
+}
+
+/// Checks the signature of move `index`, returning its chained hash.
+pub(crate) fn verify_move(
+    session_id: u32,
+    index: u32,
+    previous: &[u8; 32],
+    mv: &Move,
+    keys: &[PublicKey],
+) -> Result<[u8; 32], RuleError> {
+    if mv.player == 0 || usize::from(mv.player) > keys.len() {
+        return Err(RuleError::TurnOrder);
+    }
+    let player = mv.player;
+    let key = VerifyingKey::from_bytes(&keys[usize::from(player) - 1])
+        .map_err(|_| RuleError::PlayerKey { player })?;
+    let signature =
+        Signature::from_slice(&mv.signature).map_err(|_| RuleError::Signature { player })?;
+
+    let message = move_message(session_id, index, previous, mv);
+    key.verify_strict(&message, &signature)
+        .map_err(|_| RuleError::Signature { player })?;
+    Ok(move_hash(&message))
+}
+
+fn action_code(action: Action) -> u8 {
+    match action {
+        Action::Shot => 0,
+        Action::Bomb => 1,
+        Action::RowStrike => 2,
+        Action::ColumnStrike => 3,
+        Action::Sonar => 4,
+        Action::Resign => 5,
+    }
+}
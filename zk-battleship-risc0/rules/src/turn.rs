@@ -0,0 +1,143 @@
+//! One move at a time, for proving a game turn by turn.
+//!
+//! [`MatchState`] is everything the rules need between two moves. A turn proof shows that
+//! applying one move takes the state with commitment `before` to the state with commitment
+//! `after` ([`TurnJournal`]). The aggregation guest then checks with [`aggregate`] that the
+//! turns chain from the validated boards to the final state, and commits that state's
+//! [`PublicOutput`]: the journal [`play`](crate::play) commits for the whole game.
+
+use crate::bitboard::Grid;
+use crate::{
+    classic_boards, demo_boards, fleet_hashes, mine_boards, transcript, GameInput, GameMode, Move,
+    PublicOutput, Replay, RuleError,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// A game between two moves.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MatchState {
+    /// The rules and boards the game started from; `moves` is empty.
+    setup: GameInput,
+    board_hashes: [[u8; 32]; 2],
+    /// Ship cells of P1 and P2. `u128` holds the boards of every mode.
+    ships: [Vec<u128>; 2],
+    replay: Replay<u128>,
+    /// Chained hash of the last move applied; all zero before the first.
+    transcript_hash: [u8; 32],
+}
+
+impl MatchState {
+    /// Validates the boards of `input`, ignoring its moves.
+    pub fn new(input: &GameInput) -> Result<Self, RuleError> {
+        let ships = match input.mode {
+            GameMode::Demo => {
+                demo_boards(input)?.map(|ships| ships.into_iter().map(u128::from).collect())
+            }
+            GameMode::Classic => classic_boards(input)?,
+        };
+        let mut setup = input.clone();
+        setup.moves.clear();
+        let size = setup.mode.board_size();
+        let grid = Grid::new(size, size);
+        let mines = mine_boards(&grid, &setup, &ships)?;
+        Ok(Self {
+            board_hashes: fleet_hashes(&grid, &ships, &mines, &setup),
+            ships,
+            replay: Replay::new(&setup, mines),
+            transcript_hash: [0; 32],
+            setup,
+        })
+    }
+
+    fn grid(&self) -> Grid<u128> {
+        let size = self.setup.mode.board_size();
+        Grid::new(size, size)
+    }
+
+    pub fn session_id(&self) -> u32 {
+        self.setup.session_id
+    }
+
+    /// Moves applied so far, which is also the index of the next one.
+    pub fn moves_applied(&self) -> u32 {
+        self.replay.total_moves
+    }
+
+    /// Applies the next move, checking its signature when the game has `player_keys`.
+    ///
+    /// After an error the state is left half-updated and must be discarded.
+    pub fn apply(&mut self, mv: &Move) -> Result<(), RuleError> {
+        let session_id = self.setup.session_id;
+        let index = self.replay.total_moves;
+        let previous = &self.transcript_hash;
+        let hash = match &self.setup.player_keys {
+            Some(keys) => transcript::verify_move(session_id, index, previous, mv, keys)?,
+            None => {
+                transcript::move_hash(&transcript::move_message(session_id, index, previous, mv))
+            }
+        };
+        self.replay.step(&self.grid(), &self.ships, mv)?;
+        self.transcript_hash = hash;
+        Ok(())
+    }
+
+    /// sha256 of the whole state, boards included.
+    pub fn commitment(&self) -> [u8; 32] {
+        let encoded = bincode::serialize(self).expect("match state always encodes");
+        Sha256::digest(&encoded).into()
+    }
+
+    /// The journal for the moves applied so far.
+    pub fn output(&self) -> PublicOutput {
+        self.replay
+            .output(&self.setup, self.board_hashes, self.transcript_hash)
+    }
+}
+
+/// Journal of a turn proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TurnJournal {
+    pub session_id: u32,
+    /// Index of the move in the game's `moves`.
+    pub index: u32,
+    /// [`MatchState::commitment`] before the move.
+    pub before: [u8; 32],
+    /// [`MatchState::commitment`] after it.
+    pub after: [u8; 32],
+}
+
+/// Applies `mv` to `state`, returning the journal the turn guest commits.
+pub fn turn(state: &mut MatchState, mv: &Move) -> Result<TurnJournal, RuleError> {
+    let index = state.moves_applied();
+    let before = state.commitment();
+    state.apply(mv)?;
+    Ok(TurnJournal {
+        session_id: state.session_id(),
+        index,
+        before,
+        after: state.commitment(),
+    })
+}
+
+/// Checks that `turns` lead from the validated boards of `setup` to `last`, returning the
+/// journal to commit. The aggregation guest verifies a turn receipt for every entry of
+/// `turns` before trusting it.
+pub fn aggregate(
+    setup: &GameInput,
+    turns: &[TurnJournal],
+    last: &MatchState,
+) -> Result<PublicOutput, RuleError> {
+    let mut commitment = MatchState::new(setup)?.commitment();
+    for (index, turn) in turns.iter().enumerate() {
+        let index = index as u32;
+        if turn.session_id != setup.session_id || turn.index != index || turn.before != commitment {
+            return Err(RuleError::TurnChain { index });
+        }
+        commitment = turn.after;
+    }
+    if last.commitment() != commitment {
+        return Err(RuleError::FinalState);
+    }
+    Ok(last.output())
+}